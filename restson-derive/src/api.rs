@@ -0,0 +1,210 @@
+//! Attribute macro that generates an API client from a trait definition
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    Error, FnArg, GenericArgument, Ident, ItemTrait, LitStr, Pat, PathArguments, ReturnType, TraitItem, TraitItemFn,
+    Type,
+};
+
+/// HTTP methods of the method attributes
+const METHODS: &[(&str, &str)] = &[
+    ("get", "GET"),
+    ("post", "POST"),
+    ("put", "PUT"),
+    ("patch", "PATCH"),
+    ("delete", "DELETE"),
+];
+
+pub(crate) fn expand(item: ItemTrait) -> syn::Result<TokenStream2> {
+    let vis = &item.vis;
+    let name = format_ident!("{}Client", item.ident);
+    let doc = format!("Client of the `{}` API, generated from the trait", item.ident);
+
+    let methods = item
+        .items
+        .iter()
+        .map(|item| match item {
+            TraitItem::Fn(method) => expand_method(vis, method),
+            item => Err(Error::new_spanned(item, "only methods are supported in API traits")),
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Clone)]
+        #vis struct #name {
+            client: ::restson::RestClient,
+        }
+
+        impl #name {
+            /// Create the API client that makes the requests with the client
+            #vis fn new(client: ::restson::RestClient) -> Self {
+                Self { client }
+            }
+
+            /// Client that makes the requests
+            #vis fn client(&self) -> &::restson::RestClient {
+                &self.client
+            }
+
+            #(#methods)*
+        }
+    })
+}
+
+/// Role of a method parameter in the request
+enum Role {
+    Path,
+    Query,
+    Body,
+}
+
+fn expand_method(vis: &syn::Visibility, method: &TraitItemFn) -> syn::Result<TokenStream2> {
+    let mut sig = method.sig.clone();
+    if sig.asyncness.is_none() {
+        return Err(Error::new_spanned(&sig, "API methods must be async"));
+    }
+
+    let mut http_method = None;
+    let mut attrs = Vec::new();
+    for attr in &method.attrs {
+        match METHODS.iter().find(|(name, _)| attr.path().is_ident(name)) {
+            Some((_, method)) => {
+                let template: LitStr = attr.parse_args()?;
+                http_method = Some((*method, template));
+            }
+            None => attrs.push(attr),
+        }
+    }
+    let (http_method, template) = http_method
+        .ok_or_else(|| Error::new_spanned(&sig.ident, "missing #[get(\"...\")], #[post(\"...\")], #[put(\"...\")], #[patch(\"...\")] or #[delete(\"...\")] attribute"))?;
+
+    // Parameters by their role, with the role attributes removed
+    let mut params = Vec::new();
+    let mut has_receiver = false;
+    for input in sig.inputs.iter_mut() {
+        let input = match input {
+            FnArg::Receiver(_) => {
+                has_receiver = true;
+                continue;
+            }
+            FnArg::Typed(input) => input,
+        };
+        let name = match *input.pat {
+            Pat::Ident(ref pat) => pat.ident.clone(),
+            ref pat => return Err(Error::new_spanned(pat, "API method parameters must be identifiers")),
+        };
+
+        let mut role = Role::Path;
+        input.attrs.retain(|attr| {
+            if attr.path().is_ident("query") {
+                role = Role::Query;
+                false
+            } else if attr.path().is_ident("body") {
+                role = Role::Body;
+                false
+            } else {
+                true
+            }
+        });
+        params.push((name, role));
+    }
+    if !has_receiver {
+        return Err(Error::new_spanned(&sig, "API methods must take &self"));
+    }
+
+    let (format, path_params) = parse_template(&template)?;
+    for (name, role) in &params {
+        if matches!(role, Role::Path) && !path_params.contains(name) {
+            return Err(Error::new_spanned(name, "parameter is not in the path; mark it #[query] or #[body]"));
+        }
+    }
+    for name in &path_params {
+        if !params.iter().any(|(param, role)| param == name && matches!(role, Role::Path)) {
+            return Err(Error::new_spanned(&template, format!("no parameter `{}` for the path", name)));
+        }
+    }
+
+    let queries: Vec<_> = params.iter().filter(|(_, role)| matches!(role, Role::Query)).map(|(name, _)| name).collect();
+    let mut bodies = params.iter().filter(|(_, role)| matches!(role, Role::Body)).map(|(name, _)| name);
+    let body = match (bodies.next(), bodies.next()) {
+        (None, _) => quote!(::std::option::Option::None::<&()>),
+        (Some(body), None) => quote!(::std::option::Option::Some(&#body)),
+        (Some(_), Some(body)) => return Err(Error::new_spanned(body, "API methods can have only one #[body] parameter")),
+    };
+
+    let call = match ok_type(&sig.output)? {
+        Type::Tuple(ref tuple) if tuple.elems.is_empty() => quote! {
+            self.client.__api_call_unit(#http_method, &__restson_path, &__restson_query, #body).await.map(|_| ())
+        },
+        ty => quote! {
+            self.client
+                .__api_call::<_, #ty>(#http_method, &__restson_path, &__restson_query, #body)
+                .await
+                .map(::restson::Response::into_inner)
+        },
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            let __restson_path = ::std::format!(#format, #(#path_params = ::restson::encode_path_segment(&#path_params)?),*);
+            #[allow(unused_mut)]
+            let mut __restson_query: ::std::vec::Vec<(::std::string::String, ::std::string::String)> = ::std::vec::Vec::new();
+            #({
+                use ::restson::ToQuery as _;
+                __restson_query.extend(#queries.to_query());
+            })*
+            #call
+        }
+    })
+}
+
+/// Type of the value returned in `Ok`, e.g. `Repo` of `Result<Repo, Error>`
+fn ok_type(output: &ReturnType) -> syn::Result<Type> {
+    let error = || Error::new_spanned(output, "API methods must return Result<T, restson::Error>");
+    let ty = match output {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => return Err(error()),
+    };
+    let segment = match **ty {
+        Type::Path(ref path) => path.path.segments.last().ok_or_else(error)?,
+        _ => return Err(error()),
+    };
+    match segment.arguments {
+        PathArguments::AngleBracketed(ref args) if segment.ident == "Result" => match args.args.first() {
+            Some(GenericArgument::Type(ty)) => Ok(ty.clone()),
+            _ => Err(error()),
+        },
+        _ => Err(error()),
+    }
+}
+
+/// Parse the path template with `{name}` parameters into format string with
+/// named arguments, and the names of the parameters
+fn parse_template(template: &LitStr) -> syn::Result<(LitStr, Vec<Ident>)> {
+    let value = template.value();
+    let mut format = String::new();
+    let mut params = Vec::new();
+    let mut rest = value.as_str();
+
+    while let Some(start) = rest.find(['{', '}']) {
+        format.push_str(&rest[..start]);
+        let end = match rest[start..].find('}') {
+            Some(end) if rest.as_bytes()[start] == b'{' => start + end,
+            _ => return Err(Error::new_spanned(template, "unmatched brace in path template")),
+        };
+        let name = rest[start + 1..end].trim();
+        let name: Ident = syn::parse_str(name)
+            .map_err(|_| Error::new_spanned(template, format!("invalid parameter name `{}`", name)))?;
+        format.push_str(&format!("{{{}}}", name));
+        if !params.contains(&name) {
+            params.push(name);
+        }
+        rest = &rest[end + 1..];
+    }
+    format.push_str(rest);
+
+    Ok((LitStr::new(&format, template.span()), params))
+}
@@ -0,0 +1,165 @@
+//! Derive macro for the `RestPath` trait of restson, and attribute macro for
+//! generating API clients from traits.
+//!
+//! The path of the endpoint is given as template in `rest_path` attribute,
+//! with the parameters and their types in braces. Each attribute generates
+//! one `RestPath` implementation, whose parameter is `()` without
+//! parameters, the type of the only parameter, or a tuple of the types.
+//! The parameters are percent-encoded as path segments, and `.` and `..` are
+//! rejected with `Error::InvalidValue`.
+//!
+//! ```ignore
+//! #[derive(Deserialize, RestPath)]
+//! #[rest_path("devices/{id: u32}/sensors/{name: &str}")]
+//! #[rest_path("devices/{id: u32}/sensors")]
+//! struct Sensor {
+//!     name: String,
+//! }
+//!
+//! // GET devices/1234/sensors/temp%2Fin
+//! let data = client.get::<_, Sensor>((1234, "temp/in")).await?;
+//! ```
+
+mod api;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Error, GenericParam, Ident, ItemTrait, Lifetime, LifetimeParam, LitStr, Type};
+
+#[proc_macro_derive(RestPath, attributes(rest_path))]
+pub fn derive_rest_path(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+/// Generate API client struct `<Trait>Client` from the trait, with a method
+/// for each method of the trait.
+///
+/// The methods are async and return `Result<T, restson::Error>`. Each method
+/// has `#[get("path")]`, `#[post("path")]`, `#[put("path")]`,
+/// `#[patch("path")]` or `#[delete("path")]` attribute. Parameters in braces
+/// in the path are the method parameters with the same name, percent-encoded
+/// (`.` and `..` are rejected).
+/// Other parameters are marked `#[query]` for query parameters that implement
+/// `ToQuery`, or `#[body]` for the serialized request body.
+///
+/// ```ignore
+/// #[restson::api]
+/// pub trait GitHub {
+///     #[get("repos/{owner}/{repo}")]
+///     async fn repo(&self, owner: &str, repo: &str) -> Result<Repo, Error>;
+///
+///     #[get("repos/{owner}/{repo}/issues")]
+///     async fn issues(&self, owner: &str, repo: &str, #[query] query: &QueryPairs) -> Result<Vec<Issue>, Error>;
+///
+///     #[post("repos/{owner}/{repo}/issues")]
+///     async fn create_issue(&self, owner: &str, repo: &str, #[body] issue: &NewIssue) -> Result<Issue, Error>;
+/// }
+///
+/// let github = GitHubClient::new(RestClient::new("https://api.github.com")?);
+/// let repo = github.repo("spietika", "restson-rust").await?;
+/// ```
+#[proc_macro_attribute]
+pub fn api(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return Error::new(Span::call_site(), "api attribute takes no arguments").into_compile_error().into();
+    }
+    let item = parse_macro_input!(item as ItemTrait);
+    api::expand(item).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let mut impls = Vec::new();
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("rest_path")) {
+        let template: LitStr = attr.parse_args()?;
+        impls.push(expand_template(input, &template)?);
+    }
+
+    if impls.is_empty() {
+        return Err(Error::new_spanned(&input.ident, "missing #[rest_path(\"...\")] attribute"));
+    }
+    Ok(quote!(#(#impls)*))
+}
+
+/// Parameter of the path template
+struct Param {
+    name: Ident,
+    ty: Type,
+}
+
+/// Generate `RestPath` implementation for the path template
+fn expand_template(input: &DeriveInput, template: &LitStr) -> syn::Result<TokenStream2> {
+    let (format, params) = parse_template(template)?;
+
+    // References in the parameter types get the lifetime of the implementation
+    let lifetime = Lifetime::new("'restson", Span::call_site());
+    let mut borrowed = false;
+    let types: Vec<Type> = params
+        .iter()
+        .map(|param| match param.ty {
+            Type::Reference(ref reference) if reference.lifetime.is_none() => {
+                let mut reference = reference.clone();
+                reference.lifetime = Some(lifetime.clone());
+                borrowed = true;
+                Type::Reference(reference)
+            }
+            ref ty => ty.clone(),
+        })
+        .collect();
+
+    let mut generics = input.generics.clone();
+    if borrowed {
+        generics.params.insert(0, GenericParam::Lifetime(LifetimeParam::new(lifetime)));
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let names: Vec<&Ident> = params.iter().map(|param| &param.name).collect();
+    let (pattern, param_type) = match names.len() {
+        0 => (quote!(_), quote!(())),
+        1 => (quote!(#(#names)*), quote!(#(#types)*)),
+        _ => (quote!((#(#names),*)), quote!((#(#types),*))),
+    };
+
+    let ident = &input.ident;
+    let format = LitStr::new(&format, template.span());
+    Ok(quote! {
+        impl #impl_generics ::restson::RestPath<#param_type> for #ident #ty_generics #where_clause {
+            fn get_path(#pattern: #param_type) -> ::std::result::Result<::std::string::String, ::restson::Error> {
+                ::std::result::Result::Ok(::std::format!(#format, #(::restson::encode_path_segment(&#names)?),*))
+            }
+        }
+    })
+}
+
+/// Parse the path template into format string and parameters
+fn parse_template(template: &LitStr) -> syn::Result<(String, Vec<Param>)> {
+    let value = template.value();
+    let mut format = String::new();
+    let mut params = Vec::new();
+    let mut rest = value.as_str();
+
+    while let Some(start) = rest.find(['{', '}']) {
+        format.push_str(&rest[..start]);
+        let end = match rest[start..].find('}') {
+            Some(end) if rest.as_bytes()[start] == b'{' => start + end,
+            _ => return Err(Error::new_spanned(template, "unmatched brace in path template")),
+        };
+
+        let (name, ty) = rest[start + 1..end]
+            .split_once(':')
+            .ok_or_else(|| Error::new_spanned(template, "path parameter must be given as {name: Type}"))?;
+        let name = syn::parse_str(name.trim())
+            .map_err(|_| Error::new_spanned(template, format!("invalid parameter name `{}`", name.trim())))?;
+        let ty = syn::parse_str(ty.trim())
+            .map_err(|_| Error::new_spanned(template, format!("invalid parameter type `{}`", ty.trim())))?;
+        params.push(Param { name, ty });
+
+        format.push_str("{}");
+        rest = &rest[end + 1..];
+    }
+    format.push_str(rest);
+
+    Ok((format, params))
+}
@@ -0,0 +1,414 @@
+//! Generates restson API clients from OpenAPI 3 specifications.
+//!
+//! The generator is run from a build script. It writes Rust code with a
+//! struct or enum for each schema in `components/schemas`, and a trait with
+//! a method for each operation in `paths`. The trait uses the
+//! `#[restson::api]` attribute, which generates the `<Name>Client` struct
+//! that wraps `RestClient`. The crate that includes the code needs `restson`
+//! with the `derive` feature, `serde` with the `derive` feature and
+//! `serde_json`.
+//!
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!     let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("vendor.rs");
+//!     restson_openapi::generate_file("Vendor", "vendor.yaml", out).unwrap();
+//!     println!("cargo:rerun-if-changed=vendor.yaml");
+//! }
+//! ```
+//!
+//! ```ignore
+//! // src/vendor.rs
+//! include!(concat!(env!("OUT_DIR"), "/vendor.rs"));
+//!
+//! let vendor = VendorClient::new(RestClient::new("https://api.example.com")?);
+//! ```
+//!
+//! Path parameters are method arguments of their own, query parameters are
+//! given as `QueryPairs` and the JSON request body as reference to the
+//! generated type. The response type is the JSON schema of the first 2xx
+//! response, or `()` if it has no JSON content. Only local references
+//! (`#/components/...`) are supported, and inline object schemas are
+//! represented as `serde_json::Value`.
+
+use serde_json::Value;
+use std::path::Path;
+use std::{error, fmt, fs, io};
+
+/// Errors of the generator
+#[derive(Debug)]
+pub enum Error {
+    /// Reading the specification or writing the generated code failed
+    Io(io::Error),
+    /// The specification could not be parsed or it is not supported
+    Spec(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "IO error: {}", e),
+            Error::Spec(e) => write!(f, "Invalid specification: {}", e),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// HTTP methods of the operations that are generated
+const METHODS: &[&str] = &["get", "post", "put", "patch", "delete"];
+
+/// Keywords that can not be used as identifiers
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self", "static",
+    "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do",
+    "final", "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// Read the OpenAPI specification (JSON or YAML) from the `spec` file, and
+/// write the generated code to the `out` file. The API trait is named
+/// `name` and the client `<name>Client`.
+pub fn generate_file<P: AsRef<Path>, Q: AsRef<Path>>(name: &str, spec: P, out: Q) -> Result<(), Error> {
+    let spec = fs::read_to_string(spec)?;
+    fs::write(out, generate(name, &spec)?)?;
+    Ok(())
+}
+
+/// Generate code from the OpenAPI specification given as JSON or YAML. The
+/// API trait is named `name` and the client `<name>Client`.
+pub fn generate(name: &str, spec: &str) -> Result<String, Error> {
+    let spec: Value = match serde_json::from_str(spec) {
+        Ok(spec) => spec,
+        Err(_) => serde_yaml::from_str(spec).map_err(|e| Error::Spec(e.to_string()))?,
+    };
+    if !spec["openapi"].as_str().is_some_and(|version| version.starts_with("3.")) {
+        return Err(Error::Spec("only OpenAPI 3 specifications are supported".to_string()));
+    }
+
+    let generator = Generator { spec: &spec };
+    let mut out = String::from("// Generated by restson-openapi. Do not edit.\n");
+    if let Some(schemas) = spec["components"]["schemas"].as_object() {
+        for (name, schema) in schemas {
+            out.push('\n');
+            out.push_str(&generator.schema(name, schema)?);
+        }
+    }
+    out.push('\n');
+    out.push_str(&generator.api(name)?);
+    Ok(out)
+}
+
+struct Generator<'a> {
+    spec: &'a Value,
+}
+
+impl Generator<'_> {
+    /// Follow the local reference, if the value is one
+    fn resolve<'v>(&'v self, value: &'v Value) -> Result<&'v Value, Error> {
+        match value["$ref"].as_str() {
+            Some(reference) => {
+                let pointer = reference
+                    .strip_prefix('#')
+                    .ok_or_else(|| Error::Spec(format!("unsupported reference `{}`", reference)))?;
+                self.spec
+                    .pointer(pointer)
+                    .ok_or_else(|| Error::Spec(format!("unresolved reference `{}`", reference)))
+            }
+            None => Ok(value),
+        }
+    }
+
+    /// Type definition of the named schema
+    fn schema(&self, name: &str, schema: &Value) -> Result<String, Error> {
+        let ident = pascal_case(name);
+        let mut out = doc_comment("", schema["description"].as_str());
+
+        let (ty, _) = schema_type(schema);
+        match (ty, schema["enum"].as_array()) {
+            (Some("string"), Some(values)) => {
+                let values: Vec<&str> = values.iter().filter_map(Value::as_str).collect();
+                out.push_str("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize)]\n");
+                out.push_str(&format!("pub enum {} {{\n", ident));
+                for value in &values {
+                    out.push_str(&format!("    #[serde(rename = {:?})]\n    {},\n", value, variant(value)));
+                }
+                out.push_str("}\n\n");
+                out.push_str(&format!("impl ::std::fmt::Display for {} {{\n", ident));
+                out.push_str("    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {\n");
+                out.push_str("        f.write_str(match self {\n");
+                for value in &values {
+                    out.push_str(&format!("            {}::{} => {:?},\n", ident, variant(value), value));
+                }
+                out.push_str("        })\n    }\n}\n");
+            }
+            (Some("object") | None, _) if schema["properties"].is_object() => {
+                let required: Vec<&str> = schema["required"]
+                    .as_array()
+                    .map(|required| required.iter().filter_map(Value::as_str).collect())
+                    .unwrap_or_default();
+
+                out.push_str("#[derive(Debug, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]\n");
+                out.push_str(&format!("pub struct {} {{\n", ident));
+                for (name, property) in schema["properties"].as_object().into_iter().flatten() {
+                    out.push_str(&doc_comment("    ", property["description"].as_str()));
+                    let field = snake_case(name);
+                    if field != *name {
+                        out.push_str(&format!("    #[serde(rename = {:?})]\n", name));
+                    }
+                    let mut ty = self.rust_type(property)?;
+                    if !required.contains(&name.as_str()) && !ty.starts_with("Option<") {
+                        ty = format!("Option<{}>", ty);
+                    }
+                    if ty.starts_with("Option<") {
+                        out.push_str("    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n");
+                    }
+                    out.push_str(&format!("    pub {}: {},\n", field, ty));
+                }
+                out.push_str("}\n");
+            }
+            _ => out.push_str(&format!("pub type {} = {};\n", ident, self.rust_type(schema)?)),
+        }
+        Ok(out)
+    }
+
+    /// Rust type of the schema
+    fn rust_type(&self, schema: &Value) -> Result<String, Error> {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference
+                .strip_prefix("#/components/schemas/")
+                .ok_or_else(|| Error::Spec(format!("unsupported schema reference `{}`", reference)))?;
+            return Ok(pascal_case(name));
+        }
+
+        let (ty, nullable) = schema_type(schema);
+        let format = schema["format"].as_str();
+        let ty = match ty {
+            Some("string") => "String".to_string(),
+            Some("integer") if format == Some("int32") => "i32".to_string(),
+            Some("integer") => "i64".to_string(),
+            Some("number") if format == Some("float") => "f32".to_string(),
+            Some("number") => "f64".to_string(),
+            Some("boolean") => "bool".to_string(),
+            Some("array") => format!("Vec<{}>", self.rust_type(&schema["items"])?),
+            Some("object") if schema["additionalProperties"].is_object() && schema["properties"].is_null() => format!(
+                "::std::collections::HashMap<String, {}>",
+                self.rust_type(&schema["additionalProperties"])?
+            ),
+            _ => "::serde_json::Value".to_string(),
+        };
+        Ok(if nullable { format!("Option<{}>", ty) } else { ty })
+    }
+
+    /// Type of a path parameter, which has to implement `Display`
+    fn path_type(&self, schema: &Value) -> Result<String, Error> {
+        let target = self.resolve(schema)?;
+        match schema_type(target).0 {
+            Some("integer" | "number" | "boolean") => self.rust_type(schema),
+            Some("string") if target["enum"].is_array() && !schema["$ref"].is_null() => self.rust_type(schema),
+            _ => Ok("&str".to_string()),
+        }
+    }
+
+    /// Type of the JSON content of the request body or response
+    fn content_type(&self, value: &Value) -> Result<Option<String>, Error> {
+        let content = &self.resolve(value)?["content"];
+        match content.as_object().and_then(|content| content.iter().find(|(mime, _)| mime.contains("json"))) {
+            Some((_, media)) if !media["schema"].is_null() => self.rust_type(&media["schema"]).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// API trait with a method for each operation
+    fn api(&self, name: &str) -> Result<String, Error> {
+        let mut out = doc_comment("", self.spec["info"]["title"].as_str());
+        out.push_str("#[::restson::api]\n");
+        out.push_str(&format!("pub trait {} {{\n", pascal_case(name)));
+
+        let mut first = true;
+        for (path, item) in self.spec["paths"].as_object().into_iter().flatten() {
+            for method in METHODS {
+                let operation = &item[*method];
+                if !operation.is_object() {
+                    continue;
+                }
+                if !first {
+                    out.push('\n');
+                }
+                first = false;
+                out.push_str(&self.operation(path, method, item, operation)?);
+            }
+        }
+        out.push_str("}\n");
+        Ok(out)
+    }
+
+    /// Method of the API trait for the operation
+    fn operation(&self, path: &str, method: &str, item: &Value, operation: &Value) -> Result<String, Error> {
+        let name = match operation["operationId"].as_str() {
+            Some(id) => snake_case(id),
+            None => snake_case(&format!("{} {}", method, path.replace(['{', '}'], ""))),
+        };
+
+        let mut template = path.trim_start_matches('/').to_string();
+        let mut args = vec!["&self".to_string()];
+        let mut queries = Vec::new();
+
+        let parameters = item["parameters"].as_array().into_iter().flatten();
+        for parameter in parameters.chain(operation["parameters"].as_array().into_iter().flatten()) {
+            let parameter = self.resolve(parameter)?;
+            let name = parameter["name"]
+                .as_str()
+                .ok_or_else(|| Error::Spec(format!("parameter without name in `{}`", path)))?;
+            match parameter["in"].as_str() {
+                Some("path") => {
+                    let arg = snake_case(name);
+                    template = template.replace(&format!("{{{}}}", name), &format!("{{{}}}", arg));
+                    args.push(format!("{}: {}", arg, self.path_type(&parameter["schema"])?));
+                }
+                Some("query") => queries.push(format!("`{}`", name)),
+                _ => (),
+            }
+        }
+        if !queries.is_empty() {
+            args.push("#[query] query: &::restson::QueryPairs".to_string());
+        }
+        if !operation["requestBody"].is_null() {
+            if let Some(ty) = self.content_type(&operation["requestBody"])? {
+                args.push(format!("#[body] body: &{}", ty));
+            }
+        }
+
+        let mut output = "()".to_string();
+        for (status, response) in operation["responses"].as_object().into_iter().flatten() {
+            if status.starts_with('2') {
+                if let Some(ty) = self.content_type(response)? {
+                    output = ty;
+                }
+                break;
+            }
+        }
+
+        let mut out = String::new();
+        let summary = operation["summary"].as_str().or_else(|| operation["description"].as_str());
+        out.push_str(&doc_comment("    ", summary));
+        if !queries.is_empty() {
+            if summary.is_some() {
+                out.push_str("    ///\n");
+            }
+            out.push_str(&format!("    /// Query parameters: {}\n", queries.join(", ")));
+        }
+        out.push_str(&format!("    #[{}({:?})]\n", method, template));
+        out.push_str(&format!(
+            "    async fn {}({}) -> ::std::result::Result<{}, ::restson::Error>;\n",
+            name,
+            args.join(", "),
+            output
+        ));
+        Ok(out)
+    }
+}
+
+/// Type of the schema and whether it is nullable, as `nullable: true` in
+/// OpenAPI 3.0 or `type: [T, "null"]` in OpenAPI 3.1
+fn schema_type(schema: &Value) -> (Option<&str>, bool) {
+    let nullable = schema["nullable"].as_bool().unwrap_or(false);
+    match schema["type"] {
+        Value::String(ref ty) => (Some(ty), nullable),
+        Value::Array(ref types) => {
+            let ty = types.iter().filter_map(Value::as_str).find(|ty| *ty != "null");
+            (ty, nullable || types.iter().any(|ty| ty == "null"))
+        }
+        _ => (None, nullable),
+    }
+}
+
+/// Doc comment lines with the indentation
+fn doc_comment(indent: &str, text: Option<&str>) -> String {
+    text.into_iter()
+        .flat_map(str::lines)
+        .map(|line| match line.trim_end() {
+            "" => format!("{}///\n", indent),
+            line => format!("{}/// {}\n", indent, line),
+        })
+        .collect()
+}
+
+/// Words of the name, split at non-alphanumeric characters and case changes
+fn words(name: &str) -> Vec<String> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        let prev = if i > 0 { chars[i - 1] } else { ' ' };
+        let next = chars.get(i + 1).copied().unwrap_or(' ');
+        let boundary = c.is_ascii_uppercase()
+            && (prev.is_ascii_lowercase() || prev.is_ascii_digit() || (prev.is_ascii_uppercase() && next.is_ascii_lowercase()));
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+/// Identifier in snake case, e.g. `owner_id` of `ownerId`
+fn snake_case(name: &str) -> String {
+    let ident = words(name).join("_").to_ascii_lowercase();
+    escape(ident, "value")
+}
+
+/// Words of the name joined in Pascal case
+fn pascal_words(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|word| {
+            let word = word.to_ascii_lowercase();
+            word[..1].to_ascii_uppercase() + &word[1..]
+        })
+        .collect()
+}
+
+/// Identifier in Pascal case, e.g. `PetStatus` of `pet_status`
+fn pascal_case(name: &str) -> String {
+    escape(pascal_words(name), "Value")
+}
+
+/// Enum variant of the string value, e.g. `V404` of `404`
+fn variant(value: &str) -> String {
+    let ident = pascal_words(value);
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("V{}", ident)
+    } else {
+        escape(ident, "Empty")
+    }
+}
+
+/// Make the identifier valid: keywords get `_` suffix and identifiers that
+/// start with a digit get `_` prefix
+fn escape(ident: String, empty: &str) -> String {
+    if ident.is_empty() {
+        empty.to_string()
+    } else if KEYWORDS.contains(&ident.as_str()) {
+        ident + "_"
+    } else if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", ident)
+    } else {
+        ident
+    }
+}
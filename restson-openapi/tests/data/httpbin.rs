@@ -0,0 +1,55 @@
+// Generated by restson-openapi. Do not edit.
+
+#[derive(Debug, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct Anything {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<::std::collections::HashMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json: Option<Data>,
+    pub method: String,
+    pub url: String,
+}
+
+/// Data sent in the request
+#[derive(Debug, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct Data {
+    /// Time of creation
+    #[serde(rename = "createdAt")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    pub data: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize)]
+pub enum State {
+    #[serde(rename = "open")]
+    Open,
+    #[serde(rename = "closed")]
+    Closed,
+}
+
+impl ::std::fmt::Display for State {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        f.write_str(match self {
+            State::Open => "open",
+            State::Closed => "closed",
+        })
+    }
+}
+
+/// httpbin.org
+#[::restson::api]
+pub trait HttpBin {
+    /// Returns the request data
+    ///
+    /// Query parameters: `state`
+    #[get("anything/{id}")]
+    async fn get_anything(&self, id: i64, #[query] query: &::restson::QueryPairs) -> ::std::result::Result<Anything, ::restson::Error>;
+
+    /// Returns the request data
+    #[post("anything/{id}")]
+    async fn post_anything(&self, id: i64, #[body] body: &Data) -> ::std::result::Result<Anything, ::restson::Error>;
+
+    #[get("status/{code}")]
+    async fn get_status_code(&self, code: i32) -> ::std::result::Result<(), ::restson::Error>;
+}
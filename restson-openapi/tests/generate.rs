@@ -0,0 +1,43 @@
+use restson::{Error, QueryPairs, RestClient};
+use restson_openapi::generate;
+
+mod httpbin {
+    include!("data/httpbin.rs");
+}
+
+use httpbin::{Data, HttpBinClient, State};
+
+#[test]
+fn generate_yaml() {
+    let spec = include_str!("data/httpbin.yaml");
+    assert_eq!(generate("HttpBin", spec).unwrap(), include_str!("data/httpbin.rs"));
+}
+
+#[test]
+fn generate_unsupported() {
+    let spec = r#"{"swagger": "2.0", "info": {"title": "old", "version": "1"}, "paths": {}}"#;
+    match generate("Old", spec) {
+        Err(restson_openapi::Error::Spec(_)) => (),
+        _ => panic!("Expected unsupported specification"),
+    };
+}
+
+#[tokio::test]
+async fn generated_client() {
+    let api = HttpBinClient::new(RestClient::new("https://httpbin.org").unwrap());
+
+    let query = QueryPairs::new().push("state", State::Open);
+    let data = api.get_anything(1234, &query).await.unwrap();
+    assert_eq!(data.method, "GET");
+    assert_eq!(data.args.unwrap().get("state").map(String::as_str), Some("open"));
+
+    let body = Data { data: "test data".to_string(), created_at: None };
+    let data = api.post_anything(1234, &body).await.unwrap();
+    assert_eq!(data.method, "POST");
+    assert_eq!(data.json, Some(body));
+
+    match api.get_status_code(404).await {
+        Err(Error::HttpError(404, _, _)) => (),
+        _ => panic!("Expected 404 error status"),
+    };
+}
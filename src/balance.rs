@@ -0,0 +1,213 @@
+//! Client-side load balancing across hosts
+
+use crate::Error;
+use hyper::Uri;
+use log::{debug, warn};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// How the host of each request is chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BalanceStrategy {
+    /// Take the healthy hosts in turn
+    #[default]
+    RoundRobin,
+    /// Take the healthy host with the fewest requests in flight, in turn if
+    /// there are several
+    LeastInFlight,
+}
+
+/// Configuration of client-side load balancing across a set of base URLs,
+/// e.g. stateless API pods without a load balancer in front of them.
+///
+/// Requests whose URL starts with the base URL of the client are sent to one
+/// of the base URLs of the load balancer instead, with the base URL part
+/// replaced. A host is unhealthy after `failure_threshold` consecutive
+/// requests to it have failed with 5xx status, transport error or timeout,
+/// and it is skipped for `unhealthy_duration`. If all hosts are unhealthy,
+/// the requests are sent to all of them in turn.
+///
+/// # Examples
+/// ```ignore
+/// let client = RestClient::builder()
+///     .load_balancer(LoadBalancer::new(&["http://10.0.0.1/api/", "http://10.0.0.2/api/"]).strategy(BalanceStrategy::LeastInFlight))
+///     .build("http://10.0.0.1/api/")?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct LoadBalancer {
+    urls: Vec<String>,
+    strategy: BalanceStrategy,
+    failure_threshold: u32,
+    unhealthy_duration: Duration,
+}
+
+impl LoadBalancer {
+    /// Create load balancer across the base URLs
+    pub fn new(urls: &[&str]) -> Self {
+        Self {
+            urls: urls.iter().map(|url| url.to_string()).collect(),
+            strategy: BalanceStrategy::default(),
+            failure_threshold: 3,
+            unhealthy_duration: Duration::from_secs(30),
+        }
+    }
+
+    /// Set how the host of each request is chosen
+    ///
+    /// Default is `BalanceStrategy::RoundRobin`
+    pub fn strategy(mut self, strategy: BalanceStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Set number of consecutive failed requests after which the host is
+    /// unhealthy
+    ///
+    /// Default is 3
+    pub fn failure_threshold(mut self, failures: u32) -> Self {
+        self.failure_threshold = failures;
+        self
+    }
+
+    /// Set time that an unhealthy host is skipped
+    ///
+    /// Default is 30 seconds
+    pub fn unhealthy_duration(mut self, duration: Duration) -> Self {
+        self.unhealthy_duration = duration;
+        self
+    }
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    failures: u32,
+    unhealthy_until: Option<Instant>,
+    failed_check: bool,
+}
+
+#[derive(Debug)]
+struct Host {
+    url: String,
+    in_flight: AtomicUsize,
+    health: Mutex<Health>,
+}
+
+impl Host {
+    fn is_healthy(&self, now: Instant) -> bool {
+        let health = self.health.lock().unwrap();
+        !health.failed_check && health.unhealthy_until.is_none_or(|until| now >= until)
+    }
+}
+
+/// State of the hosts of the load balancer, shared by the clones of the client
+#[derive(Debug)]
+pub(crate) struct Balancer {
+    config: LoadBalancer,
+    hosts: Vec<Host>,
+    next: AtomicUsize,
+}
+
+impl Balancer {
+    pub(crate) fn new(config: LoadBalancer) -> Result<Self, Error> {
+        if config.urls.is_empty() {
+            return Err(Error::InvalidValue);
+        }
+        let hosts = config
+            .urls
+            .iter()
+            .map(|url| {
+                let url = Url::parse(url).map_err(|_| Error::UrlError)?;
+                Ok(Host { url: url.into(), in_flight: AtomicUsize::new(0), health: Mutex::default() })
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(Self { config, hosts, next: AtomicUsize::new(0) })
+    }
+
+    /// Choose the host for the request to the URI, and replace the base URL
+    /// in the URI with the base URL of the host. Returns `None` if the URI
+    /// does not start with the base URL.
+    pub(crate) fn choose(&self, base: &Url, uri: &mut Uri) -> Option<InFlight<'_>> {
+        let target = uri.to_string();
+        let rest = target.strip_prefix(base.as_str())?;
+
+        let now = Instant::now();
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let order = (0..self.hosts.len()).map(|i| (start + i) % self.hosts.len());
+        let healthy: Vec<usize> = order.clone().filter(|&i| self.hosts[i].is_healthy(now)).collect();
+        let candidates = if healthy.is_empty() { order.collect() } else { healthy };
+
+        let index = match self.config.strategy {
+            BalanceStrategy::RoundRobin => candidates[0],
+            BalanceStrategy::LeastInFlight => candidates
+                .iter()
+                .copied()
+                .min_by_key(|&i| self.hosts[i].in_flight.load(Ordering::Relaxed))
+                .unwrap_or(candidates[0]),
+        };
+
+        let host = &self.hosts[index];
+        *uri = format!("{}{}", host.url, rest).parse().ok()?;
+        host.in_flight.fetch_add(1, Ordering::Relaxed);
+        Some(InFlight { host, balancer: self })
+    }
+
+    /// Base URLs of the hosts
+    pub(crate) fn urls(&self) -> impl Iterator<Item = &str> {
+        self.hosts.iter().map(|host| host.url.as_str())
+    }
+
+    /// Set result of the health check of the host. A host that fails the
+    /// check is skipped until it passes the check or a request to it
+    /// succeeds.
+    pub(crate) fn set_health(&self, index: usize, healthy: bool) {
+        let host = &self.hosts[index];
+        let mut health = host.health.lock().unwrap();
+        if healthy {
+            health.failures = 0;
+            health.unhealthy_until = None;
+        } else if !health.failed_check {
+            warn!("host {} failed health check", host.url);
+        }
+        health.failed_check = !healthy;
+    }
+
+    fn record(&self, host: &Host, success: bool) {
+        let mut health = host.health.lock().unwrap();
+        if success {
+            if health.unhealthy_until.take().is_some() || health.failed_check {
+                debug!("host {} is healthy again", host.url);
+            }
+            health.failures = 0;
+            health.failed_check = false;
+            return;
+        }
+
+        health.failures += 1;
+        if health.failures >= self.config.failure_threshold {
+            warn!("too many failed requests, skipping host {} for {:?}", host.url, self.config.unhealthy_duration);
+            health.unhealthy_until = Some(Instant::now() + self.config.unhealthy_duration);
+        }
+    }
+}
+
+/// Request in flight to a host of the load balancer. Dropping it without
+/// recording the result, e.g. when the request is cancelled, counts as
+/// neither success nor failure.
+pub(crate) struct InFlight<'a> {
+    host: &'a Host,
+    balancer: &'a Balancer,
+}
+
+impl InFlight<'_> {
+    pub(crate) fn record(self, success: bool) {
+        self.balancer.record(self.host, success);
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.host.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
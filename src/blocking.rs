@@ -1,21 +1,179 @@
 //! Blocking variant of the `RestClient`
 
-use crate::{Error, Query, Response, RestClient as AsyncRestClient, RestPath};
+use crate::{hal, jsonapi, BodyFormat, Conditional, Error, Event, EventStream, HostHealth, Multipart, Paginated, Pagination, Response, RestClient as AsyncRestClient, RestPath, ToQuery, TypedError};
+use bytes::{Buf, Bytes};
+use futures::StreamExt;
+#[cfg(feature = "websocket")]
+use futures::SinkExt;
 use hyper::header::HeaderValue;
+use hyper::body::HttpBody;
+use hyper::Method;
+use std::io::{self, Read, Write};
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::sync::OnceLock;
 use std::{convert::TryFrom, time::Duration};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 use tokio::runtime::{Builder, Runtime};
 
 /// REST client to make HTTP GET and POST requests. Blocking version.
+///
+/// The requests are run in the calling thread on a single-threaded tokio
+/// runtime, which is created when the first blocking client is created and
+/// shared by all blocking clients. The blocking client depends on tokio like
+/// the async client; there is no backend without tokio.
 pub struct RestClient {
     inner_client: AsyncRestClient,
-    runtime: Runtime,
+    runtime: &'static Runtime,
+}
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// Runtime shared by the blocking clients, created on first use
+fn shared_runtime() -> io::Result<&'static Runtime> {
+    if let Some(runtime) = RUNTIME.get() {
+        return Ok(runtime);
+    }
+    let runtime = Builder::new_current_thread().enable_all().build()?;
+    Ok(RUNTIME.get_or_init(|| runtime))
+}
+
+/// Iterator over the Server-Sent Events, returned by `RestClient::sse`.
+/// Blocking version of `EventStream`.
+pub struct Events<'a, T> {
+    stream: EventStream<T>,
+    runtime: &'a Runtime,
+}
+
+impl<T> Iterator for Events<'_, T> {
+    type Item = Result<Event<T>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+/// Iterator over the health transitions of the hosts, returned by
+/// `RestClient::healthcheck`. Blocking version of `crate::HealthChecks`.
+pub struct HealthChecks<'a> {
+    stream: crate::HealthChecks,
+    runtime: &'a Runtime,
+}
+
+impl Iterator for HealthChecks<'_> {
+    type Item = HostHealth;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+/// Iterator over the pages of a paginated collection, returned by
+/// `RestClient::get_pages`. Blocking version of `crate::Pages`.
+pub struct Pages<'a, T> {
+    stream: crate::Pages<T>,
+    runtime: &'a Runtime,
+}
+
+impl<T> Iterator for Pages<'_, T> {
+    type Item = Result<Response<T>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+/// Body of the response returned by `RestClient::get_hyper`, read as the
+/// chunks are received.
+pub struct BodyReader<'a> {
+    body: hyper::Body,
+    chunk: Bytes,
+    runtime: &'a Runtime,
+}
+
+impl Read for BodyReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while !self.chunk.has_remaining() {
+            match self.runtime.block_on(self.body.data()) {
+                Some(Ok(chunk)) => self.chunk = chunk,
+                Some(Err(err)) => return Err(io::Error::other(err)),
+                None => return Ok(0),
+            }
+        }
+
+        let len = buf.len().min(self.chunk.remaining());
+        self.chunk.copy_to_slice(&mut buf[..len]);
+        Ok(len)
+    }
+}
+
+/// WebSocket connection returned by `RestClient::websocket`. Blocking
+/// version of `crate::WebSocket`.
+///
+/// Received messages are read by iterating over the connection.
+#[cfg(feature = "websocket")]
+pub struct WebSocket<'a, T, K> {
+    inner: crate::WebSocket<T, K>,
+    runtime: &'a Runtime,
+}
+
+#[cfg(feature = "websocket")]
+impl<T: serde::Serialize, K> WebSocket<'_, T, K> {
+    /// Send the message
+    pub fn send(&mut self, msg: T) -> Result<(), Error> {
+        self.runtime.block_on(self.inner.send(msg))
+    }
+
+    /// Close the connection
+    pub fn close(&mut self) -> Result<(), Error> {
+        self.runtime.block_on(self.inner.close())
+    }
+}
+
+#[cfg(feature = "websocket")]
+impl<T, K: serde::de::DeserializeOwned> Iterator for WebSocket<'_, T, K> {
+    type Item = Result<K, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.runtime.block_on(self.inner.next())
+    }
+}
+
+/// Adapter that allows using a blocking writer with the async client.
+struct SyncWriter<'a, W: ?Sized>(&'a mut W);
+
+impl<W: Write + ?Sized> AsyncWrite for SyncWriter<'_, W> {
+    fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Poll::Ready(self.get_mut().0.write(buf))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(self.get_mut().0.flush())
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Adapter that allows using a blocking reader with the async client.
+struct SyncReader<R>(R);
+
+impl<R: Read + Unpin> AsyncRead for SyncReader<R> {
+    fn poll_read(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let n = self.get_mut().0.read(buf.initialize_unfilled())?;
+        buf.advance(n);
+        Poll::Ready(Ok(()))
+    }
 }
 
 impl TryFrom<AsyncRestClient> for RestClient {
     type Error = Error;
 
     fn try_from(other: AsyncRestClient) -> Result<Self, Self::Error> {
-        match Builder::new_current_thread().enable_all().build() {
+        match shared_runtime() {
             Ok(runtime) => Ok(Self { inner_client: other, runtime }),
             Err(e) => Err(Error::IoError(e)),
         }
@@ -37,9 +195,37 @@ impl RestClient {
         self.inner_client.auth = Some("Basic ".to_owned() + &base64::encode(&s));
     }
 
+    /// Set token for HTTP Bearer authentication.
+    pub fn set_auth_bearer(&mut self, token: &str) {
+        self.inner_client.auth = Some("Bearer ".to_owned() + token);
+    }
+
     /// Set a function that cleans the response body up before deserializing it.
-    pub fn set_body_wash_fn(&mut self, func: fn(String) -> String) {
-        self.inner_client.body_wash_fn = func;
+    pub fn set_body_wash_fn<F>(&mut self, func: F)
+    where
+        F: Fn(String) -> String + Send + Sync + 'static,
+    {
+        self.inner_client.set_body_wash_fn(func);
+    }
+
+    /// Set an asynchronous function that prepares the response body before it
+    /// is deserialized. The function is run on the runtime of the client.
+    pub fn set_async_body_wash_fn<F, Fut>(&mut self, func: F)
+    where
+        F: Fn(Bytes) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Bytes, Error>> + Send + 'static,
+    {
+        self.inner_client.set_async_body_wash_fn(func);
+    }
+
+    /// Base URL that the paths of the requests are joined to
+    pub fn base_url(&self) -> &str {
+        self.inner_client.base_url()
+    }
+
+    /// Set base URL of the requests. See `RestClient::set_base_url`.
+    pub fn set_base_url(&mut self, url: &str) -> Result<(), Error> {
+        self.inner_client.set_base_url(url)
     }
 
     /// Set request timeout
@@ -47,6 +233,21 @@ impl RestClient {
         self.inner_client.timeout = timeout;
     }
 
+    /// Set maximum number of pages requested by `get_all`
+    pub fn set_max_pages(&mut self, max: usize) {
+        self.inner_client.max_pages = max;
+    }
+
+    /// Set `Accept` header of the requests whose response is deserialized
+    pub fn set_accept(&mut self, value: &str) -> Result<(), Error> {
+        self.inner_client.set_accept(value)
+    }
+
+    /// Set format of the request and response bodies
+    pub fn set_body_format<F: BodyFormat + 'static>(&mut self, format: F) {
+        self.inner_client.set_body_format(format);
+    }
+
     /// Set HTTP header from string name and value.
     ///
     /// The header is added to all subsequent GET and POST requests
@@ -62,6 +263,20 @@ impl RestClient {
         self.inner_client.headers.clear();
     }
 
+    /// Build a request and return it as `curl` command without sending it.
+    pub fn as_curl<U, T>(&self, method: Method, params: U, data: Option<&T>) -> Result<String, Error>
+    where
+        T: serde::Serialize + RestPath<U>,
+    {
+        self.inner_client.as_curl(method, params, data)
+    }
+
+    /// Open a connection to the server of the base URL. See
+    /// `RestClient::connect` of the async client.
+    pub fn connect(&self) -> Result<(), Error> {
+        self.runtime.block_on(self.inner_client.connect())
+    }
+
     /// Make a GET request.
     pub fn get<U, T>(&self, params: U) -> Result<Response<T>, Error>
     where
@@ -71,13 +286,333 @@ impl RestClient {
     }
 
     /// Make a GET request with query parameters.
-    pub fn get_with<U, T>(&self, params: U, query: &Query<'_>) -> Result<Response<T>, Error>
+    pub fn get_with<U, T>(&self, params: U, query: &(impl ToQuery + ?Sized)) -> Result<Response<T>, Error>
     where
         T: serde::de::DeserializeOwned + RestPath<U>,
     {
         self.runtime.block_on(self.inner_client.get_with(params, query))
     }
 
+    /// Make a GET request with query parameters serialized from a struct.
+    pub fn get_with_query<U, T, Q>(&self, params: U, query: &Q) -> Result<Response<T>, Error>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U>,
+        Q: serde::Serialize + ?Sized,
+    {
+        self.runtime.block_on(self.inner_client.get_with_query(params, query))
+    }
+
+    /// Make GET requests for each of the parameters, with at most
+    /// `max_concurrency` requests in flight at a time. See
+    /// `RestClient::get_many` of the async client.
+    pub fn get_many<U, T, I>(&self, params: I, max_concurrency: usize) -> Vec<Result<Response<T>, Error>>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U>,
+        I: IntoIterator<Item = U>,
+    {
+        self.runtime.block_on(self.inner_client.get_many(params, max_concurrency))
+    }
+
+    /// Make a GET request to the `next` link of the `Link` header of the
+    /// response. See `RestClient::get_next` of the async client.
+    pub fn get_next<T>(&self, res: &Response<T>) -> Result<Option<Response<T>>, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.runtime.block_on(self.inner_client.get_next(res))
+    }
+
+    /// Get the pages of a paginated collection, following the `next` links
+    /// of the `Link` headers. See `RestClient::get_pages` of the async
+    /// client.
+    pub fn get_pages<U, T>(&self, params: U) -> Result<Pages<'_, T>, Error>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U> + Send + 'static,
+    {
+        let stream = self.inner_client.get_pages::<U, T>(params)?;
+        Ok(Pages { stream, runtime: self.runtime })
+    }
+
+    /// Get the pages of a paginated collection, finding the next pages with
+    /// the pagination strategy.
+    pub fn get_pages_with<U, T, P>(&self, params: U, pagination: P) -> Result<Pages<'_, T>, Error>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U> + Send + 'static,
+        P: Pagination<T> + 'static,
+    {
+        let stream = self.inner_client.get_pages_with::<U, T, P>(params, pagination)?;
+        Ok(Pages { stream, runtime: self.runtime })
+    }
+
+    /// Get all pages of a paginated collection and concatenate their items,
+    /// following the `next` links of the `Link` headers. See
+    /// `RestClient::get_all` of the async client.
+    pub fn get_all<U, T>(&self, params: U) -> Result<Vec<T::Item>, Error>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U> + Paginated,
+    {
+        self.runtime.block_on(self.inner_client.get_all::<U, T>(params))
+    }
+
+    /// Get all pages of a paginated collection and concatenate their items,
+    /// finding the next pages with the pagination strategy.
+    pub fn get_all_with<U, T, P>(&self, params: U, pagination: P) -> Result<Vec<T::Item>, Error>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U> + Paginated,
+        P: Pagination<T>,
+    {
+        self.runtime.block_on(self.inner_client.get_all_with::<U, T, P>(params, pagination))
+    }
+
+    /// Make a GET request and return the response body as text.
+    pub fn get_text<U, T>(&self, params: U) -> Result<Response<String>, Error>
+    where
+        T: RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.get_text::<U, T>(params))
+    }
+
+    /// Make a GET request with query parameters and return the response body as text.
+    pub fn get_text_with<U, T>(&self, params: U, query: &(impl ToQuery + ?Sized)) -> Result<Response<String>, Error>
+    where
+        T: RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.get_text_with::<U, T>(params, query))
+    }
+
+    /// Make a GET request, returning `None` if the resource is not found.
+    pub fn get_opt<U, T>(&self, params: U) -> Result<Option<Response<T>>, Error>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.get_opt::<U, T>(params))
+    }
+
+    /// Make a GET request, returning `NotModified` if the resource still has the given `ETag`.
+    pub fn get_if_none_match<U, T>(&self, params: U, etag: &str) -> Result<Conditional<T>, Error>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.get_if_none_match::<U, T>(params, etag))
+    }
+
+    /// Make a GET request and deserialize the error body sent by the server.
+    pub fn get_typed_err<U, T, E>(&self, params: U) -> Result<Response<T>, TypedError<E>>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U>,
+        E: serde::de::DeserializeOwned,
+    {
+        self.runtime.block_on(self.inner_client.get_typed_err::<U, T, E>(params))
+    }
+
+    /// Make GET requests until the predicate returns true for the response.
+    /// See `RestClient::poll_until` of the async client.
+    pub fn poll_until<U, T, F>(&self, params: U, interval: Duration, timeout: Duration, predicate: F) -> Result<Response<T>, Error>
+    where
+        U: Clone,
+        T: serde::de::DeserializeOwned + RestPath<U>,
+        F: FnMut(&T) -> bool,
+    {
+        self.runtime.block_on(self.inner_client.poll_until(params, interval, timeout, predicate))
+    }
+
+    /// Make a GET request for a JSON:API resource. See
+    /// `RestClient::get_jsonapi` of the async client.
+    pub fn get_jsonapi<U, T>(&self, params: U, query: &jsonapi::Params) -> Result<Response<jsonapi::Document<jsonapi::Resource<T>>>, Error>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.get_jsonapi(params, query))
+    }
+
+    /// Make a GET request for a collection of JSON:API resources. See
+    /// `RestClient::get_jsonapi_collection` of the async client.
+    pub fn get_jsonapi_collection<U, T>(
+        &self,
+        params: U,
+        query: &jsonapi::Params,
+    ) -> Result<Response<jsonapi::Document<Vec<jsonapi::Resource<T>>>>, Error>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.get_jsonapi_collection(params, query))
+    }
+
+    /// Make a GET request to the link of the HAL resource in the response with
+    /// the relation. See `RestClient::follow` of the async client.
+    pub fn follow<T, K>(&self, res: &Response<hal::Hal<T>>, rel: &str) -> Result<Option<Response<K>>, Error>
+    where
+        K: serde::de::DeserializeOwned,
+    {
+        self.runtime.block_on(self.inner_client.follow(res, rel))
+    }
+
+    /// Make a GET request to the URL. See `RestClient::get_url` of the async
+    /// client.
+    pub fn get_url<T>(&self, url: &str) -> Result<Response<T>, Error>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.runtime.block_on(self.inner_client.get_url(url))
+    }
+
+    /// Make a POST request to the URL. See `RestClient::get_url`.
+    pub fn post_url<T>(&self, url: &str, data: &T) -> Result<Response<()>, Error>
+    where
+        T: serde::Serialize,
+    {
+        self.runtime.block_on(self.inner_client.post_url(url, data))
+    }
+
+    /// Make a PUT request to the URL. See `RestClient::get_url`.
+    pub fn put_url<T>(&self, url: &str, data: &T) -> Result<Response<()>, Error>
+    where
+        T: serde::Serialize,
+    {
+        self.runtime.block_on(self.inner_client.put_url(url, data))
+    }
+
+    /// Make a PATCH request to the URL. See `RestClient::get_url`.
+    pub fn patch_url<T>(&self, url: &str, data: &T) -> Result<Response<()>, Error>
+    where
+        T: serde::Serialize,
+    {
+        self.runtime.block_on(self.inner_client.patch_url(url, data))
+    }
+
+    /// Make a DELETE request to the URL. See `RestClient::get_url`.
+    pub fn delete_url(&self, url: &str) -> Result<Response<()>, Error> {
+        self.runtime.block_on(self.inner_client.delete_url(url))
+    }
+
+    /// Check the health of the hosts periodically. The iterator blocks until
+    /// the next change. See `RestClient::healthcheck` of the async client.
+    pub fn healthcheck(&self, path: &str, expected_status: u16, interval: Duration) -> Result<HealthChecks<'_>, Error> {
+        let stream = self.inner_client.healthcheck(path, expected_status, interval)?;
+        Ok(HealthChecks { stream, runtime: self.runtime })
+    }
+
+    /// Subscribe to the Server-Sent Events of the resource. See
+    /// `RestClient::sse` of the async client.
+    pub fn sse<U, T>(&self, params: U) -> Result<Events<'_, T>, Error>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U> + Send + 'static,
+    {
+        let stream = self.inner_client.sse::<U, T>(params)?;
+        Ok(Events { stream, runtime: self.runtime })
+    }
+
+    /// Open a WebSocket connection to the resource. See
+    /// `RestClient::websocket` of the async client.
+    #[cfg(feature = "websocket")]
+    pub fn websocket<U, T, K>(&self, params: U) -> Result<WebSocket<'_, T, K>, Error>
+    where
+        T: serde::Serialize + RestPath<U>,
+        K: serde::de::DeserializeOwned,
+    {
+        let inner = self.runtime.block_on(self.inner_client.websocket::<U, T, K>(params))?;
+        Ok(WebSocket { inner, runtime: self.runtime })
+    }
+
+    /// Make a GET request and stream the response body into a writer.
+    ///
+    /// Returns the number of bytes written.
+    pub fn get_to_writer<U, T, W>(&self, params: U, writer: &mut W) -> Result<Response<u64>, Error>
+    where
+        T: RestPath<U>,
+        W: Write + ?Sized,
+    {
+        let mut writer = SyncWriter(writer);
+        self.runtime.block_on(self.inner_client.get_to_writer::<U, T, _>(params, &mut writer))
+    }
+
+    /// Make a GET request and stream the response body into a writer, reporting progress.
+    ///
+    /// The progress function is called after each received chunk with the number of bytes
+    /// received so far and the total length of the body, if known.
+    pub fn get_to_writer_with_progress<U, T, W, P>(
+        &self,
+        params: U,
+        writer: &mut W,
+        progress: P,
+    ) -> Result<Response<u64>, Error>
+    where
+        T: RestPath<U>,
+        W: Write + ?Sized,
+        P: FnMut(u64, Option<u64>),
+    {
+        let mut writer = SyncWriter(writer);
+        self.runtime.block_on(
+            self.inner_client
+                .get_to_writer_with_progress::<U, T, _, _>(params, &mut writer, progress),
+        )
+    }
+
+    /// Make a GET request and download the response body into a file.
+    ///
+    /// If the file already exists, the download continues from the end of the file.
+    /// Returns the size of the downloaded file.
+    pub fn download_resumable<U, T, P>(&self, params: U, path: P) -> Result<Response<u64>, Error>
+    where
+        T: RestPath<U>,
+        P: AsRef<Path>,
+    {
+        self.runtime.block_on(self.inner_client.download_resumable::<U, T, P>(params, path))
+    }
+
+    /// Make a GET request and return the response body as raw bytes.
+    pub fn get_raw<U, T>(&self, params: U) -> Result<Response<Bytes>, Error>
+    where
+        T: RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.get_raw::<U, T>(params))
+    }
+
+    /// Make a GET request with query parameters and return the response body as raw bytes.
+    pub fn get_raw_with<U, T>(&self, params: U, query: &(impl ToQuery + ?Sized)) -> Result<Response<Bytes>, Error>
+    where
+        T: RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.get_raw_with::<U, T>(params, query))
+    }
+
+    /// Make a GET request and return the hyper response as-is, with the body
+    /// read as it is received. See `RestClient::get_hyper` of the async client.
+    pub fn get_hyper<U, T>(&self, params: U) -> Result<hyper::Response<BodyReader<'_>>, Error>
+    where
+        T: RestPath<U>,
+    {
+        let res = self.runtime.block_on(self.inner_client.get_hyper::<U, T>(params))?;
+        Ok(res.map(|body| BodyReader { body, chunk: Bytes::new(), runtime: self.runtime }))
+    }
+
+    /// Make a GET request with query parameters and return the hyper response as-is.
+    pub fn get_hyper_with<U, T>(&self, params: U, query: &(impl ToQuery + ?Sized)) -> Result<hyper::Response<BodyReader<'_>>, Error>
+    where
+        T: RestPath<U>,
+    {
+        let res = self.runtime.block_on(self.inner_client.get_hyper_with::<U, T>(params, query))?;
+        Ok(res.map(|body| BodyReader { body, chunk: Bytes::new(), runtime: self.runtime }))
+    }
+
+    /// Make a GET request and deserialize the JSON response body as it is
+    /// received. See `RestClient::get_incremental` of the async client.
+    pub fn get_incremental<U, T>(&self, params: U) -> Result<Response<T>, Error>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U> + Send + 'static,
+    {
+        self.runtime.block_on(self.inner_client.get_incremental(params))
+    }
+
+    /// Make a GET request with query parameters and deserialize the JSON
+    /// response body as it is received.
+    pub fn get_incremental_with<U, T>(&self, params: U, query: &(impl ToQuery + ?Sized)) -> Result<Response<T>, Error>
+    where
+        T: serde::de::DeserializeOwned + RestPath<U> + Send + 'static,
+    {
+        self.runtime.block_on(self.inner_client.get_incremental_with(params, query))
+    }
+
     /// Make a POST request.
     pub fn post<U, T>(&self, params: U, data: &T) -> Result<Response<()>, Error>
     where
@@ -102,8 +637,123 @@ impl RestClient {
         self.runtime.block_on(self.inner_client.patch(params, data))
     }
 
+    /// Make a POST request with `application/x-www-form-urlencoded` body.
+    pub fn post_form<U, T>(&self, params: U, data: &T) -> Result<Response<()>, Error>
+    where
+        T: serde::Serialize + RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.post_form(params, data))
+    }
+
+    /// Make a POST request with `application/x-www-form-urlencoded` body and capture
+    /// returned body.
+    pub fn post_form_capture<U, T, K>(&self, params: U, data: &T) -> Result<Response<K>, Error>
+    where
+        T: serde::Serialize + RestPath<U>,
+        K: serde::de::DeserializeOwned,
+    {
+        self.runtime.block_on(self.inner_client.post_form_capture(params, data))
+    }
+
+    /// Make a POST request with `multipart/form-data` body.
+    pub fn post_multipart<U, T>(&self, params: U, form: Multipart) -> Result<Response<()>, Error>
+    where
+        T: RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.post_multipart::<U, T>(params, form))
+    }
+
+    /// Make a POST request with `multipart/form-data` body and capture returned body.
+    pub fn post_multipart_capture<U, T, K>(&self, params: U, form: Multipart) -> Result<Response<K>, Error>
+    where
+        T: RestPath<U>,
+        K: serde::de::DeserializeOwned,
+    {
+        self.runtime.block_on(self.inner_client.post_multipart_capture::<U, T, K>(params, form))
+    }
+
+    /// Make a POST request with raw body and custom content type.
+    pub fn post_raw<U, T>(&self, params: U, body: impl Into<Bytes>, content_type: &str) -> Result<Response<()>, Error>
+    where
+        T: RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.post_raw::<U, T>(params, body, content_type))
+    }
+
+    /// Make a PUT request with raw body and custom content type.
+    pub fn put_raw<U, T>(&self, params: U, body: impl Into<Bytes>, content_type: &str) -> Result<Response<()>, Error>
+    where
+        T: RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.put_raw::<U, T>(params, body, content_type))
+    }
+
+    /// Make a PATCH request with raw body and custom content type.
+    pub fn patch_raw<U, T>(&self, params: U, body: impl Into<Bytes>, content_type: &str) -> Result<Response<()>, Error>
+    where
+        T: RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.patch_raw::<U, T>(params, body, content_type))
+    }
+
+    /// Make a POST request with body streamed from a reader.
+    pub fn post_stream<U, T, R>(&self, params: U, reader: R, len: Option<u64>) -> Result<Response<()>, Error>
+    where
+        T: RestPath<U>,
+        R: Read + Send + Unpin + 'static,
+    {
+        self.runtime.block_on(self.inner_client.post_stream::<U, T, _>(params, SyncReader(reader), len))
+    }
+
+    /// Make a POST request with body streamed from a reader, reporting upload progress.
+    pub fn post_stream_with_progress<U, T, R, P>(
+        &self,
+        params: U,
+        reader: R,
+        len: Option<u64>,
+        progress: P,
+    ) -> Result<Response<()>, Error>
+    where
+        T: RestPath<U>,
+        R: Read + Send + Unpin + 'static,
+        P: FnMut(u64, Option<u64>) + Send + 'static,
+    {
+        self.runtime.block_on(
+            self.inner_client
+                .post_stream_with_progress::<U, T, _, _>(params, SyncReader(reader), len, progress),
+        )
+    }
+
+    /// Make a PUT request with body streamed from a reader.
+    pub fn put_stream<U, T, R>(&self, params: U, reader: R, len: Option<u64>) -> Result<Response<()>, Error>
+    where
+        T: RestPath<U>,
+        R: Read + Send + Unpin + 'static,
+    {
+        self.runtime.block_on(self.inner_client.put_stream::<U, T, _>(params, SyncReader(reader), len))
+    }
+
+    /// Make a PUT request with body streamed from a reader, reporting upload progress.
+    pub fn put_stream_with_progress<U, T, R, P>(
+        &self,
+        params: U,
+        reader: R,
+        len: Option<u64>,
+        progress: P,
+    ) -> Result<Response<()>, Error>
+    where
+        T: RestPath<U>,
+        R: Read + Send + Unpin + 'static,
+        P: FnMut(u64, Option<u64>) + Send + 'static,
+    {
+        self.runtime.block_on(
+            self.inner_client
+                .put_stream_with_progress::<U, T, _, _>(params, SyncReader(reader), len, progress),
+        )
+    }
+
     /// Make POST request with query parameters.
-    pub fn post_with<U, T>(&self, params: U, data: &T, query: &Query<'_>) -> Result<Response<()>, Error>
+    pub fn post_with<U, T>(&self, params: U, data: &T, query: &(impl ToQuery + ?Sized)) -> Result<Response<()>, Error>
     where
         T: serde::Serialize + RestPath<U>,
     {
@@ -111,7 +761,7 @@ impl RestClient {
     }
 
     /// Make PUT request with query parameters.
-    pub fn put_with<U, T>(&self, params: U, data: &T, query: &Query<'_>) -> Result<Response<()>, Error>
+    pub fn put_with<U, T>(&self, params: U, data: &T, query: &(impl ToQuery + ?Sized)) -> Result<Response<()>, Error>
     where
         T: serde::Serialize + RestPath<U>,
     {
@@ -119,7 +769,7 @@ impl RestClient {
     }
 
     /// Make PATCH request with query parameters.
-    pub fn patch_with<U, T>(&self, params: U, data: &T, query: &Query<'_>) -> Result<Response<()>, Error>
+    pub fn patch_with<U, T>(&self, params: U, data: &T, query: &(impl ToQuery + ?Sized)) -> Result<Response<()>, Error>
     where
         T: serde::Serialize + RestPath<U>,
     {
@@ -135,6 +785,14 @@ impl RestClient {
         self.runtime.block_on(self.inner_client.post_capture(params, data))
     }
 
+    /// Make a POST request and capture returned body as text.
+    pub fn post_capture_text<U, T>(&self, params: U, data: &T) -> Result<Response<String>, Error>
+    where
+        T: serde::Serialize + RestPath<U>,
+    {
+        self.runtime.block_on(self.inner_client.post_capture_text(params, data))
+    }
+
     /// Make a PUT request and capture returned body.
     pub fn put_capture<U, T, K>(&self, params: U, data: &T) -> Result<Response<K>, Error>
     where
@@ -158,7 +816,7 @@ impl RestClient {
         &self,
         params: U,
         data: &T,
-        query: &Query<'_>,
+        query: &(impl ToQuery + ?Sized),
     ) -> Result<Response<K>, Error>
     where
         T: serde::Serialize + RestPath<U>,
@@ -172,7 +830,7 @@ impl RestClient {
         &self,
         params: U,
         data: &T,
-        query: &Query<'_>,
+        query: &(impl ToQuery + ?Sized),
     ) -> Result<Response<K>, Error>
     where
         T: serde::Serialize + RestPath<U>,
@@ -186,7 +844,7 @@ impl RestClient {
         &self,
         params: U,
         data: &T,
-        query: &Query<'_>,
+        query: &(impl ToQuery + ?Sized),
     ) -> Result<Response<K>, Error>
     where
         T: serde::Serialize + RestPath<U>,
@@ -204,13 +862,19 @@ impl RestClient {
     }
 
     /// Make a DELETE request with query and body.
-    pub fn delete_with<U, T>(&self, params: U, data: &T, query: &Query<'_>) -> Result<Response<()>, Error>
+    pub fn delete_with<U, T>(&self, params: U, data: &T, query: &(impl ToQuery + ?Sized)) -> Result<Response<()>, Error>
     where
         T: serde::Serialize + RestPath<U>,
     {
         self.runtime.block_on(self.inner_client.delete_with(params, data, query))
     }
 
+    /// Run a prebuilt request and return the response body as raw bytes. See
+    /// `RestClient::execute` of the async client.
+    pub fn execute(&self, req: hyper::Request<hyper::Body>) -> Result<Response<Bytes>, Error> {
+        self.runtime.block_on(self.inner_client.execute(req))
+    }
+
     /// Make a DELETE request and capture returned body.
     pub fn delete_capture<U, T, K>(&self, params: U, data: &T) -> Result<Response<K>, Error>
     where
@@ -225,7 +889,7 @@ impl RestClient {
         &self,
         params: U,
         data: &T,
-        query: &Query<'_>,
+        query: &(impl ToQuery + ?Sized),
     ) -> Result<Response<K>, Error>
     where
         T: serde::Serialize + RestPath<U>,
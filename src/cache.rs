@@ -0,0 +1,407 @@
+//! Caching of GET responses according to RFC 7234
+
+use crate::timing::Timings;
+use crate::Response;
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use hyper::header::*;
+use hyper::{Method, Request, StatusCode, Uri};
+use log::warn;
+use std::collections::HashMap;
+use std::convert::TryInto;
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Storage for the cached responses.
+///
+/// The responses of each URL are stored as one opaque value, so that the
+/// store only needs to map keys to bytes. Errors returned by the store are
+/// logged and the request is sent to the server as if nothing was cached.
+///
+/// # Examples
+/// ```ignore
+/// struct DiskStore(PathBuf);
+///
+/// impl CacheStore for DiskStore {
+///     fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<Option<Vec<u8>>>> {
+///         Box::pin(async move {
+///             match tokio::fs::read(self.0.join(file_name(key))).await {
+///                 Ok(value) => Ok(Some(value)),
+///                 Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+///                 Err(err) => Err(err),
+///             }
+///         })
+///     }
+///
+///     fn put<'a>(&'a self, key: &'a str, value: Vec<u8>) -> BoxFuture<'a, io::Result<()>> {
+///         Box::pin(tokio::fs::write(self.0.join(file_name(key)), value))
+///     }
+///
+///     fn remove<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<()>> {
+///         Box::pin(tokio::fs::remove_file(self.0.join(file_name(key))))
+///     }
+/// }
+///
+/// let client = RestClient::builder().cache_store(DiskStore(dir)).build("http://httpbin.org").unwrap();
+/// ```
+pub trait CacheStore: Send + Sync {
+    /// Get the value stored with the key
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<Option<Vec<u8>>>>;
+
+    /// Store the value with the key, replacing the previous value
+    fn put<'a>(&'a self, key: &'a str, value: Vec<u8>) -> BoxFuture<'a, io::Result<()>>;
+
+    /// Remove the value stored with the key
+    fn remove<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<()>>;
+}
+
+/// Cache store that keeps the values in memory. This is the default store.
+#[derive(Debug, Default)]
+pub struct MemoryStore {
+    values: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryStore {
+    /// Create empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CacheStore for MemoryStore {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<Option<Vec<u8>>>> {
+        let value = self.values.lock().unwrap().get(key).cloned();
+        Box::pin(async move { Ok(value) })
+    }
+
+    fn put<'a>(&'a self, key: &'a str, value: Vec<u8>) -> BoxFuture<'a, io::Result<()>> {
+        self.values.lock().unwrap().insert(key.to_owned(), value);
+        Box::pin(async { Ok(()) })
+    }
+
+    fn remove<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<()>> {
+        self.values.lock().unwrap().remove(key);
+        Box::pin(async { Ok(()) })
+    }
+}
+
+/// Responses stored by URL. Each URL can have several responses that differ
+/// by the request headers listed in their `Vary` header.
+#[derive(Clone)]
+pub(crate) struct Cache {
+    store: Arc<dyn CacheStore>,
+}
+
+/// Stored response
+#[derive(Debug, Clone)]
+pub(crate) struct Entry {
+    response: Response<Bytes>,
+
+    /// Request headers selected by the `Vary` header of the response
+    vary: Vec<(HeaderName, Option<HeaderValue>)>,
+
+    /// Time when the response was stored or revalidated
+    stored: SystemTime,
+}
+
+pub(crate) enum Lookup {
+    /// Fresh response that is used without contacting the server
+    Fresh(Response<Bytes>),
+
+    /// Stale response that must be revalidated with the server
+    Stale(Entry),
+
+    /// No stored response for the request
+    Miss,
+}
+
+impl Cache {
+    pub(crate) fn new(store: Arc<dyn CacheStore>) -> Self {
+        Self { store }
+    }
+
+    /// Find stored response for the request
+    pub(crate) async fn lookup<B>(&self, req: &Request<B>) -> Lookup {
+        let entries = self.entries(req.uri()).await;
+        let entry = entries.into_iter().find(|entry| entry.matches(req.headers()));
+
+        match entry {
+            Some(entry) if entry.is_fresh() && !has_directive(req.headers(), "no-cache") => {
+                let mut response = entry.response;
+                response.timings = Timings::default();
+                Lookup::Fresh(response)
+            }
+            Some(entry) => Lookup::Stale(entry),
+            None => Lookup::Miss,
+        }
+    }
+
+    /// Store the response, if allowed, replacing the previous response with
+    /// the same `Vary` headers
+    pub(crate) async fn store(&self, uri: &Uri, request: &HeaderMap, response: &Response<Bytes>) {
+        let entry = match Entry::new(request, response.clone()) {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        let mut entries = self.entries(uri).await;
+        entries.retain(|old| old.vary != entry.vary);
+        entries.push(entry);
+
+        if let Err(err) = self.store.put(&uri.to_string(), encode(&entries)).await {
+            warn!("failed to store response in cache: {}", err);
+        }
+    }
+
+    /// Update the stale response with the headers of 304 response and return
+    /// the updated response
+    pub(crate) async fn revalidated(
+        &self,
+        uri: &Uri,
+        request: &HeaderMap,
+        stale: Entry,
+        response: &Response<Bytes>,
+    ) -> Response<Bytes> {
+        let mut updated = stale.response;
+        for (name, value) in response.headers.iter() {
+            updated.headers.insert(name, value.clone());
+        }
+        updated.timings = response.timings;
+        updated.request_id = response.request_id.clone();
+
+        self.store(uri, request, &updated).await;
+        updated
+    }
+
+    /// Remove the responses of the URL after it was modified
+    pub(crate) async fn invalidate(&self, uri: &Uri) {
+        if let Err(err) = self.store.remove(&uri.to_string()).await {
+            warn!("failed to remove response from cache: {}", err);
+        }
+    }
+
+    async fn entries(&self, uri: &Uri) -> Vec<Entry> {
+        match self.store.get(&uri.to_string()).await {
+            Ok(Some(value)) => decode(&value).unwrap_or_default(),
+            Ok(None) => Vec::new(),
+            Err(err) => {
+                warn!("failed to read response from cache: {}", err);
+                Vec::new()
+            }
+        }
+    }
+}
+
+impl Entry {
+    fn new(request: &HeaderMap, response: Response<Bytes>) -> Option<Self> {
+        if !matches!(response.status, StatusCode::OK | StatusCode::NON_AUTHORITATIVE_INFORMATION)
+            || has_directive(request, "no-store")
+            || has_directive(&response.headers, "no-store")
+        {
+            return None;
+        }
+
+        let mut vary = Vec::new();
+        for value in response.headers.get_all(VARY) {
+            for name in value.to_str().ok()?.split(',').map(str::trim) {
+                if name == "*" {
+                    return None;
+                }
+                let name = HeaderName::from_bytes(name.as_bytes()).ok()?;
+                let value = request.get(&name).cloned();
+                vary.push((name, value));
+            }
+        }
+
+        let has_validator = response.headers.contains_key(ETAG) || response.headers.contains_key(LAST_MODIFIED);
+        if lifetime(&response.headers).is_zero() && !has_validator {
+            return None;
+        }
+
+        Some(Self {
+            response,
+            vary,
+            stored: SystemTime::now(),
+        })
+    }
+
+    fn matches(&self, request: &HeaderMap) -> bool {
+        self.vary.iter().all(|(name, value)| request.get(name) == value.as_ref())
+    }
+
+    fn is_fresh(&self) -> bool {
+        let initial_age = self
+            .response
+            .headers
+            .get(AGE)
+            .and_then(|age| age.to_str().ok())
+            .and_then(|age| age.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or_default();
+        let resident = self.stored.elapsed().unwrap_or_default();
+        initial_age + resident < lifetime(&self.response.headers)
+    }
+
+    /// Add the validators of the response to the request
+    pub(crate) fn add_validators<B>(&self, req: &mut Request<B>) {
+        if let Some(etag) = self.response.headers.get(ETAG) {
+            req.headers_mut().insert(IF_NONE_MATCH, etag.clone());
+        }
+        if let Some(modified) = self.response.headers.get(LAST_MODIFIED) {
+            req.headers_mut().insert(IF_MODIFIED_SINCE, modified.clone());
+        }
+    }
+}
+
+/// Request can be served from the cache: GET without conditional headers
+/// set by the user
+pub(crate) fn is_cacheable<B>(req: &Request<B>) -> bool {
+    req.method() == Method::GET
+        && !req.headers().contains_key(IF_NONE_MATCH)
+        && !req.headers().contains_key(IF_MODIFIED_SINCE)
+        && !has_directive(req.headers(), "no-store")
+}
+
+/// Time the response is fresh, from `Cache-Control: max-age` or `Expires`
+/// header. Responses with `no-cache` directive are stale immediately.
+fn lifetime(headers: &HeaderMap) -> Duration {
+    if has_directive(headers, "no-cache") {
+        return Duration::ZERO;
+    }
+
+    if let Some(max_age) = directive(headers, "max-age") {
+        return max_age.parse().map(Duration::from_secs).unwrap_or_default();
+    }
+
+    let date = |name| {
+        headers
+            .get(name)
+            .and_then(|value: &HeaderValue| value.to_str().ok())
+            .and_then(|value| httpdate::parse_http_date(value).ok())
+    };
+    match date(EXPIRES) {
+        Some(expires) => {
+            let date = date(DATE).unwrap_or_else(SystemTime::now);
+            expires.duration_since(date).unwrap_or_default()
+        }
+        None => Duration::ZERO,
+    }
+}
+
+/// Value of the `Cache-Control` directive, or empty string for directives
+/// without value
+fn directive(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get_all(CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|directive| {
+            let mut parts = directive.trim().splitn(2, '=');
+            match parts.next() {
+                Some(key) if key.eq_ignore_ascii_case(name) => {
+                    Some(parts.next().unwrap_or_default().trim_matches('"').to_owned())
+                }
+                _ => None,
+            }
+        })
+}
+
+fn has_directive(headers: &HeaderMap, name: &str) -> bool {
+    directive(headers, name).is_some()
+}
+
+/// Encode the entries of a URL for the store. Each field is written with its
+/// length, and the stored time in seconds since the Unix epoch.
+fn encode(entries: &[Entry]) -> Vec<u8> {
+    fn put(buf: &mut Vec<u8>, value: &[u8]) {
+        buf.extend_from_slice(&(value.len() as u64).to_be_bytes());
+        buf.extend_from_slice(value);
+    }
+
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&(entries.len() as u64).to_be_bytes());
+    for entry in entries {
+        let stored = entry.stored.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        buf.extend_from_slice(&stored.to_be_bytes());
+        buf.extend_from_slice(&entry.response.status.as_u16().to_be_bytes());
+
+        buf.extend_from_slice(&(entry.response.headers.len() as u64).to_be_bytes());
+        for (name, value) in entry.response.headers.iter() {
+            put(&mut buf, name.as_str().as_bytes());
+            put(&mut buf, value.as_bytes());
+        }
+
+        buf.extend_from_slice(&(entry.vary.len() as u64).to_be_bytes());
+        for (name, value) in entry.vary.iter() {
+            put(&mut buf, name.as_str().as_bytes());
+            match value {
+                Some(value) => {
+                    buf.push(1);
+                    put(&mut buf, value.as_bytes());
+                }
+                None => buf.push(0),
+            }
+        }
+
+        put(&mut buf, &entry.response.body);
+    }
+    buf
+}
+
+/// Decode the entries written by `encode`, or `None` if the value is invalid
+fn decode(mut buf: &[u8]) -> Option<Vec<Entry>> {
+    fn take<'a>(buf: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+        if buf.len() < len {
+            return None;
+        }
+        let (value, rest) = buf.split_at(len);
+        *buf = rest;
+        Some(value)
+    }
+    fn int(buf: &mut &[u8]) -> Option<u64> {
+        Some(u64::from_be_bytes(take(buf, 8)?.try_into().ok()?))
+    }
+    fn bytes<'a>(buf: &mut &'a [u8]) -> Option<&'a [u8]> {
+        let len = int(buf)?.try_into().ok()?;
+        take(buf, len)
+    }
+
+    let mut entries = Vec::new();
+    for _ in 0..int(&mut buf)? {
+        let stored = UNIX_EPOCH + Duration::from_secs(int(&mut buf)?);
+        let status = u16::from_be_bytes(take(&mut buf, 2)?.try_into().ok()?);
+        let status = StatusCode::from_u16(status).ok()?;
+
+        let mut headers = HeaderMap::new();
+        for _ in 0..int(&mut buf)? {
+            let name = HeaderName::from_bytes(bytes(&mut buf)?).ok()?;
+            let value = HeaderValue::from_bytes(bytes(&mut buf)?).ok()?;
+            headers.append(name, value);
+        }
+
+        let mut vary = Vec::new();
+        for _ in 0..int(&mut buf)? {
+            let name = HeaderName::from_bytes(bytes(&mut buf)?).ok()?;
+            let value = match take(&mut buf, 1)? {
+                [1] => Some(HeaderValue::from_bytes(bytes(&mut buf)?).ok()?),
+                _ => None,
+            };
+            vary.push((name, value));
+        }
+
+        let body = Bytes::copy_from_slice(bytes(&mut buf)?);
+        let response = Response {
+            body,
+            headers,
+            status,
+            timings: Timings::default(),
+            request_id: None,
+            redirects: Vec::new(),
+            url: String::new(),
+        };
+        entries.push(Entry { response, vary, stored });
+    }
+    Some(entries)
+}
@@ -0,0 +1,186 @@
+//! Recording the responses to a file and replaying them (VCR)
+
+use crate::client::HttpClient;
+use crate::transport::Transport;
+use crate::Error;
+use futures::future::BoxFuture;
+use hyper::header::{HeaderName, HeaderValue};
+use hyper::{Body, Request, Response, StatusCode};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+use tokio::sync::{Mutex, OnceCell};
+
+/// Request and the response received to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    method: String,
+    url: String,
+
+    /// FNV-1a hash of the request body in hex
+    body_hash: String,
+
+    status: u16,
+    headers: Vec<(String, String)>,
+
+    /// Response body, if it is valid UTF-8
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+
+    /// Response body in base64, if it is not valid UTF-8
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body_base64: Option<String>,
+
+    /// Interaction has been replayed
+    #[serde(skip)]
+    used: bool,
+}
+
+impl Interaction {
+    fn matches(&self, method: &str, url: &str, body_hash: &str) -> bool {
+        self.method == method && self.url == url && self.body_hash == body_hash
+    }
+
+    fn response(&self) -> Result<Response<Body>, Error> {
+        let body = match (&self.body, &self.body_base64) {
+            (_, Some(encoded)) => base64::decode(encoded).map_err(|err| invalid(&err))?,
+            (Some(body), None) => body.clone().into_bytes(),
+            (None, None) => Vec::new(),
+        };
+
+        let mut res = Response::new(Body::from(body));
+        *res.status_mut() = StatusCode::from_u16(self.status).map_err(|err| invalid(&err))?;
+        for (name, value) in &self.headers {
+            let name = HeaderName::try_from(name.as_str()).map_err(|err| invalid(&err))?;
+            let value = HeaderValue::try_from(value.as_str()).map_err(|err| invalid(&err))?;
+            res.headers_mut().append(name, value);
+        }
+        Ok(res)
+    }
+}
+
+/// Transport that replays the interactions in the cassette file, or records
+/// them with the HTTP client if the file does not exist. The file is read on
+/// the first request.
+pub(crate) struct Cassette {
+    path: PathBuf,
+    client: HttpClient,
+    recording: OnceCell<bool>,
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl Cassette {
+    pub(crate) fn new(path: PathBuf, client: HttpClient) -> Self {
+        Self {
+            path,
+            client,
+            recording: OnceCell::new(),
+            interactions: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Read the interactions from the file if it exists. Returns whether the
+    /// interactions are recorded.
+    async fn load(&self) -> Result<bool, Error> {
+        let recording = match tokio::fs::read(&self.path).await {
+            Ok(data) => {
+                *self.interactions.lock().await = serde_json::from_slice(&data).map_err(|err| invalid(&err))?;
+                false
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => true,
+            Err(err) => return Err(Error::IoError(err)),
+        };
+        debug!("{} cassette {}", if recording { "recording" } else { "replaying" }, self.path.display());
+        Ok(recording)
+    }
+
+    /// Next recorded response to the request. Interactions with the same
+    /// request are replayed in the order they were recorded, and the last
+    /// one is repeated when all have been used.
+    async fn replay(&self, method: &str, url: &str, body_hash: &str) -> Result<Response<Body>, Error> {
+        let mut interactions = self.interactions.lock().await;
+        let mut matching: Vec<_> = interactions
+            .iter_mut()
+            .filter(|interaction| interaction.matches(method, url, body_hash))
+            .collect();
+
+        let next = matching.iter().position(|interaction| !interaction.used);
+        if let Some(interaction) = next.or(matching.len().checked_sub(1)).map(|i| &mut matching[i]) {
+            interaction.used = true;
+            return interaction.response();
+        }
+
+        Err(Error::IoError(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no recorded response to {} {} in {}", method, url, self.path.display()),
+        )))
+    }
+
+    /// Send the request with the HTTP client and add the interaction to the
+    /// cassette file
+    async fn record(&self, req: Request<Body>, body_hash: String) -> Result<Response<Body>, Error> {
+        let method = req.method().to_string();
+        let url = req.uri().to_string();
+        let res = self.client.request(req).await?;
+
+        let (parts, body) = res.into_parts();
+        let body = hyper::body::to_bytes(body).await.map_err(Error::BodyReadError)?;
+        let headers = parts
+            .headers
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+            .collect();
+        let (text, encoded) = match std::str::from_utf8(&body) {
+            Ok(text) => (Some(text.to_owned()), None),
+            Err(_) => (None, Some(base64::encode(&body))),
+        };
+
+        let interaction = Interaction {
+            method,
+            url,
+            body_hash,
+            status: parts.status.as_u16(),
+            headers,
+            body: text,
+            body_base64: encoded,
+            used: false,
+        };
+
+        // File is written after each interaction so that nothing is lost if
+        // the test fails
+        let mut interactions = self.interactions.lock().await;
+        interactions.push(interaction);
+        let data = serde_json::to_vec_pretty(&*interactions).map_err(|err| invalid(&err))?;
+        tokio::fs::write(&self.path, data).await.map_err(Error::IoError)?;
+
+        Ok(Response::from_parts(parts, Body::from(body)))
+    }
+}
+
+impl Transport for Cassette {
+    fn send<'a>(&'a self, req: Request<Body>) -> BoxFuture<'a, Result<Response<Body>, Error>> {
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let body = hyper::body::to_bytes(body).await.map_err(Error::BodyReadError)?;
+            let body_hash = format!("{:016x}", fnv1a(&body));
+
+            match self.recording.get_or_try_init(|| self.load()).await? {
+                true => self.record(Request::from_parts(parts, Body::from(body)), body_hash).await,
+                false => self.replay(parts.method.as_str(), &parts.uri.to_string(), &body_hash).await,
+            }
+        })
+    }
+}
+
+/// 64-bit FNV-1a hash, which stays the same between Rust versions unlike the
+/// hasher of the standard library
+fn fnv1a(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn invalid(err: &dyn std::fmt::Display) -> Error {
+    Error::IoError(io::Error::new(io::ErrorKind::InvalidData, format!("invalid cassette: {}", err)))
+}
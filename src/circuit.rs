@@ -0,0 +1,151 @@
+//! Per-host circuit breaker
+
+use crate::Error;
+use hyper::Uri;
+use log::{debug, warn};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Configuration of the circuit breaker.
+///
+/// The circuit of a host opens after `failure_threshold` consecutive requests
+/// have failed with 5xx status, transport error or timeout. While the circuit
+/// is open, requests to the host fail immediately with `Error::CircuitOpen`.
+/// After the open duration, a limited number of probe requests is let through
+/// (half-open state). If a probe succeeds, the circuit is closed again and if
+/// it fails, the circuit is opened for another period.
+///
+/// # Examples
+/// ```ignore
+/// let client = RestClient::builder()
+///     .circuit_breaker(CircuitBreaker::new(5, Duration::from_secs(30)))
+///     .build("http://httpbin.org")
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    half_open_probes: u32,
+}
+
+impl CircuitBreaker {
+    /// Create circuit breaker that opens after `failure_threshold` consecutive
+    /// failures and stays open for `open_duration`
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold,
+            open_duration,
+            half_open_probes: 1,
+        }
+    }
+
+    /// Set number of concurrent probe requests allowed in half-open state
+    ///
+    /// Default is 1
+    pub fn half_open_probes(mut self, probes: u32) -> Self {
+        self.half_open_probes = probes;
+        self
+    }
+}
+
+#[derive(Debug)]
+enum State {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen { probes: u32 },
+}
+
+/// State of the circuits of all hosts
+#[derive(Debug)]
+pub(crate) struct Circuits {
+    config: CircuitBreaker,
+    hosts: Mutex<HashMap<String, State>>,
+}
+
+impl Circuits {
+    pub(crate) fn new(config: CircuitBreaker) -> Self {
+        Self {
+            config,
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Get permission to send request to the host of the URI
+    pub(crate) fn acquire(&self, uri: &Uri) -> Result<Permit<'_>, Error> {
+        let host = uri.authority().map(|auth| auth.to_string()).unwrap_or_default();
+
+        let mut hosts = self.hosts.lock().unwrap();
+        let state = hosts.entry(host.clone()).or_insert(State::Closed { failures: 0 });
+
+        match *state {
+            State::Closed { .. } => (),
+            State::Open { until } if Instant::now() >= until => {
+                debug!("circuit of {} is half-open", host);
+                *state = State::HalfOpen { probes: 1 };
+            }
+            State::HalfOpen { ref mut probes } if *probes < self.config.half_open_probes => {
+                *probes += 1;
+            }
+            _ => return Err(Error::CircuitOpen),
+        }
+
+        Ok(Permit { circuits: self, host, done: false })
+    }
+
+    /// Give back the probe of a permit whose result was not recorded
+    fn release(&self, host: &str) {
+        let mut hosts = self.hosts.lock().unwrap();
+        if let Some(State::HalfOpen { ref mut probes }) = hosts.get_mut(host) {
+            *probes = probes.saturating_sub(1);
+        }
+    }
+
+    fn record(&self, host: &str, success: bool) {
+        let mut hosts = self.hosts.lock().unwrap();
+        let state = match hosts.get_mut(host) {
+            Some(state) => state,
+            None => return,
+        };
+
+        match *state {
+            _ if success => *state = State::Closed { failures: 0 },
+            State::Closed { ref mut failures } => {
+                *failures += 1;
+                if *failures >= self.config.failure_threshold {
+                    warn!("too many failed requests, opening circuit of {}", host);
+                    *state = State::Open { until: Instant::now() + self.config.open_duration };
+                }
+            }
+            State::HalfOpen { .. } => {
+                warn!("probe request failed, opening circuit of {}", host);
+                *state = State::Open { until: Instant::now() + self.config.open_duration };
+            }
+            State::Open { .. } => (),
+        }
+    }
+}
+
+/// Permission to send a request. Dropping it without recording the result,
+/// e.g. when the request is cancelled, counts as neither success nor failure.
+pub(crate) struct Permit<'a> {
+    circuits: &'a Circuits,
+    host: String,
+    done: bool,
+}
+
+impl Permit<'_> {
+    pub(crate) fn record(mut self, success: bool) {
+        self.done = true;
+        self.circuits.record(&self.host, success);
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.circuits.release(&self.host);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! HTTP client that sends the requests built by `RestClient`
+
+use crate::dns::{DnsConfig, Resolver};
+#[cfg(feature = "http3")]
+use crate::http3::{self, Http3Client};
+use crate::proxy::{Proxy, ProxyConnector};
+use crate::timing::{self, Phase, Timed};
+use crate::tls::{self, TlsConfig};
+use crate::transport::Transport;
+use crate::{Error, HyperClient};
+use hyper::client::connect::HttpConnector;
+use hyper::header::PROXY_AUTHORIZATION;
+use hyper::{Body, Client, Request};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "native-tls")]
+use hyper_tls::HttpsConnector;
+#[cfg(feature = "rustls")]
+use hyper_rustls::HttpsConnector;
+
+type TimedConnector = Timed<HttpsConnector<Timed<ProxyConnector<HttpConnector<Timed<Resolver>>>>>>;
+
+/// HTTP settings of the connections given with the builder
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HttpConfig {
+    /// Time to wait for the TCP connection to be opened
+    pub(crate) connect_timeout: Option<Duration>,
+
+    /// Time to wait for a connection before trying the other address family
+    pub(crate) happy_eyeballs_timeout: Option<Duration>,
+
+    /// Maximum number of idle connections kept open per host
+    pub(crate) pool_max_idle_per_host: Option<usize>,
+
+    /// Time after which idle connections are closed
+    pub(crate) pool_idle_timeout: Option<Duration>,
+
+    /// Use HTTP/2 without negotiating it
+    pub(crate) http2_only: bool,
+
+    /// Interval of HTTP/2 keep-alive pings
+    pub(crate) http2_keep_alive_interval: Option<Duration>,
+
+    /// Time to wait for acknowledgement of keep-alive ping
+    pub(crate) http2_keep_alive_timeout: Option<Duration>,
+
+    /// Send keep-alive pings also on idle connections
+    pub(crate) http2_keep_alive_while_idle: bool,
+
+    /// Initial HTTP/2 flow control window of the streams
+    pub(crate) http2_stream_window: Option<u32>,
+
+    /// Initial HTTP/2 flow control window of the connections
+    pub(crate) http2_connection_window: Option<u32>,
+
+    /// Adjust the HTTP/2 flow control windows based on the bandwidth
+    pub(crate) http2_adaptive_window: bool,
+
+    /// Send HTTPS requests with HTTP/3
+    #[cfg(feature = "http3")]
+    pub(crate) http3: bool,
+}
+
+/// HTTP client used for sending the requests
+#[derive(Clone)]
+pub(crate) enum HttpClient {
+    /// Default client that records the connection phases
+    Timed {
+        client: Client<TimedConnector>,
+        proxy: Option<Arc<Proxy>>,
+        #[cfg(feature = "http3")]
+        http3: Option<Arc<Http3Client>>,
+    },
+
+    /// Client given by the user
+    Custom(HyperClient),
+
+    /// Transport given by the user
+    Transport(Arc<dyn Transport>),
+}
+
+impl HttpClient {
+    pub(crate) fn new(
+        proxy: Option<Arc<Proxy>>,
+        http: &HttpConfig,
+        tls: &TlsConfig,
+        dns: &DnsConfig,
+    ) -> Result<Self, Error> {
+        let resolver = Timed::new(Resolver::new(dns), Phase::Dns);
+        let mut connector = HttpConnector::new_with_resolver(resolver);
+        connector.enforce_http(false);
+        connector.set_connect_timeout(http.connect_timeout);
+        if let Some(timeout) = http.happy_eyeballs_timeout {
+            connector.set_happy_eyeballs_timeout(Some(timeout));
+        }
+        let connector = Timed::new(ProxyConnector::new(connector, proxy.clone()), Phase::Tcp);
+
+        let https = tls::https_connector(connector, tls, http.http2_only)?;
+        let connector = Timed::new(https, Phase::Connection);
+        let mut builder = Client::builder();
+        if let Some(max) = http.pool_max_idle_per_host {
+            builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = http.pool_idle_timeout {
+            builder.pool_idle_timeout(timeout);
+        }
+        builder
+            .http2_only(http.http2_only)
+            .http2_keep_alive_interval(http.http2_keep_alive_interval)
+            .http2_keep_alive_while_idle(http.http2_keep_alive_while_idle)
+            .http2_initial_stream_window_size(http.http2_stream_window)
+            .http2_initial_connection_window_size(http.http2_connection_window)
+            .http2_adaptive_window(http.http2_adaptive_window);
+        if let Some(timeout) = http.http2_keep_alive_timeout {
+            builder.http2_keep_alive_timeout(timeout);
+        }
+        let client = builder.build(connector);
+
+        #[cfg(feature = "http3")]
+        let http3 = match http.http3 {
+            true => Some(Arc::new(Http3Client::new(tls::client_config(tls)?, Resolver::new(dns), http))),
+            false => None,
+        };
+
+        Ok(HttpClient::Timed {
+            client,
+            proxy,
+            #[cfg(feature = "http3")]
+            http3,
+        })
+    }
+
+    /// Send the request, recording the connection phases in `Timings`
+    /// extension of the response
+    pub(crate) async fn request(&self, mut req: Request<Body>) -> Result<hyper::Response<Body>, Error> {
+        let (client, proxy) = match *self {
+            HttpClient::Timed { ref client, ref proxy, .. } => (client, proxy),
+            HttpClient::Custom(ref client) => return Ok(client.request(req).await?),
+            HttpClient::Transport(ref transport) => return transport.send(req).await,
+        };
+
+        #[cfg(feature = "http3")]
+        if let HttpClient::Timed { http3: Some(ref http3), .. } = *self {
+            let proxied = proxy.as_ref().is_some_and(|proxy| proxy.is_proxied(req.uri()));
+            if req.uri().scheme_str() == Some("https") && !proxied {
+                if let Some(sender) = http3.connect(req.uri()).await {
+                    return http3::send(sender, req).await;
+                }
+            }
+        }
+
+        if let Some(auth) = proxy.as_ref().and_then(|proxy| proxy.request_auth(req.uri())) {
+            req.headers_mut().insert(PROXY_AUTHORIZATION, auth.clone());
+        }
+
+        let uri = req.uri().clone();
+        Ok(timing::record(&uri, client.request(req)).await?)
+    }
+}
@@ -0,0 +1,86 @@
+//! Deduplication of identical GET requests that are in flight at the same time
+
+use crate::Response;
+use bytes::Bytes;
+use hyper::header::HeaderName;
+use hyper::Request;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use tokio::sync::oneshot;
+
+type Waiter = oneshot::Sender<Option<Response<Bytes>>>;
+
+/// Requests in flight and the callers waiting for their response
+#[derive(Debug, Default)]
+pub(crate) struct Coalescer {
+    in_flight: Mutex<HashMap<String, Vec<Waiter>>>,
+}
+
+pub(crate) enum Role<'a> {
+    /// Caller sends the request and shares the response
+    Leader(Leader<'a>),
+
+    /// Caller waits for the response of identical request. `None` is received
+    /// if the request failed and no response can be shared.
+    Follower(oneshot::Receiver<Option<Response<Bytes>>>),
+}
+
+impl Coalescer {
+    /// Join identical request in flight or become the leader for the request.
+    /// Header `ignore` (request ID) is not part of the request identity.
+    pub(crate) fn join<B>(&self, req: &Request<B>, ignore: Option<&HeaderName>) -> Role<'_> {
+        let key = key(req, ignore);
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        match in_flight.get_mut(&key) {
+            Some(waiters) => {
+                let (tx, rx) = oneshot::channel();
+                waiters.push(tx);
+                Role::Follower(rx)
+            }
+            None => {
+                in_flight.insert(key.clone(), Vec::new());
+                Role::Leader(Leader { coalescer: self, key, done: false })
+            }
+        }
+    }
+
+    fn remove(&self, key: &str) -> Vec<Waiter> {
+        self.in_flight.lock().unwrap().remove(key).unwrap_or_default()
+    }
+}
+
+/// Request identity: method, URL and headers
+fn key<B>(req: &Request<B>, ignore: Option<&HeaderName>) -> String {
+    let mut key = format!("{} {}\n", req.method(), req.uri());
+    for (name, value) in req.headers().iter().filter(|(name, _)| Some(*name) != ignore) {
+        let _ = writeln!(key, "{}: {:?}", name, value);
+    }
+    key
+}
+
+/// Sender of a coalesced request. If dropped before the response is shared,
+/// the waiting callers send their own requests.
+pub(crate) struct Leader<'a> {
+    coalescer: &'a Coalescer,
+    key: String,
+    done: bool,
+}
+
+impl Leader<'_> {
+    pub(crate) fn finish(mut self, res: Option<&Response<Bytes>>) {
+        self.done = true;
+        for waiter in self.coalescer.remove(&self.key) {
+            let _ = waiter.send(res.cloned());
+        }
+    }
+}
+
+impl Drop for Leader<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.coalescer.remove(&self.key);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Decompression of response bodies
+
+use hyper::header::{HeaderMap, HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH};
+use hyper::{Body, Method, Request, Response, StatusCode};
+
+/// Content codings that are decompressed, in the order of preference
+const ENCODINGS: &[&str] = &[
+    #[cfg(feature = "brotli")]
+    "br",
+    #[cfg(feature = "gzip")]
+    "gzip",
+    #[cfg(feature = "deflate")]
+    "deflate",
+];
+
+/// Value of `Accept-Encoding` header, or `None` if no decompression feature
+/// is enabled
+pub(crate) fn accept_encoding() -> Option<HeaderValue> {
+    match ENCODINGS.is_empty() {
+        true => None,
+        false => HeaderValue::from_str(&ENCODINGS.join(", ")).ok(),
+    }
+}
+
+/// Marker in the request extensions of requests whose response body is
+/// returned as received, e.g. raw and range requests
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Identity;
+
+/// Request the body without content coding, and keep the response body as
+/// received. An `Accept-Encoding` header set by the caller is kept.
+pub(crate) fn identity(req: &mut Request<Body>) {
+    req.headers_mut().entry(ACCEPT_ENCODING).or_insert(HeaderValue::from_static("identity"));
+    req.extensions_mut().insert(Identity);
+}
+
+/// Response with the body decompressed, if it is compressed with one of the
+/// enabled content codings. `Content-Encoding` and `Content-Length` headers
+/// of a decompressed response are removed.
+pub(crate) fn decompress(method: &Method, res: Response<Body>) -> Response<Body> {
+    if *method == Method::HEAD || matches!(res.status(), StatusCode::NO_CONTENT | StatusCode::NOT_MODIFIED) {
+        return res;
+    }
+    let encoding = match content_encoding(res.headers()) {
+        Some(encoding) => encoding,
+        None => return res,
+    };
+
+    let (mut parts, body) = res.into_parts();
+    parts.headers.remove(CONTENT_ENCODING);
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, decode(encoding, body))
+}
+
+/// Enabled content coding of the response
+fn content_encoding(headers: &HeaderMap) -> Option<&'static str> {
+    let encoding = headers.get(CONTENT_ENCODING)?.to_str().ok()?.trim();
+    let encoding = match encoding {
+        "x-gzip" => "gzip",
+        encoding => encoding,
+    };
+    ENCODINGS.iter().copied().find(|enabled| enabled.eq_ignore_ascii_case(encoding))
+}
+
+#[cfg(any(feature = "gzip", feature = "deflate", feature = "brotli"))]
+fn decode(encoding: &str, body: Body) -> Body {
+    use async_compression::tokio::bufread;
+    use futures::TryStreamExt;
+    use tokio_util::io::{ReaderStream, StreamReader};
+
+    let reader = StreamReader::new(body.map_err(std::io::Error::other));
+    match encoding {
+        #[cfg(feature = "brotli")]
+        "br" => Body::wrap_stream(ReaderStream::new(bufread::BrotliDecoder::new(reader))),
+        #[cfg(feature = "gzip")]
+        "gzip" => Body::wrap_stream(ReaderStream::new(bufread::GzipDecoder::new(reader))),
+        #[cfg(feature = "deflate")]
+        "deflate" => Body::wrap_stream(ReaderStream::new(bufread::ZlibDecoder::new(reader))),
+        _ => unreachable!("content coding {} is not enabled", encoding),
+    }
+}
+
+#[cfg(not(any(feature = "gzip", feature = "deflate", feature = "brotli")))]
+fn decode(_: &str, body: Body) -> Body {
+    body
+}
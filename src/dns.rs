@@ -0,0 +1,102 @@
+//! Resolving the host names of the connections
+
+use crate::resolvers::{Resolve, SystemResolver};
+use futures::future::BoxFuture;
+use hyper::client::connect::dns::Name;
+use hyper::service::Service;
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// DNS settings given with the builder
+#[derive(Clone, Default)]
+pub(crate) struct DnsConfig {
+    /// Addresses used instead of resolving the host names, by lowercase host
+    pub(crate) overrides: HashMap<String, IpAddr>,
+
+    /// Resolver of the other host names, or system resolver if not set
+    pub(crate) resolver: Option<Arc<dyn Resolve>>,
+}
+
+/// Resolver that uses the addresses set in the builder, and the resolver of
+/// the builder for the other host names
+#[derive(Clone)]
+pub(crate) struct Resolver {
+    resolver: Arc<dyn Resolve>,
+    overrides: Arc<HashMap<String, IpAddr>>,
+}
+
+impl Resolver {
+    pub(crate) fn new(config: &DnsConfig) -> Self {
+        Self {
+            resolver: config.resolver.clone().unwrap_or_else(|| Arc::new(SystemResolver::new())),
+            overrides: Arc::new(config.overrides.clone()),
+        }
+    }
+}
+
+impl std::fmt::Debug for Resolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Resolver").field("overrides", &self.overrides).finish_non_exhaustive()
+    }
+}
+
+impl Service<Name> for Resolver {
+    type Response = std::vec::IntoIter<SocketAddr>;
+    type Error = io::Error;
+    type Future = BoxFuture<'static, io::Result<Self::Response>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        // Port is set by the connector from the URI
+        if let Some(addr) = self.overrides.get(&name.as_str().to_lowercase()) {
+            let addrs = vec![SocketAddr::new(*addr, 0)];
+            return Box::pin(async move { Ok(addrs.into_iter()) });
+        }
+
+        let resolver = self.resolver.clone();
+        Box::pin(async move {
+            let addrs = resolver.resolve(name.as_str()).await.map_err(|err| io::Error::new(err.kind(), ResolveError(err)))?;
+            let addrs = interleave(addrs);
+            Ok(addrs.into_iter().map(|addr| SocketAddr::new(addr, 0)).collect::<Vec<_>>().into_iter())
+        })
+    }
+}
+
+/// Error of the resolver, wrapped in the IO error returned to the connector
+/// so that it can be told apart from the errors of connecting
+#[derive(Debug)]
+pub(crate) struct ResolveError(io::Error);
+
+impl std::fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for ResolveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+/// Addresses in alternating address families, starting with the family of the
+/// first address, so that both families are tried early (RFC 8305)
+fn interleave(addrs: Vec<IpAddr>) -> Vec<IpAddr> {
+    let first_v6 = addrs.first().is_some_and(IpAddr::is_ipv6);
+    let (preferred, other): (Vec<_>, Vec<_>) = addrs.into_iter().partition(|addr| addr.is_ipv6() == first_v6);
+
+    let mut addrs = Vec::with_capacity(preferred.len() + other.len());
+    let (mut preferred, mut other) = (preferred.into_iter(), other.into_iter());
+    loop {
+        match (preferred.next(), other.next()) {
+            (None, None) => return addrs,
+            (first, second) => addrs.extend(first.into_iter().chain(second)),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! Formats of the request and response bodies
+
+use serde::ser::{self, Impossible, Serialize, Serializer};
+use std::fmt;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Function given to `BodyFormat::deserialize`, which deserializes the
+/// response data from the deserializer of the format
+pub type Visit<'a> =
+    &'a mut dyn for<'de> FnMut(&mut dyn erased_serde::Deserializer<'de>) -> Result<(), erased_serde::Error>;
+
+/// Format of the serialized request and response bodies, e.g. XML or
+/// MessagePack instead of JSON.
+///
+/// The format is given the data as type-erased serde values from the
+/// `erased-serde` crate, so the serializer and deserializer of the format
+/// handle the data directly, e.g. bytes, 128-bit integers and maps with
+/// non-string keys. Response body wash function is not applied to the
+/// bodies handled by the format.
+///
+/// The format is set for all requests with `Builder::body_format`, or for
+/// some requests with `RestClient::with_body_format`. JSON is used if no
+/// format is set.
+///
+/// # Examples
+/// ```ignore
+/// struct Yaml;
+///
+/// impl BodyFormat for Yaml {
+///     fn content_type(&self) -> &str {
+///         "application/yaml"
+///     }
+///
+///     fn serialize(&self, data: &dyn erased_serde::Serialize) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+///         Ok(serde_yaml::to_string(data)?.into_bytes())
+///     }
+///
+///     fn deserialize(&self, body: &[u8], visit: Visit<'_>) -> Result<(), Box<dyn Error + Send + Sync>> {
+///         let de = serde_yaml::Deserializer::from_slice(body);
+///         Ok(visit(&mut <dyn erased_serde::Deserializer>::erase(de))?)
+///     }
+/// }
+///
+/// let client = RestClient::builder().body_format(Yaml).build("http://httpbin.org").unwrap();
+/// ```
+pub trait BodyFormat: Send + Sync {
+    /// Content type of the serialized request body, e.g. `application/xml`
+    fn content_type(&self) -> &str;
+
+    /// Serialize the data to request body
+    fn serialize(&self, data: &dyn erased_serde::Serialize) -> Result<Vec<u8>, BoxError>;
+
+    /// Deserialize the response body by calling the function with the
+    /// deserializer of the body
+    fn deserialize(&self, body: &[u8], visit: Visit<'_>) -> Result<(), BoxError>;
+}
+
+/// JSON format, which is used when no format is set
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json;
+
+impl BodyFormat for Json {
+    fn content_type(&self) -> &str {
+        "application/json"
+    }
+
+    fn serialize(&self, data: &dyn erased_serde::Serialize) -> Result<Vec<u8>, BoxError> {
+        Ok(serde_json::to_vec(data)?)
+    }
+
+    fn deserialize(&self, body: &[u8], visit: Visit<'_>) -> Result<(), BoxError> {
+        let mut de = serde_json::Deserializer::from_slice(body);
+        visit(&mut <dyn erased_serde::Deserializer>::erase(&mut de))?;
+        Ok(de.end()?)
+    }
+}
+
+/// YAML format, with content type `application/yaml`
+#[cfg(feature = "yaml")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Yaml;
+
+#[cfg(feature = "yaml")]
+impl BodyFormat for Yaml {
+    fn content_type(&self) -> &str {
+        "application/yaml"
+    }
+
+    fn serialize(&self, data: &dyn erased_serde::Serialize) -> Result<Vec<u8>, BoxError> {
+        Ok(serde_yaml::to_string(data)?.into_bytes())
+    }
+
+    fn deserialize(&self, body: &[u8], visit: Visit<'_>) -> Result<(), BoxError> {
+        let de = serde_yaml::Deserializer::from_slice(body);
+        Ok(visit(&mut <dyn erased_serde::Deserializer>::erase(de))?)
+    }
+}
+
+/// Whether the data is serialized as null in JSON: unit, `None` or unit
+/// struct, possibly inside `Some` or newtype struct
+pub(crate) fn is_null<T: Serialize + ?Sized>(data: &T) -> bool {
+    data.serialize(IsNull).is_ok()
+}
+
+/// Serializer that fails for all data that is not null
+struct IsNull;
+
+#[derive(Debug)]
+struct NotNull;
+
+impl fmt::Display for NotNull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("not null")
+    }
+}
+
+impl std::error::Error for NotNull {}
+
+impl ser::Error for NotNull {
+    fn custom<T: fmt::Display>(_: T) -> Self {
+        NotNull
+    }
+}
+
+macro_rules! not_null {
+    ($($method:ident($($ty:ty),*);)*) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<(), NotNull> {
+                Err(NotNull)
+            }
+        )*
+    };
+}
+
+impl Serializer for IsNull {
+    type Ok = ();
+    type Error = NotNull;
+    type SerializeSeq = Impossible<(), NotNull>;
+    type SerializeTuple = Impossible<(), NotNull>;
+    type SerializeTupleStruct = Impossible<(), NotNull>;
+    type SerializeTupleVariant = Impossible<(), NotNull>;
+    type SerializeMap = Impossible<(), NotNull>;
+    type SerializeStruct = Impossible<(), NotNull>;
+    type SerializeStructVariant = Impossible<(), NotNull>;
+
+    not_null! {
+        serialize_bool(bool);
+        serialize_i8(i8);
+        serialize_i16(i16);
+        serialize_i32(i32);
+        serialize_i64(i64);
+        serialize_i128(i128);
+        serialize_u8(u8);
+        serialize_u16(u16);
+        serialize_u32(u32);
+        serialize_u64(u64);
+        serialize_u128(u128);
+        serialize_f32(f32);
+        serialize_f64(f64);
+        serialize_char(char);
+        serialize_str(&str);
+        serialize_bytes(&[u8]);
+        serialize_unit_variant(&'static str, u32, &'static str);
+    }
+
+    fn serialize_none(self) -> Result<(), NotNull> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), NotNull> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), NotNull> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), NotNull> {
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<(), NotNull> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), NotNull> {
+        Err(NotNull)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, NotNull> {
+        Err(NotNull)
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, NotNull> {
+        Err(NotNull)
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, NotNull> {
+        Err(NotNull)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, NotNull> {
+        Err(NotNull)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, NotNull> {
+        Err(NotNull)
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, NotNull> {
+        Err(NotNull)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, NotNull> {
+        Err(NotNull)
+    }
+}
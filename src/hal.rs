@@ -0,0 +1,119 @@
+//! Resources of Hypertext Application Language (HAL)
+//!
+//! See [HAL specification](https://datatracker.ietf.org/doc/html/draft-kelly-json-hal)
+//! for the meaning of the members of the resources.
+
+use crate::{Error, RestPath};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::ops::Deref;
+
+/// Resource with the fields `T` and the `_links` and `_embedded` members of
+/// HAL.
+///
+/// `Hal<T>` has the REST path of `T`, so it can be requested like `T`, and
+/// it dereferences to `T`. The links are followed with `RestClient::follow`.
+///
+/// # Examples
+/// ```ignore
+/// let order = client.get::<_, Hal<Order>>(1).await?;
+/// println!("total {}", order.total);
+/// if let Some(customer) = client.follow::<_, Hal<Customer>>(&order, "customer").await? {
+///     println!("customer {}", customer.name);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Hal<T> {
+    /// Fields of the resource
+    #[serde(flatten)]
+    pub resource: T,
+
+    /// Links of the resource by their relation
+    #[serde(rename = "_links", default, skip_serializing_if = "HashMap::is_empty")]
+    pub links: HashMap<String, Relation>,
+
+    /// Embedded resources by their relation
+    #[serde(rename = "_embedded", default, skip_serializing_if = "HashMap::is_empty")]
+    pub embedded: HashMap<String, Value>,
+}
+
+impl<T> Hal<T> {
+    /// First link of the relation, e.g. `next`
+    pub fn link(&self, rel: &str) -> Option<&Link> {
+        self.links(rel).first()
+    }
+
+    /// All links of the relation
+    pub fn links(&self, rel: &str) -> &[Link] {
+        match self.links.get(rel) {
+            Some(Relation::One(link)) => std::slice::from_ref(link),
+            Some(Relation::Many(links)) => links,
+            None => &[],
+        }
+    }
+
+    /// Embedded resources of the relation, deserialized to `E`, which is
+    /// usually `Hal<R>` or `Vec<Hal<R>>`
+    pub fn embedded<E: DeserializeOwned>(&self, rel: &str) -> Result<Option<E>, Error> {
+        self.embedded
+            .get(rel)
+            .map(|value| {
+                serde_json::from_value(value.clone()).map_err(|err| Error::DeserializeParseError(err, value.to_string()))
+            })
+            .transpose()
+    }
+}
+
+impl<T> Deref for Hal<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.resource
+    }
+}
+
+impl<U, T: RestPath<U>> RestPath<U> for Hal<T> {
+    fn get_path(params: U) -> Result<String, Error> {
+        T::get_path(params)
+    }
+
+    fn get_query(params: &U) -> Vec<(String, String)> {
+        T::get_query(params)
+    }
+}
+
+/// Link or links of a relation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Relation {
+    /// Relation with single link
+    One(Link),
+
+    /// Relation with array of links
+    Many(Vec<Link>),
+}
+
+/// Link to a resource
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Link {
+    /// URL of the resource, or URI template if the link is templated
+    pub href: String,
+
+    /// Link is URI template
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub templated: bool,
+
+    /// Media type of the resource
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+
+    /// Name that tells apart the links of the same relation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Human-readable title of the link
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
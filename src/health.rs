@@ -0,0 +1,112 @@
+//! Health checks of the hosts
+
+use crate::RestClient;
+use futures::future::join_all;
+use futures::stream::{BoxStream, Stream, StreamExt};
+use hyper::StatusCode;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// Health of a host, reported by `HealthChecks` when it changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostHealth {
+    /// URL of the health check of the host
+    pub url: String,
+    /// Whether the host responded with the expected status
+    pub healthy: bool,
+}
+
+/// Marker of the health check requests, which are sent to the host of the
+/// URL instead of a host chosen by the load balancer
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Probe;
+
+/// Stream of the health transitions of the hosts, returned by
+/// `RestClient::healthcheck`.
+///
+/// The hosts are checked when the stream is polled for the first time and
+/// then after each interval, as long as the stream is polled. The first
+/// result of each host is reported, and after that only the changes. With a
+/// load balancer, the hosts that fail the check are skipped until they pass
+/// it again.
+pub struct HealthChecks {
+    inner: BoxStream<'static, HostHealth>,
+}
+
+impl std::fmt::Debug for HealthChecks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HealthChecks").finish_non_exhaustive()
+    }
+}
+
+impl Stream for HealthChecks {
+    type Item = HostHealth;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+/// Health check URL of a host, with the index of the host in the load
+/// balancer if there is one
+struct Target {
+    index: Option<usize>,
+    url: String,
+    healthy: Option<bool>,
+}
+
+struct State {
+    client: RestClient,
+    targets: Vec<Target>,
+    expected: StatusCode,
+    interval: Duration,
+    events: VecDeque<HostHealth>,
+    started: bool,
+}
+
+impl State {
+    async fn check(&mut self) {
+        let probes = self.targets.iter().map(|target| self.client.probe(&target.url, self.expected));
+        let results = join_all(probes).await;
+
+        for (target, healthy) in self.targets.iter_mut().zip(results) {
+            if let (Some(index), Some(balancer)) = (target.index, self.client.balancer.as_ref()) {
+                balancer.set_health(index, healthy);
+            }
+            if target.healthy != Some(healthy) {
+                target.healthy = Some(healthy);
+                self.events.push_back(HostHealth { url: target.url.clone(), healthy });
+            }
+        }
+    }
+}
+
+impl HealthChecks {
+    pub(crate) fn new(client: RestClient, targets: Vec<(Option<usize>, String)>, expected: StatusCode, interval: Duration) -> Self {
+        let state = State {
+            client,
+            targets: targets.into_iter().map(|(index, url)| Target { index, url, healthy: None }).collect(),
+            expected,
+            interval,
+            events: VecDeque::new(),
+            started: false,
+        };
+
+        let inner = futures::stream::unfold(state, |mut state| async move {
+            loop {
+                if let Some(event) = state.events.pop_front() {
+                    return Some((event, state));
+                }
+                if state.started {
+                    tokio::time::sleep(state.interval).await;
+                }
+                state.started = true;
+                state.check().await;
+            }
+        });
+
+        HealthChecks { inner: inner.boxed() }
+    }
+}
@@ -0,0 +1,192 @@
+//! Sending the requests with HTTP/3 over QUIC
+
+use crate::client::HttpConfig;
+use crate::dns::Resolver;
+use crate::Error;
+use bytes::{Buf, Bytes};
+use futures::stream::{FuturesUnordered, StreamExt};
+use hyper::body::HttpBody;
+use hyper::client::connect::dns::Name;
+use hyper::header::*;
+use hyper::service::Service;
+use hyper::{Body, Request, Response, Uri};
+use log::debug;
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default time to wait for the QUIC handshake before falling back to TCP
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Default time to wait for a connection attempt before trying the next address
+const ATTEMPT_DELAY: Duration = Duration::from_millis(300);
+
+/// Time to use TCP for the hosts that HTTP/3 connection failed to
+const BROKEN_TTL: Duration = Duration::from_secs(300);
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+pub(crate) type SendRequest = h3::client::SendRequest<h3_quinn::OpenStreams, Bytes>;
+
+/// Open HTTP/3 connection
+struct Connection {
+    quic: quinn::Connection,
+    sender: SendRequest,
+}
+
+/// HTTP/3 client that keeps one connection per host
+pub(crate) struct Http3Client {
+    config: quinn::ClientConfig,
+    resolver: Resolver,
+    connect_timeout: Duration,
+    attempt_delay: Duration,
+
+    /// Endpoint is opened in the runtime of the first request
+    endpoint: Mutex<Option<quinn::Endpoint>>,
+
+    /// Connections by the authority of the URL
+    connections: Mutex<HashMap<String, Connection>>,
+
+    /// Hosts that HTTP/3 connection failed to, and the time of the failure
+    broken: Mutex<HashMap<String, Instant>>,
+}
+
+impl Http3Client {
+    pub(crate) fn new(mut tls: rustls::ClientConfig, resolver: Resolver, http: &HttpConfig) -> Self {
+        tls.alpn_protocols = vec![b"h3".to_vec()];
+        Self {
+            config: quinn::ClientConfig::new(Arc::new(tls)),
+            resolver,
+            connect_timeout: http.connect_timeout.unwrap_or(CONNECT_TIMEOUT),
+            attempt_delay: http.happy_eyeballs_timeout.unwrap_or(ATTEMPT_DELAY),
+            endpoint: Mutex::new(None),
+            connections: Mutex::new(HashMap::new()),
+            broken: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sender for an open connection to the server of the URL, or `None` if
+    /// HTTP/3 connection could not be opened and TCP should be used instead
+    pub(crate) async fn connect(&self, uri: &Uri) -> Option<SendRequest> {
+        let authority = uri.authority()?.to_string();
+        match self.connection(uri, &authority).await {
+            Ok(sender) => Some(sender),
+            Err(err) => {
+                debug!("HTTP/3 connection to {} failed, using TCP: {}", authority, err);
+                self.broken.lock().unwrap().entry(authority).or_insert_with(Instant::now);
+                None
+            }
+        }
+    }
+
+    async fn connection(&self, uri: &Uri, authority: &str) -> Result<SendRequest, BoxError> {
+        {
+            let mut broken = self.broken.lock().unwrap();
+            match broken.get(authority) {
+                Some(failed) if failed.elapsed() < BROKEN_TTL => return Err("connection failed recently".into()),
+                Some(_) => {
+                    broken.remove(authority);
+                }
+                None => (),
+            }
+        }
+        if let Some(conn) = self.connections.lock().unwrap().get(authority) {
+            if conn.quic.close_reason().is_none() {
+                return Ok(conn.sender.clone());
+            }
+        }
+
+        let host = uri.host().unwrap_or_default().trim_start_matches('[').trim_end_matches(']');
+        let port = uri.port_u16().unwrap_or(443);
+        let addrs: Vec<SocketAddr> = match host.parse() {
+            Ok(ip) => vec![SocketAddr::new(ip, port)],
+            Err(_) => {
+                let name: Name = host.parse()?;
+                let mut resolver = self.resolver.clone();
+                resolver.call(name).await?.map(|addr| SocketAddr::new(addr.ip(), port)).collect()
+            }
+        };
+        let endpoint = self.endpoint()?;
+
+        // Next address is tried when the previous attempts fail or do not
+        // complete in the attempt delay, and the first connection is used
+        let mut pending = addrs.into_iter();
+        let mut attempts = FuturesUnordered::new();
+        let mut last_err: BoxError = "no addresses".into();
+        loop {
+            if let Some(addr) = pending.next() {
+                match endpoint.connect_with(self.config.clone(), addr, host) {
+                    Ok(connecting) => attempts.push(tokio::time::timeout(self.connect_timeout, connecting)),
+                    Err(err) => {
+                        last_err = err.into();
+                        continue;
+                    }
+                }
+            }
+
+            let quic = match tokio::time::timeout(self.attempt_delay, attempts.next()).await {
+                Ok(Some(Ok(Ok(quic)))) => quic,
+                Ok(Some(Ok(Err(err)))) => {
+                    last_err = err.into();
+                    continue;
+                }
+                Ok(Some(Err(err))) => {
+                    last_err = err.into();
+                    continue;
+                }
+                Ok(None) => return Err(last_err),
+                Err(_) => continue,
+            };
+
+            let (mut driver, sender) = h3::client::new(h3_quinn::Connection::new(quic.clone())).await?;
+            tokio::spawn(async move {
+                let _ = futures::future::poll_fn(|cx| driver.poll_close(cx)).await;
+            });
+
+            let conn = Connection { quic, sender: sender.clone() };
+            self.connections.lock().unwrap().insert(authority.to_owned(), conn);
+            return Ok(sender);
+        }
+    }
+
+    fn endpoint(&self) -> std::io::Result<quinn::Endpoint> {
+        let mut endpoint = self.endpoint.lock().unwrap();
+        if let Some(ref endpoint) = *endpoint {
+            return Ok(endpoint.clone());
+        }
+
+        // IPv6 socket is used also for IPv4 addresses where dual-stack is supported
+        let opened = quinn::Endpoint::client(SocketAddr::new(Ipv6Addr::UNSPECIFIED.into(), 0))
+            .or_else(|_| quinn::Endpoint::client(SocketAddr::new(Ipv4Addr::UNSPECIFIED.into(), 0)))?;
+        *endpoint = Some(opened.clone());
+        Ok(opened)
+    }
+}
+
+/// Send the request on the connection and return the response with
+/// streamed body
+pub(crate) async fn send(mut sender: SendRequest, req: Request<Body>) -> Result<Response<Body>, Error> {
+    let (mut parts, mut body) = req.into_parts();
+    for name in [CONNECTION, HOST, TRANSFER_ENCODING, UPGRADE, HeaderName::from_static("keep-alive")] {
+        parts.headers.remove(name);
+    }
+
+    let mut stream = sender.send_request(Request::from_parts(parts, ())).await.map_err(Error::Http3Error)?;
+    while let Some(chunk) = body.data().await {
+        stream.send_data(chunk?).await.map_err(Error::Http3Error)?;
+    }
+    stream.finish().await.map_err(Error::Http3Error)?;
+
+    let res = stream.recv_response().await.map_err(Error::Http3Error)?;
+    let body = futures::stream::unfold(Some(stream), |stream| async move {
+        let mut stream = stream?;
+        match stream.recv_data().await {
+            Ok(Some(mut chunk)) => Some((Ok(chunk.copy_to_bytes(chunk.remaining())), Some(stream))),
+            Ok(None) => None,
+            Err(err) => Some((Err(err), None)),
+        }
+    });
+    let (parts, _) = res.into_parts();
+    Ok(Response::from_parts(parts, Body::wrap_stream(body)))
+}
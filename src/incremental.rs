@@ -0,0 +1,70 @@
+//! Deserialization of response bodies as they are received
+
+use crate::Error;
+use bytes::{Buf, Bytes};
+use hyper::body::HttpBody;
+use hyper::Body;
+use std::io::{self, Read};
+use tokio::sync::mpsc;
+
+/// Number of received chunks that wait for the deserializer
+const CHUNKS_IN_FLIGHT: usize = 4;
+
+/// Deserialize the JSON body chunk by chunk as it is received, so that the
+/// whole body is never in memory.
+///
+/// The deserializer runs on the blocking thread pool and reads the chunks
+/// from a channel. Errors have no body text, as the body is not stored.
+pub(crate) async fn parse_json<T>(mut body: Body) -> Result<T, Error>
+where
+    T: serde::de::DeserializeOwned + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel(CHUNKS_IN_FLIGHT);
+    let parser = tokio::task::spawn_blocking(move || {
+        serde_json::from_reader::<_, T>(ChunkReader { rx, chunk: Bytes::new() })
+    });
+
+    let mut read_error = None;
+    while let Some(chunk) = body.data().await {
+        match chunk {
+            // Deserializer has stopped on error if the channel is closed
+            Ok(chunk) => {
+                if tx.send(chunk).await.is_err() {
+                    break;
+                }
+            }
+            Err(err) => {
+                read_error = Some(err);
+                break;
+            }
+        }
+    }
+    drop(tx);
+
+    let parsed = parser.await.map_err(|_| Error::RequestError)?;
+    if let Some(err) = read_error {
+        return Err(Error::BodyReadError(err));
+    }
+    parsed.map_err(|err| Error::DeserializeParseError(err, String::new()))
+}
+
+/// Reader of the chunks received from the channel
+struct ChunkReader {
+    rx: mpsc::Receiver<Bytes>,
+    chunk: Bytes,
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while !self.chunk.has_remaining() {
+            match self.rx.blocking_recv() {
+                Some(chunk) => self.chunk = chunk,
+                None => return Ok(0),
+            }
+        }
+
+        let len = buf.len().min(self.chunk.remaining());
+        self.chunk.copy_to_slice(&mut buf[..len]);
+        Ok(len)
+    }
+}
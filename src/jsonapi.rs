@@ -0,0 +1,275 @@
+//! Documents of JSON:API (`application/vnd.api+json`)
+//!
+//! See [JSON:API specification](https://jsonapi.org/format/) for the meaning
+//! of the members of the documents.
+
+use crate::format::{BodyFormat, Json, Visit};
+use crate::Error;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Media type of JSON:API documents
+pub const MEDIA_TYPE: &str = "application/vnd.api+json";
+
+/// Body format for sending JSON:API documents, which is JSON with the media
+/// type of JSON:API
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonApi;
+
+impl BodyFormat for JsonApi {
+    fn content_type(&self) -> &str {
+        MEDIA_TYPE
+    }
+
+    fn serialize(&self, data: &dyn erased_serde::Serialize) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        Json.serialize(data)
+    }
+
+    fn deserialize(&self, body: &[u8], visit: Visit<'_>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Json.deserialize(body, visit)
+    }
+}
+
+/// Links by their name, e.g. `self` or `next`
+pub type Links = HashMap<String, Link>;
+
+/// Link of a document, resource or relationship
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Link {
+    /// Link given as URL
+    Url(String),
+
+    /// Link object with URL and meta information
+    Object {
+        href: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        meta: Option<Value>,
+    },
+}
+
+impl Link {
+    /// URL of the link
+    pub fn href(&self) -> &str {
+        match *self {
+            Link::Url(ref href) | Link::Object { ref href, .. } => href,
+        }
+    }
+}
+
+/// Top-level document with primary data `D`, which is `Resource<A>` for a
+/// single resource or `Vec<Resource<A>>` for a collection.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Document<D> {
+    /// Primary data
+    pub data: D,
+
+    /// Resources related to the primary data, requested with `include`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub included: Vec<Resource<Value>>,
+
+    /// Links of the primary data, e.g. for pagination
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub links: Links,
+
+    /// Meta information of the document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
+}
+
+impl<D> Document<D> {
+    /// Included resource with the type and ID, with the attributes
+    /// deserialized to `A`
+    pub fn find_included<A: DeserializeOwned>(&self, identifier: &Identifier) -> Result<Option<Resource<A>>, Error> {
+        self.included
+            .iter()
+            .find(|resource| {
+                resource.resource_type == identifier.resource_type && resource.id.as_deref() == Some(&identifier.id)
+            })
+            .map(Resource::cast)
+            .transpose()
+    }
+
+    /// Included resources of the relationship of the resource, with the
+    /// attributes deserialized to `B`. Related resources that are not
+    /// included in the document are skipped.
+    pub fn related<A, B: DeserializeOwned>(&self, resource: &Resource<A>, relationship: &str) -> Result<Vec<Resource<B>>, Error> {
+        let identifiers = match resource.relationships.get(relationship) {
+            Some(relationship) => relationship.identifiers(),
+            None => &[],
+        };
+        let mut related = Vec::new();
+        for identifier in identifiers {
+            related.extend(self.find_included(identifier)?);
+        }
+        Ok(related)
+    }
+}
+
+/// Resource object with attributes `A`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Resource<A> {
+    /// Type of the resource, e.g. `articles`
+    #[serde(rename = "type")]
+    pub resource_type: String,
+
+    /// ID of the resource, which may be missing in resources sent to the
+    /// server to be created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Attributes of the resource
+    pub attributes: A,
+
+    /// Relationships of the resource by their name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub relationships: HashMap<String, Relationship>,
+
+    /// Links of the resource
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub links: Links,
+
+    /// Meta information of the resource
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
+}
+
+impl Resource<Value> {
+    /// Resource with the attributes deserialized to `A`
+    fn cast<A: DeserializeOwned>(&self) -> Result<Resource<A>, Error> {
+        let attributes = serde_json::from_value(self.attributes.clone())
+            .map_err(|err| Error::DeserializeParseError(err, self.attributes.to_string()))?;
+        Ok(Resource {
+            resource_type: self.resource_type.clone(),
+            id: self.id.clone(),
+            attributes,
+            relationships: self.relationships.clone(),
+            links: self.links.clone(),
+            meta: self.meta.clone(),
+        })
+    }
+}
+
+/// Type and ID of a resource
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Identifier {
+    /// Type of the resource
+    #[serde(rename = "type")]
+    pub resource_type: String,
+
+    /// ID of the resource
+    pub id: String,
+}
+
+/// Relationship of a resource to other resources
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Relationship {
+    /// Related resources, if the server sent them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Related>,
+
+    /// Links of the relationship
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub links: Links,
+
+    /// Meta information of the relationship
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<Value>,
+}
+
+impl Relationship {
+    /// Identifiers of the related resources
+    pub fn identifiers(&self) -> &[Identifier] {
+        match self.data {
+            Some(Related::One(ref identifier)) => std::slice::from_ref(identifier),
+            Some(Related::Many(ref identifiers)) => identifiers,
+            None => &[],
+        }
+    }
+}
+
+/// Resource linkage of a to-one or to-many relationship
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Related {
+    /// Related resource of to-one relationship
+    One(Identifier),
+
+    /// Related resources of to-many relationship
+    Many(Vec<Identifier>),
+}
+
+/// Query parameters of JSON:API requests.
+///
+/// # Examples
+/// ```ignore
+/// // include=author,comments.author&fields[articles]=title,author&sort=-created
+/// let params = Params::new()
+///     .include("author")
+///     .include("comments.author")
+///     .fields("articles", &["title", "author"])
+///     .sort("-created");
+/// let res = client.get_jsonapi::<_, Article>(1, &params).await?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Params {
+    include: Vec<String>,
+    fields: Vec<(String, Vec<String>)>,
+    other: Vec<(String, String)>,
+}
+
+impl Params {
+    /// Create empty parameters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Include the related resources of the relationship path, e.g.
+    /// `comments.author`
+    pub fn include(mut self, path: &str) -> Self {
+        self.include.push(path.to_owned());
+        self
+    }
+
+    /// Return only the fields of the resource type (sparse fieldset)
+    pub fn fields(mut self, resource_type: &str, fields: &[&str]) -> Self {
+        let fields = fields.iter().map(|field| field.to_string()).collect();
+        self.fields.push((resource_type.to_owned(), fields));
+        self
+    }
+
+    /// Sort the collection by the fields, separated by comma. Field prefixed
+    /// with `-` sorts in descending order.
+    pub fn sort(mut self, fields: &str) -> Self {
+        self.other.push((String::from("sort"), fields.to_owned()));
+        self
+    }
+
+    /// Add `page[name]` parameter, e.g. `page[number]` or `page[cursor]`
+    pub fn page(mut self, name: &str, value: &str) -> Self {
+        self.other.push((format!("page[{}]", name), value.to_owned()));
+        self
+    }
+
+    /// Add `filter[name]` parameter
+    pub fn filter(mut self, name: &str, value: &str) -> Self {
+        self.other.push((format!("filter[{}]", name), value.to_owned()));
+        self
+    }
+
+    /// Query parameters in the order they were added, with the included
+    /// relationships first
+    pub fn pairs(&self) -> Vec<(String, String)> {
+        let mut pairs = Vec::new();
+        if !self.include.is_empty() {
+            pairs.push((String::from("include"), self.include.join(",")));
+        }
+        for (resource_type, fields) in &self.fields {
+            pairs.push((format!("fields[{}]", resource_type), fields.join(",")));
+        }
+        pairs.extend(self.other.iter().cloned());
+        pairs
+    }
+}
@@ -0,0 +1,68 @@
+//! Integration with `tower` services and layers
+
+use crate::client::HttpClient;
+use crate::{Error, RestClient};
+use futures::future::BoxFuture;
+use hyper::{Body, Request, Response};
+use std::task::{Context, Poll};
+use tower::util::BoxCloneSyncService;
+use tower::{BoxError, Layer, Service, ServiceExt};
+
+/// Service that sends the requests to the server, wrapped by the layers
+pub(crate) type HttpService = BoxCloneSyncService<Request<Body>, Response<Body>, Error>;
+
+/// Function that wraps the service with a layer
+pub(crate) type ApplyLayer = Box<dyn FnOnce(HttpService) -> HttpService + Send>;
+
+pub(crate) fn apply_layer<L>(layer: L) -> ApplyLayer
+where
+    L: Layer<HttpService> + Send + 'static,
+    L::Service: Service<Request<Body>, Response = Response<Body>> + Clone + Send + Sync + 'static,
+    <L::Service as Service<Request<Body>>>::Error: Into<BoxError>,
+    <L::Service as Service<Request<Body>>>::Future: Send + 'static,
+{
+    Box::new(move |inner| {
+        let service = layer.layer(inner).map_err(|err| service_error(err.into()));
+        BoxCloneSyncService::new(service)
+    })
+}
+
+/// Service that sends the requests with Hyper client
+pub(crate) fn client_service(client: HttpClient) -> HttpService {
+    BoxCloneSyncService::new(tower::service_fn(move |req| {
+        let client = client.clone();
+        async move { client.request(req).await }
+    }))
+}
+
+/// Convert error returned by a layer, keeping the errors of this crate as is
+fn service_error(err: BoxError) -> Error {
+    let err = match err.downcast::<Error>() {
+        Ok(err) => return *err,
+        Err(err) => err,
+    };
+    if err.is::<tower::timeout::error::Elapsed>() {
+        return Error::TimeoutError;
+    }
+    Error::ServiceError(err)
+}
+
+/// Requests sent through the service go through the same pipeline as the
+/// requests made with the client methods, without the client timeout.
+impl Service<Request<Body>> for RestClient {
+    type Response = Response<Body>;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Response<Body>, Error>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move {
+            client.acquire_rate().await;
+            client.send(req).await
+        })
+    }
+}
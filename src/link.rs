@@ -0,0 +1,120 @@
+//! Links of `Link` response headers (RFC 8288, formerly RFC 5988)
+
+use crate::Error;
+use hyper::header::{HeaderMap, LINK};
+use url::Url;
+
+/// Link sent by the server in `Link` header, e.g. the next page of a
+/// paginated collection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// URL of the link, which may be relative to the base URL
+    pub href: String,
+
+    /// Relation of the link, e.g. `next`, `prev`, `first` or `last`
+    pub rel: String,
+
+    /// Other parameters of the link, e.g. `title` or `type`
+    pub params: Vec<(String, String)>,
+}
+
+impl Link {
+    /// Value of the parameter of the link
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// URL of the link resolved against the URL of the response, as the links
+/// are relative to it (RFC 8288)
+pub(crate) fn resolve(base: &str, href: &str) -> Result<Url, Error> {
+    Url::parse(base).and_then(|base| base.join(href)).map_err(|_| Error::UrlError)
+}
+
+/// Links of all `Link` headers, in order. A link with several relations,
+/// e.g. `rel="next last"`, is returned once for each relation. Malformed
+/// links are skipped.
+pub(crate) fn parse(headers: &HeaderMap) -> Vec<Link> {
+    let mut links = Vec::new();
+    for value in headers.get_all(LINK) {
+        if let Ok(value) = value.to_str() {
+            parse_value(value, &mut links);
+        }
+    }
+    links
+}
+
+fn parse_value(value: &str, links: &mut Vec<Link>) {
+    let mut rest = value;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+        let href = match rest.strip_prefix('<').and_then(|s| s.split_once('>')) {
+            Some((href, tail)) => {
+                rest = tail;
+                href
+            }
+            None => return,
+        };
+
+        let mut rels = Vec::new();
+        let mut params = Vec::new();
+        while let Some(tail) = rest.trim_start().strip_prefix(';') {
+            let (param, tail) = parse_param(tail);
+            rest = tail;
+            match param {
+                // Only the first rel parameter counts
+                Some((name, value)) if name == "rel" && rels.is_empty() => {
+                    rels.extend(value.split_ascii_whitespace().map(str::to_ascii_lowercase));
+                }
+                Some((name, _)) if name == "rel" => (),
+                Some(param) => params.push(param),
+                None => (),
+            }
+        }
+
+        for rel in rels {
+            links.push(Link {
+                href: href.trim().to_owned(),
+                rel,
+                params: params.clone(),
+            });
+        }
+
+        // Skip anything up to the next link
+        match rest.find(',') {
+            Some(end) => rest = &rest[end..],
+            None => return,
+        }
+    }
+}
+
+/// Parse `name=value` or `name="quoted value"` parameter, returning it and
+/// the rest of the header value
+fn parse_param(s: &str) -> (Option<(String, String)>, &str) {
+    let end = s.find([';', ',', '=']).unwrap_or(s.len());
+    let name = s[..end].trim().to_ascii_lowercase();
+    let rest = &s[end..];
+    let rest = match rest.strip_prefix('=') {
+        Some(rest) => rest.trim_start(),
+        None => return (Some((name, String::new())).filter(|(name, _)| !name.is_empty()), rest),
+    };
+
+    if let Some(quoted) = rest.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = quoted.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => return (Some((name, value)), &quoted[i + 1..]),
+                '\\' => value.extend(chars.next().map(|(_, c)| c)),
+                c => value.push(c),
+            }
+        }
+        (Some((name, value)), "")
+    } else {
+        let end = rest.find([';', ',']).unwrap_or(rest.len());
+        (Some((name, rest[..end].trim().to_owned())), &rest[end..])
+    }
+}
@@ -0,0 +1,116 @@
+//! Counters and latency histogram of the requests made by the client
+
+use hyper::Method;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Upper bounds of the latency histogram buckets in milliseconds
+const BUCKETS_MS: [u64; 11] = [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Handle for collecting metrics of the requests.
+///
+/// The same handle can be given to several clients and cloned to read the
+/// values with `snapshot` while the clients are used.
+///
+/// # Examples
+/// ```ignore
+/// let metrics = Metrics::new();
+/// let client = RestClient::builder().metrics(metrics.clone()).build("http://httpbin.org").unwrap();
+/// client.get::<_, HttpBinAnything>(()).await.unwrap();
+///
+/// let snapshot = metrics.snapshot();
+/// println!("{:?}", snapshot.requests);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    inner: Arc<Mutex<MetricsSnapshot>>,
+}
+
+/// Values collected by `Metrics`
+#[derive(Debug, Clone, Default)]
+pub struct MetricsSnapshot {
+    /// Number of requests by method and response status. Status is `None`
+    /// for requests that failed without a response.
+    pub requests: HashMap<(Method, Option<u16>), u64>,
+
+    /// Number of retried requests
+    pub retries: u64,
+
+    /// Number of request body bytes sent, for bodies with known length
+    pub bytes_sent: u64,
+
+    /// Number of response body bytes received
+    pub bytes_received: u64,
+
+    /// Time until the response headers were received
+    pub latency: Histogram,
+}
+
+/// Histogram of durations
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    /// Upper bound and number of values of each bucket. The last bucket has
+    /// `Duration::MAX` bound.
+    pub buckets: Vec<(Duration, u64)>,
+
+    /// Number of values
+    pub count: u64,
+
+    /// Sum of the values
+    pub sum: Duration,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        let buckets = BUCKETS_MS
+            .iter()
+            .map(|ms| Duration::from_millis(*ms))
+            .chain(std::iter::once(Duration::MAX))
+            .map(|bound| (bound, 0))
+            .collect();
+
+        Self {
+            buckets,
+            count: 0,
+            sum: Duration::ZERO,
+        }
+    }
+}
+
+impl Histogram {
+    fn record(&mut self, value: Duration) {
+        if let Some(bucket) = self.buckets.iter_mut().find(|(bound, _)| value <= *bound) {
+            bucket.1 += 1;
+        }
+        self.count += 1;
+        self.sum += value;
+    }
+}
+
+impl Metrics {
+    /// Create handle with all values set to zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current values of the metrics
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.inner.lock().unwrap().clone()
+    }
+
+    pub(crate) fn record_request(&self, method: &Method, status: Option<u16>, latency: Duration, sent: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        *inner.requests.entry((method.clone(), status)).or_default() += 1;
+        inner.bytes_sent += sent;
+        inner.latency.record(latency);
+    }
+
+    pub(crate) fn record_received(&self, bytes: u64) {
+        self.inner.lock().unwrap().bytes_received += bytes;
+    }
+
+    pub(crate) fn record_retry(&self) {
+        self.inner.lock().unwrap().retries += 1;
+    }
+}
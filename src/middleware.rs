@@ -0,0 +1,65 @@
+//! Middleware for intercepting requests made by the client
+
+use crate::{Error, RestClient};
+use futures::future::BoxFuture;
+use hyper::{Body, Request, Response};
+use std::sync::Arc;
+
+/// Future returned by the middleware
+pub type MiddlewareFuture<'a> = BoxFuture<'a, Result<Response<Body>, Error>>;
+
+/// Interceptor for the requests made by the client.
+///
+/// Middleware receives the outgoing request and passes it on to the rest of
+/// the chain with `next.run(req)`. It can modify the request before passing it
+/// on, inspect or modify the response, or return a response without calling
+/// the next one at all. Middleware is called in the order it was added to the
+/// builder.
+///
+/// # Examples
+/// ```ignore
+/// struct Logger;
+///
+/// impl Middleware for Logger {
+///     fn handle<'a>(&'a self, req: Request<Body>, next: Next<'a>) -> MiddlewareFuture<'a> {
+///         Box::pin(async move {
+///             let uri = req.uri().clone();
+///             let res = next.run(req).await?;
+///             println!("{} {}", uri, res.status());
+///             Ok(res)
+///         })
+///     }
+/// }
+///
+/// let client = RestClient::builder().with_middleware(Logger).build("http://httpbin.org").unwrap();
+/// ```
+pub trait Middleware: Send + Sync {
+    /// Handle the request and return the response sent by the server
+    fn handle<'a>(&'a self, req: Request<Body>, next: Next<'a>) -> MiddlewareFuture<'a>;
+}
+
+/// Rest of the middleware chain, ending in the HTTP client. It can be run
+/// several times, for example to send the request again.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    client: &'a RestClient,
+    middleware: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(client: &'a RestClient, middleware: &'a [Arc<dyn Middleware>]) -> Self {
+        Self { client, middleware }
+    }
+
+    pub(crate) fn client(&self) -> &'a RestClient {
+        self.client
+    }
+
+    /// Pass the request to the next middleware or send it to the server
+    pub fn run(self, req: Request<Body>) -> MiddlewareFuture<'a> {
+        match self.middleware.split_first() {
+            Some((first, rest)) => first.handle(req, Next::new(self.client, rest)),
+            None => self.client.transport(req),
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! Mock transport for testing the code that uses the client
+
+use crate::transport::Transport;
+use crate::Error;
+use futures::future::BoxFuture;
+use hyper::header::{HeaderName, HeaderValue};
+use hyper::{Body, Method, Request, Response, StatusCode};
+use std::sync::{Arc, Mutex};
+
+/// Transport that answers the requests according to the expectations set
+/// in the test, without a server.
+///
+/// Each expectation matches the method and path of the request, and
+/// optionally its query, headers and body. The first expectation that
+/// matches the request and has not been used up gives the response. Other
+/// requests get 404 status, and they are reported by `verify` after the
+/// test together with the expectations that were not met.
+///
+/// # Examples
+/// ```ignore
+/// let mock = MockClient::new();
+/// mock.when(Method::GET, "/devices/1").respond(200, r#"{"id": 1}"#);
+/// mock.when(Method::DELETE, "/devices/1").times(1).respond(204, "");
+///
+/// let client = RestClient::builder().transport(mock.clone()).build("http://api.test").unwrap();
+/// // ... run the code under test with the client
+/// mock.verify();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockClient {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    expectations: Vec<Expectation>,
+
+    /// Requests that did not match any expectation, as "METHOD path"
+    unexpected: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Expectation {
+    method: Method,
+    path: String,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    body: Option<String>,
+    times: Option<usize>,
+    calls: usize,
+    status: StatusCode,
+    response_headers: Vec<(HeaderName, HeaderValue)>,
+    response_body: String,
+}
+
+impl Expectation {
+    /// Request matches the expectation, whose path includes the query if it
+    /// has one
+    fn matches(&self, req: &Request<Body>, body: &[u8]) -> bool {
+        let path = match self.path.contains('?') {
+            true => req.uri().path_and_query().map_or("/", |path| path.as_str()),
+            false => req.uri().path(),
+        };
+
+        self.method == req.method()
+            && self.path == path
+            && self.times.is_none_or(|times| self.calls < times)
+            && self.headers.iter().all(|(name, value)| req.headers().get(name) == Some(value))
+            && self.body.as_ref().is_none_or(|expected| expected.as_bytes() == body)
+    }
+
+    fn response(&self) -> Response<Body> {
+        let mut res = Response::new(Body::from(self.response_body.clone()));
+        *res.status_mut() = self.status;
+        for (name, value) in &self.response_headers {
+            res.headers_mut().append(name.clone(), value.clone());
+        }
+        res
+    }
+}
+
+impl MockClient {
+    /// Create mock without expectations
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start an expectation for requests with the method and path, e.g.
+    /// `/devices/1`. If the path has a query, e.g. `/devices?page=2`, the
+    /// query of the request must be the same.
+    pub fn when(&self, method: Method, path: &str) -> When<'_> {
+        When {
+            mock: self,
+            expectation: Expectation {
+                method,
+                path: path.to_owned(),
+                headers: Vec::new(),
+                body: None,
+                times: None,
+                calls: 0,
+                status: StatusCode::OK,
+                response_headers: Vec::new(),
+                response_body: String::new(),
+            },
+        }
+    }
+
+    /// Check that all expectations were met and no other requests were made
+    ///
+    /// # Panics
+    ///
+    /// If an expectation was used less than the number of times set for it,
+    /// or not at all, or a request did not match any expectation.
+    pub fn verify(&self) {
+        let state = self.state.lock().unwrap();
+        let mut errors = Vec::new();
+        for expectation in &state.expectations {
+            match expectation.times {
+                Some(times) if expectation.calls != times => errors.push(format!(
+                    "expected {} {} {} times, got {}",
+                    expectation.method, expectation.path, times, expectation.calls
+                )),
+                None if expectation.calls == 0 => {
+                    errors.push(format!("expected {} {}, got none", expectation.method, expectation.path))
+                }
+                _ => (),
+            }
+        }
+        for req in &state.unexpected {
+            errors.push(format!("unexpected request {}", req));
+        }
+
+        if !errors.is_empty() {
+            panic!("mock expectations not met:\n{}", errors.join("\n"));
+        }
+    }
+
+    /// Remove all expectations and recorded requests
+    pub fn reset(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.expectations.clear();
+        state.unexpected.clear();
+    }
+}
+
+impl Transport for MockClient {
+    fn send<'a>(&'a self, req: Request<Body>) -> BoxFuture<'a, Result<Response<Body>, Error>> {
+        Box::pin(async move {
+            let (parts, body) = req.into_parts();
+            let body = hyper::body::to_bytes(body).await.map_err(Error::BodyReadError)?;
+            let req = Request::from_parts(parts, Body::empty());
+
+            let mut state = self.state.lock().unwrap();
+            match state.expectations.iter_mut().find(|expectation| expectation.matches(&req, &body)) {
+                Some(expectation) => {
+                    expectation.calls += 1;
+                    Ok(expectation.response())
+                }
+                None => {
+                    let path = req.uri().path_and_query().map_or("/", |path| path.as_str());
+                    let desc = format!("{} {}", req.method(), path);
+                    let mut res = Response::new(Body::from(format!("no mock for {}", desc)));
+                    *res.status_mut() = StatusCode::NOT_FOUND;
+                    state.unexpected.push(desc);
+                    Ok(res)
+                }
+            }
+        })
+    }
+}
+
+/// Expectation being set with `MockClient::when`. It is added to the mock
+/// when the response is set with `respond`.
+#[derive(Debug)]
+pub struct When<'a> {
+    mock: &'a MockClient,
+    expectation: Expectation,
+}
+
+impl When<'_> {
+    /// Match only requests with the header value
+    ///
+    /// # Panics
+    ///
+    /// If the header name or value is invalid.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.expectation.headers.push(header(name, value));
+        self
+    }
+
+    /// Match only requests with the body, e.g. the JSON sent in POST
+    pub fn body(mut self, body: &str) -> Self {
+        self.expectation.body = Some(body.to_owned());
+        self
+    }
+
+    /// Expect exactly this number of requests. Further requests are not
+    /// matched by the expectation.
+    ///
+    /// Default is one or more requests
+    pub fn times(mut self, times: usize) -> Self {
+        self.expectation.times = Some(times);
+        self
+    }
+
+    /// Add header to the response
+    ///
+    /// # Panics
+    ///
+    /// If the header name or value is invalid.
+    pub fn respond_header(mut self, name: &str, value: &str) -> Self {
+        self.expectation.response_headers.push(header(name, value));
+        self
+    }
+
+    /// Respond to the matching requests with the status and body, and add
+    /// the expectation to the mock
+    ///
+    /// # Panics
+    ///
+    /// If the status code is invalid.
+    pub fn respond(mut self, status: u16, body: &str) {
+        self.expectation.status = StatusCode::from_u16(status).expect("invalid status code");
+        self.expectation.response_body = body.to_owned();
+        self.mock.state.lock().unwrap().expectations.push(self.expectation);
+    }
+}
+
+fn header(name: &str, value: &str) -> (HeaderName, HeaderValue) {
+    let name = HeaderName::try_from(name).expect("invalid header name");
+    let value = HeaderValue::try_from(value).expect("invalid header value");
+    (name, value)
+}
@@ -0,0 +1,148 @@
+//! Builder for `multipart/form-data` request bodies
+
+use crate::Error;
+use bytes::{BufMut, Bytes, BytesMut};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Multipart form that can be sent with `post_multipart`.
+///
+/// # Examples
+/// ```ignore
+/// let form = Multipart::new()
+///     .text("description", "log archive")
+///     .file("upload", "/tmp/logs.zip");
+/// client.post_multipart::<_, Upload>((), form).await.unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Multipart {
+    boundary: String,
+    parts: Vec<Part>,
+}
+
+#[derive(Debug)]
+struct Part {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+    data: PartData,
+}
+
+#[derive(Debug)]
+enum PartData {
+    Bytes(Bytes),
+    File(PathBuf),
+}
+
+impl Default for Multipart {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Multipart {
+    /// Create an empty form with a random boundary
+    pub fn new() -> Self {
+        let a = RandomState::new().build_hasher().finish();
+        let b = RandomState::new().build_hasher().finish();
+
+        Self {
+            boundary: format!("restson-{a:016x}{b:016x}"),
+            parts: Vec::new(),
+        }
+    }
+
+    /// Add a text field to the form
+    pub fn text(mut self, name: &str, value: &str) -> Self {
+        self.parts.push(Part {
+            name: name.to_owned(),
+            filename: None,
+            content_type: None,
+            data: PartData::Bytes(Bytes::copy_from_slice(value.as_bytes())),
+        });
+        self
+    }
+
+    /// Add a file field to the form
+    ///
+    /// The file is read when the request is made and it is sent with
+    /// `application/octet-stream` content type.
+    pub fn file<P: AsRef<Path>>(mut self, name: &str, path: P) -> Self {
+        let path = path.as_ref();
+        self.parts.push(Part {
+            name: name.to_owned(),
+            filename: path.file_name().map(|name| name.to_string_lossy().into_owned()),
+            content_type: Some("application/octet-stream".to_owned()),
+            data: PartData::File(path.to_owned()),
+        });
+        self
+    }
+
+    /// Add a file field from in-memory data to the form
+    pub fn bytes(mut self, name: &str, filename: &str, content_type: &str, data: impl Into<Bytes>) -> Self {
+        self.parts.push(Part {
+            name: name.to_owned(),
+            filename: Some(filename.to_owned()),
+            content_type: Some(content_type.to_owned()),
+            data: PartData::Bytes(data.into()),
+        });
+        self
+    }
+
+    /// Value of the `Content-Type` header for this form
+    pub fn content_type(&self) -> String {
+        format!("multipart/form-data; boundary={}", self.boundary)
+    }
+
+    /// Encode the form into request body, reading the files as needed
+    pub(crate) async fn encode(self) -> Result<Bytes, Error> {
+        let mut buf = BytesMut::new();
+
+        for part in self.parts {
+            buf.put_slice(b"--");
+            buf.put_slice(self.boundary.as_bytes());
+            buf.put_slice(b"\r\n");
+
+            buf.put_slice(b"Content-Disposition: form-data; name=\"");
+            buf.put_slice(escape(&part.name).as_bytes());
+            buf.put_slice(b"\"");
+            if let Some(filename) = part.filename {
+                buf.put_slice(b"; filename=\"");
+                buf.put_slice(escape(&filename).as_bytes());
+                buf.put_slice(b"\"");
+            }
+            buf.put_slice(b"\r\n");
+
+            if let Some(content_type) = part.content_type {
+                buf.put_slice(b"Content-Type: ");
+                buf.put_slice(content_type.as_bytes());
+                buf.put_slice(b"\r\n");
+            }
+            buf.put_slice(b"\r\n");
+
+            match part.data {
+                PartData::Bytes(data) => buf.put_slice(&data),
+                PartData::File(path) => {
+                    let data = tokio::fs::read(path).await.map_err(Error::IoError)?;
+                    buf.put_slice(&data);
+                }
+            }
+            buf.put_slice(b"\r\n");
+        }
+
+        buf.put_slice(b"--");
+        buf.put_slice(self.boundary.as_bytes());
+        buf.put_slice(b"--\r\n");
+
+        Ok(buf.freeze())
+    }
+}
+
+/// Escape field name or filename for use in quoted header parameter
+fn escape(value: &str) -> String {
+    value
+        .replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
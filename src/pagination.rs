@@ -0,0 +1,139 @@
+//! Paginated collections
+
+use crate::{link, Error, Response, RestClient};
+use futures::stream::{self, BoxStream, Stream};
+use hyper::{Body, Request};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+/// Strategy for finding the next page of a paginated collection.
+///
+/// `LinkHeader` follows the `next` link of the `Link` header. Other
+/// strategies are implemented with closures that take the response of a page
+/// and return the URL or path of the next page, e.g. from a cursor in the
+/// body.
+///
+/// # Examples
+/// ```ignore
+/// let pages = client.get_pages_with::<_, Items, _>((), |res: &Response<Items>| {
+///     res.next_cursor.as_ref().map(|cursor| format!("items?cursor={}", cursor))
+/// })?;
+/// ```
+pub trait Pagination<T>: Send + Sync {
+    /// URL or path of the page after the response, or `None` if the
+    /// response is the last page. Paths are resolved against the base URL,
+    /// and URLs to another origin are requested without the credentials.
+    fn next(&self, res: &Response<T>) -> Option<String>;
+}
+
+/// Pagination that follows the `next` link of the `Link` header. The link is
+/// resolved against the URL of the response.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LinkHeader;
+
+impl<T> Pagination<T> for LinkHeader {
+    fn next(&self, res: &Response<T>) -> Option<String> {
+        let link = res.link("next")?;
+        match link::resolve(res.url(), &link.href) {
+            Ok(url) => Some(url.into()),
+            Err(_) => Some(link.href),
+        }
+    }
+}
+
+impl<T, F> Pagination<T> for F
+where
+    F: Fn(&Response<T>) -> Option<String> + Send + Sync,
+{
+    fn next(&self, res: &Response<T>) -> Option<String> {
+        self(res)
+    }
+}
+
+/// Collection whose pages are concatenated by `RestClient::get_all`.
+///
+/// # Examples
+/// ```ignore
+/// #[derive(Deserialize)]
+/// struct Users {
+///     users: Vec<User>,
+/// }
+///
+/// impl Paginated for Users {
+///     type Item = User;
+///
+///     fn into_items(self) -> Vec<User> {
+///         self.users
+///     }
+/// }
+/// ```
+pub trait Paginated {
+    /// Type of the items of the collection
+    type Item;
+
+    /// Items of the page
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+/// Stream of the pages of a paginated collection, returned by
+/// `RestClient::get_pages`.
+///
+/// Pages are requested one at a time when the stream is polled. The stream
+/// ends after the last page, or after the first error.
+pub struct Pages<T> {
+    inner: BoxStream<'static, Result<Response<T>, Error>>,
+}
+
+impl<T> std::fmt::Debug for Pages<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pages").finish_non_exhaustive()
+    }
+}
+
+impl<T> Stream for Pages<T> {
+    type Item = Result<Response<T>, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+impl<T: serde::de::DeserializeOwned + Send + 'static> Pages<T> {
+    pub(crate) fn new(client: RestClient, req: Request<Body>, pagination: Arc<dyn Pagination<T>>) -> Self {
+        let state = State { client, next: Some(Ok(req)), pagination };
+        let inner = stream::unfold(state, |mut state| async move {
+            let item = state.next_page().await?;
+            Some((item, state))
+        });
+        Self { inner: Box::pin(inner) }
+    }
+}
+
+struct State<T> {
+    client: RestClient,
+
+    /// Request of the next page, or the error of making it
+    next: Option<Result<Request<Body>, Error>>,
+
+    pagination: Arc<dyn Pagination<T>>,
+}
+
+impl<T: serde::de::DeserializeOwned> State<T> {
+    async fn next_page(&mut self) -> Option<Result<Response<T>, Error>> {
+        let req = match self.next.take()? {
+            Ok(req) => req,
+            Err(err) => return Some(Err(err)),
+        };
+
+        let res = match self.client.run_request_parsed(req).await {
+            Ok(res) => res,
+            Err(err) => return Some(Err(err)),
+        };
+        self.next = self
+            .pagination
+            .next(&res)
+            .map(|next| self.client.make_link_request(&next));
+        Some(Ok(res))
+    }
+}
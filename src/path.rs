@@ -0,0 +1,162 @@
+//! Building the paths of the requests
+
+use crate::Error;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
+use std::fmt::{self, Display};
+use url::Url;
+
+/// Characters that are percent-encoded in a path segment, so that the value
+/// can not end the segment or the path
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'[')
+    .add(b'\\')
+    .add(b']')
+    .add(b'^')
+    .add(b'`')
+    .add(b'{')
+    .add(b'|')
+    .add(b'}');
+
+/// Percent-encode the value to be used as a segment of the path, e.g. in
+/// `get_path`. Slashes, spaces, `#`, `?` and `%` are encoded, so a value can
+/// not change the path or add a query or a fragment. Returns
+/// `Error::InvalidValue` for `.` and `..`, which would be resolved as dot
+/// segments and change the path.
+///
+/// # Examples
+/// ```
+/// assert_eq!(restson::encode_path_segment("a b/c?").unwrap(), "a%20b%2Fc%3F");
+/// assert!(restson::encode_path_segment("..").is_err());
+/// ```
+pub fn encode_path_segment<T: Display + ?Sized>(value: &T) -> Result<String, Error> {
+    let value = value.to_string();
+    if value == "." || value == ".." {
+        return Err(Error::InvalidValue);
+    }
+    Ok(utf8_percent_encode(&value, PATH_SEGMENT).to_string())
+}
+
+/// Path built from segments, each percent-encoded with
+/// `encode_path_segment`. The segments are joined with slashes, so a value
+/// with a slash, `?` or `#` stays in its own segment.
+///
+/// # Examples
+/// ```
+/// use restson::PathSegments;
+///
+/// let path = PathSegments::new().push("devices")?.push(1234)?.push("temp/in?")?;
+/// assert_eq!(path.to_string(), "devices/1234/temp%2Fin%3F");
+/// # Ok::<(), restson::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathSegments {
+    path: String,
+}
+
+impl PathSegments {
+    /// Create empty path
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add segment to the end of the path. Returns `Error::InvalidValue` if
+    /// the segment is `.` or `..`.
+    pub fn push(mut self, segment: impl Display) -> Result<Self, Error> {
+        let segment = encode_path_segment(&segment)?;
+        if !self.path.is_empty() {
+            self.path.push('/');
+        }
+        self.path.push_str(&segment);
+        Ok(self)
+    }
+}
+
+impl fmt::Display for PathSegments {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.path)
+    }
+}
+
+impl From<PathSegments> for String {
+    fn from(path: PathSegments) -> Self {
+        path.path
+    }
+}
+
+/// How the paths of the requests are joined to the base URL
+///
+/// With `Url`, the path is resolved like a link relative to the base URL:
+/// `https://host/api/v2` joined with `devices` gives `https://host/api/devices`
+/// because the base URL has no trailing slash, and a path starting with `/`
+/// replaces the whole base path. With `Append`, the path is always appended
+/// to the base path, so both `devices` and `/devices` give
+/// `https://host/api/v2/devices`. Absolute URLs are used as they are in both
+/// modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathJoinMode {
+    #[default]
+    Url,
+    Append,
+}
+
+/// Join the path of the request to the base URL
+pub(crate) fn join(base: &Url, path: &str, mode: PathJoinMode) -> Result<Url, Error> {
+    match mode {
+        PathJoinMode::Url => base.join(path),
+        PathJoinMode::Append if base.path().ends_with('/') => base.join(path.trim_start_matches('/')),
+        PathJoinMode::Append => {
+            let mut base = base.clone();
+            base.set_path(&format!("{}/", base.path()));
+            base.join(path.trim_start_matches('/'))
+        }
+    }
+    .map_err(|_| Error::UrlError)
+}
+
+/// Implement `RestPath` for the type from path templates, without a
+/// procedural macro. The parameters are given in parentheses and used in the
+/// path by name, and their values are percent-encoded with
+/// `encode_path_segment`, which rejects `.` and `..`.
+///
+/// # Examples
+/// ```ignore
+/// rest_path!(Device, () => "devices", (id: u32) => "devices/{id}");
+/// rest_path!(Sensor, (id: u32, name: &str) => "devices/{id}/sensors/{name}");
+/// ```
+#[macro_export]
+macro_rules! rest_path {
+    ($ty:ty, $($params:tt => $path:literal),+ $(,)?) => {
+        $($crate::rest_path!(@impl $ty, $params => $path);)+
+    };
+    (@impl $ty:ty, () => $path:literal) => {
+        impl $crate::RestPath<()> for $ty {
+            fn get_path(_: ()) -> ::std::result::Result<::std::string::String, $crate::Error> {
+                ::std::result::Result::Ok(::std::format!($path))
+            }
+        }
+    };
+    (@impl $ty:ty, ($name:ident: $param:ty) => $path:literal) => {
+        impl $crate::RestPath<$param> for $ty {
+            fn get_path($name: $param) -> ::std::result::Result<::std::string::String, $crate::Error> {
+                let $name = $crate::encode_path_segment(&$name)?;
+                ::std::result::Result::Ok(::std::format!($path))
+            }
+        }
+    };
+    (@impl $ty:ty, ($($name:ident: $param:ty),+) => $path:literal) => {
+        impl $crate::RestPath<($($param),+)> for $ty {
+            fn get_path(($($name),+): ($($param),+)) -> ::std::result::Result<::std::string::String, $crate::Error> {
+                $(let $name = $crate::encode_path_segment(&$name)?;)+
+                ::std::result::Result::Ok(::std::format!($path))
+            }
+        }
+    };
+}
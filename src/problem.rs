@@ -0,0 +1,37 @@
+//! Problem details for HTTP APIs (RFC 7807)
+
+use serde::{Deserialize, Serialize};
+
+/// Problem details sent by the server with `application/problem+json` content type.
+///
+/// See [RFC 7807](https://tools.ietf.org/html/rfc7807) for the meaning of the fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Problem {
+    /// URI reference that identifies the problem type
+    #[serde(rename = "type", default = "about_blank")]
+    pub problem_type: String,
+
+    /// Short, human-readable summary of the problem type
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// HTTP status code generated by the origin server
+    #[serde(default)]
+    pub status: Option<u16>,
+
+    /// Human-readable explanation specific to this occurrence of the problem
+    #[serde(default)]
+    pub detail: Option<String>,
+
+    /// URI reference that identifies the specific occurrence of the problem
+    #[serde(default)]
+    pub instance: Option<String>,
+
+    /// Additional members defined by the problem type
+    #[serde(flatten)]
+    pub extensions: serde_json::Map<String, serde_json::Value>,
+}
+
+fn about_blank() -> String {
+    String::from("about:blank")
+}
@@ -0,0 +1,302 @@
+//! Connecting to the servers through HTTP proxy
+
+use crate::Error;
+use futures::future::BoxFuture;
+use hyper::client::connect::{Connected, Connection};
+use hyper::header::HeaderValue;
+use hyper::http::uri::Scheme;
+use hyper::service::Service;
+use hyper::Uri;
+use percent_encoding::percent_decode_str;
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+use url::Url;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Maximum size of the proxy response to CONNECT request
+const MAX_CONNECT_RESPONSE: usize = 8192;
+
+/// HTTP proxy for the requests
+#[derive(Debug)]
+pub(crate) struct Proxy {
+    /// Proxy for plain HTTP requests
+    http: Option<ProxyServer>,
+
+    /// Proxy for HTTPS requests
+    https: Option<ProxyServer>,
+
+    /// Hosts that are connected without proxy
+    no_proxy: Vec<String>,
+}
+
+impl Proxy {
+    /// Proxy for all requests
+    pub(crate) fn new(url: &str) -> Result<Self, Error> {
+        let uri = proxy_uri(url)?;
+        Ok(Self {
+            http: Some(uri.clone()),
+            https: Some(uri),
+            no_proxy: Vec::new(),
+        })
+    }
+
+    /// Proxy from `http_proxy`, `https_proxy` and `no_proxy` environment
+    /// variables, or their uppercase variants. Returns `None` if no proxy is
+    /// set.
+    pub(crate) fn from_env() -> Result<Option<Self>, Error> {
+        let var = |name: &str| {
+            std::env::var(name)
+                .or_else(|_| std::env::var(name.to_uppercase()))
+                .ok()
+                .filter(|value| !value.is_empty())
+        };
+
+        let proxy = Self {
+            http: var("http_proxy").as_deref().map(proxy_uri).transpose()?,
+            https: var("https_proxy").as_deref().map(proxy_uri).transpose()?,
+            no_proxy: var("no_proxy")
+                .unwrap_or_default()
+                .split(',')
+                .map(|host| host.trim().to_lowercase())
+                .filter(|host| !host.is_empty())
+                .collect(),
+        };
+        match proxy.http.is_some() || proxy.https.is_some() {
+            true => Ok(Some(proxy)),
+            false => Ok(None),
+        }
+    }
+
+    /// Use Basic authentication with the proxy servers
+    pub(crate) fn set_auth(&mut self, user: &str, pass: &str) -> Result<(), Error> {
+        let auth = basic_auth(user, pass)?;
+        for server in self.http.iter_mut().chain(self.https.iter_mut()) {
+            server.auth = Some(auth.clone());
+        }
+        Ok(())
+    }
+
+    /// `Proxy-Authorization` header for plain HTTP request sent to the proxy
+    pub(crate) fn request_auth(&self, dst: &Uri) -> Option<&HeaderValue> {
+        match dst.scheme() {
+            Some(scheme) if *scheme == Scheme::HTTPS => None,
+            _ => self.select(dst)?.auth.as_ref(),
+        }
+    }
+
+    /// Destination is connected through a proxy
+    #[cfg(feature = "http3")]
+    pub(crate) fn is_proxied(&self, dst: &Uri) -> bool {
+        self.select(dst).is_some()
+    }
+
+    /// Proxy for connecting to the destination
+    fn select(&self, dst: &Uri) -> Option<&ProxyServer> {
+        let proxy = match dst.scheme() {
+            Some(scheme) if *scheme == Scheme::HTTPS => self.https.as_ref(),
+            _ => self.http.as_ref(),
+        }?;
+        match self.no_proxy.iter().any(|pattern| no_proxy_matches(pattern, dst)) {
+            true => None,
+            false => Some(proxy),
+        }
+    }
+}
+
+/// Proxy server and its credentials
+#[derive(Debug, Clone)]
+struct ProxyServer {
+    uri: Uri,
+    auth: Option<HeaderValue>,
+}
+
+/// Parse proxy URL, with `http://` as the default scheme. Credentials in the
+/// URL are used for Basic authentication.
+fn proxy_uri(url: &str) -> Result<ProxyServer, Error> {
+    let url = match url.contains("://") {
+        true => url.to_owned(),
+        false => format!("http://{}", url),
+    };
+    let url = Url::parse(&url).map_err(|_| Error::UrlError)?;
+    let host = match url.host_str() {
+        Some(host) if url.scheme() == "http" => host,
+        _ => return Err(Error::UrlError),
+    };
+
+    let uri = format!("http://{}:{}", host, url.port().unwrap_or(80));
+    let uri = uri.parse().map_err(|_| Error::UrlError)?;
+    let auth = match url.username() {
+        "" => None,
+        user => {
+            let decode = |value| percent_decode_str(value).decode_utf8().map_err(|_| Error::UrlError);
+            Some(basic_auth(&decode(user)?, &decode(url.password().unwrap_or_default())?)?)
+        }
+    };
+    Ok(ProxyServer { uri, auth })
+}
+
+fn basic_auth(user: &str, pass: &str) -> Result<HeaderValue, Error> {
+    let credentials = base64::encode(format!("{}:{}", user, pass));
+    HeaderValue::from_str(&format!("Basic {}", credentials)).map_err(|_| Error::InvalidValue)
+}
+
+/// Destination matches `no_proxy` entry: `*` for all hosts, or the host
+/// name, optionally with port. Entry matches also the subdomains of the host,
+/// and leading `.` or `*.` in the entry is ignored.
+fn no_proxy_matches(pattern: &str, dst: &Uri) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+
+    // Colons of IPv6 address without brackets are not port separators
+    let (pattern, port) = match pattern.rsplit_once(':') {
+        Some((host, port)) if host.ends_with(']') || !host.contains(':') => (host, port.parse::<u16>().ok()),
+        _ => (pattern, None),
+    };
+    let pattern = pattern.trim_start_matches('*').trim_start_matches('.');
+    let pattern = pattern.trim_start_matches('[').trim_end_matches(']');
+
+    let host = dst.host().unwrap_or_default().to_lowercase();
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let default_port = match dst.scheme() {
+        Some(scheme) if *scheme == Scheme::HTTPS => 443,
+        _ => 80,
+    };
+    if port.is_some_and(|port| port != dst.port_u16().unwrap_or(default_port)) {
+        return false;
+    }
+
+    host == pattern || host.ends_with(&format!(".{}", pattern))
+}
+
+/// Connector that opens the connections through the proxy, if one is set.
+/// Plain HTTP requests are sent to the proxy, and HTTPS connections are
+/// tunneled through it with CONNECT request.
+#[derive(Debug, Clone)]
+pub(crate) struct ProxyConnector<C> {
+    inner: C,
+    proxy: Option<Arc<Proxy>>,
+}
+
+impl<C> ProxyConnector<C> {
+    pub(crate) fn new(inner: C, proxy: Option<Arc<Proxy>>) -> Self {
+        Self { inner, proxy }
+    }
+}
+
+impl<C> Service<Uri> for ProxyConnector<C>
+where
+    C: Service<Uri>,
+    C::Response: AsyncRead + AsyncWrite + Connection + Unpin + Send + 'static,
+    C::Error: Into<BoxError>,
+    C::Future: Send + 'static,
+{
+    type Response = ProxyStream<C::Response>;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<Self::Response, BoxError>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        let proxy = match self.proxy.as_ref().and_then(|proxy| proxy.select(&dst)) {
+            Some(proxy) => proxy.clone(),
+            None => {
+                let fut = self.inner.call(dst);
+                return Box::pin(async move {
+                    let inner = fut.await.map_err(Into::into)?;
+                    Ok(ProxyStream { inner, proxied: false })
+                });
+            }
+        };
+
+        let fut = self.inner.call(proxy.uri);
+        Box::pin(async move {
+            let mut inner = fut.await.map_err(Into::into)?;
+            if dst.scheme() == Some(&Scheme::HTTPS) {
+                tunnel(&mut inner, &dst, proxy.auth.as_ref()).await?;
+                return Ok(ProxyStream { inner, proxied: false });
+            }
+            Ok(ProxyStream { inner, proxied: true })
+        })
+    }
+}
+
+/// Open tunnel to the destination through the proxy
+async fn tunnel<S>(stream: &mut S, dst: &Uri, auth: Option<&HeaderValue>) -> io::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let host = dst
+        .host()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing host"))?;
+    let port = dst.port_u16().unwrap_or(443);
+    let mut req = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n").into_bytes();
+    if let Some(auth) = auth {
+        req.extend_from_slice(b"Proxy-Authorization: ");
+        req.extend_from_slice(auth.as_bytes());
+        req.extend_from_slice(b"\r\n");
+    }
+    req.extend_from_slice(b"\r\n");
+    stream.write_all(&req).await?;
+
+    let mut res = Vec::new();
+    let mut buf = [0; 1024];
+    while !res.windows(4).any(|window| window == b"\r\n\r\n") {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        res.extend_from_slice(&buf[..n]);
+        if res.len() > MAX_CONNECT_RESPONSE {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "proxy response too large"));
+        }
+    }
+
+    let status = String::from_utf8_lossy(&res);
+    let status = status.lines().next().unwrap_or_default();
+    match status.split(' ').nth(1) {
+        Some("200") => Ok(()),
+        _ => Err(io::Error::other(format!("proxy refused tunnel: {}", status))),
+    }
+}
+
+/// Connection opened by `ProxyConnector`
+#[derive(Debug)]
+pub(crate) struct ProxyStream<S> {
+    inner: S,
+
+    /// Requests are sent to the proxy in absolute form
+    proxied: bool,
+}
+
+impl<S: Connection> Connection for ProxyStream<S> {
+    fn connected(&self) -> Connected {
+        self.inner.connected().proxy(self.proxied)
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for ProxyStream<S> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_read(cx, buf)
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for ProxyStream<S> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
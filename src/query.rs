@@ -0,0 +1,550 @@
+//! Query parameters of the requests
+
+use crate::Error;
+use serde::ser::{self, Error as _, Impossible, Serialize};
+use serde_urlencoded::ser::Error as SerError;
+use std::fmt;
+
+/// Encoding of sequences in query parameters serialized from a struct
+///
+/// With `Repeat`, `tags: vec!["a", "b"]` is sent as `tags=a&tags=b`, with
+/// `Comma` as `tags=a,b` and with `Brackets` as `tags[]=a&tags[]=b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrayStyle {
+    #[default]
+    Repeat,
+    Comma,
+    Brackets,
+}
+
+/// Query parameters of the `*_with` requests, given as a slice, array or
+/// vector of name-value pairs. Values of slices can be of any type that
+/// implements `Display`. Arrays and vectors are limited to text values, so
+/// that the type of an empty query, e.g. `&vec![]`, can be inferred.
+///
+/// # Examples
+/// ```ignore
+/// client.get_with::<_, Items>((), &vec![("limit", "50"), ("offset", "100")]).await?;
+///
+/// // Values of other types in a slice
+/// client.get_with::<_, Items>((), &[("limit", 50), ("offset", 100)][..]).await?;
+///
+/// // Values of different types
+/// let query: Vec<(&str, &dyn Display)> = vec![("limit", &50), ("active", &true)];
+/// client.get_with::<_, Items>((), &query[..]).await?;
+/// ```
+pub trait ToQuery {
+    /// Name-value pairs of the query parameters, in order
+    fn to_query(&self) -> Vec<(String, String)>;
+}
+
+impl<K: AsRef<str>, V: fmt::Display> ToQuery for [(K, V)] {
+    fn to_query(&self) -> Vec<(String, String)> {
+        self.iter()
+            .map(|(name, value)| (name.as_ref().to_owned(), value.to_string()))
+            .collect()
+    }
+}
+
+impl<const N: usize> ToQuery for [(&str, &str); N] {
+    fn to_query(&self) -> Vec<(String, String)> {
+        self[..].to_query()
+    }
+}
+
+impl ToQuery for Vec<(&str, &str)> {
+    fn to_query(&self) -> Vec<(String, String)> {
+        self[..].to_query()
+    }
+}
+
+/// Owned query parameters, e.g. for parameters computed at runtime or kept
+/// across await points.
+///
+/// # Examples
+/// ```ignore
+/// let mut query = QueryPairs::new().push("limit", 50);
+/// if let Some(cursor) = cursor {
+///     query = query.push("cursor", cursor);
+/// }
+/// let res = client.get_with::<_, Items>((), &query).await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryPairs {
+    pairs: Vec<(String, String)>,
+}
+
+impl QueryPairs {
+    /// Create empty query parameters
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add parameter. The same name can be added more than once.
+    pub fn push(mut self, name: &str, value: impl fmt::Display) -> Self {
+        self.pairs.push((name.to_owned(), value.to_string()));
+        self
+    }
+
+    /// Name-value pairs of the parameters, in the order they were added
+    pub fn pairs(&self) -> &[(String, String)] {
+        &self.pairs
+    }
+}
+
+impl<K: AsRef<str>, V: fmt::Display> FromIterator<(K, V)> for QueryPairs {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(pairs: I) -> Self {
+        let pairs = pairs.into_iter().map(|(name, value)| (name.as_ref().to_owned(), value.to_string()));
+        Self { pairs: pairs.collect() }
+    }
+}
+
+impl ToQuery for QueryPairs {
+    fn to_query(&self) -> Vec<(String, String)> {
+        self.pairs.clone()
+    }
+}
+
+/// Serialize the query struct or map to name-value pairs. Fields that are
+/// `None` are skipped, and sequences are encoded with the array style.
+pub(crate) fn to_pairs<Q: Serialize + ?Sized>(query: &Q, style: ArrayStyle) -> Result<Vec<(String, String)>, Error> {
+    let mut pairs = Vec::new();
+    query
+        .serialize(PairSerializer { pairs: &mut pairs, style })
+        .map_err(Error::SerializeFormError)?;
+    Ok(pairs)
+}
+
+fn unsupported<T>() -> Result<T, SerError> {
+    Err(SerError::custom("unsupported query parameter value"))
+}
+
+/// Serializer of the query, which must be a struct or a map
+struct PairSerializer<'a> {
+    pairs: &'a mut Vec<(String, String)>,
+    style: ArrayStyle,
+}
+
+impl PairSerializer<'_> {
+    fn push(&mut self, name: String, value: Value) {
+        match value {
+            Value::Skip => (),
+            Value::One(value) => self.pairs.push((name, value)),
+            Value::Many(values) => match self.style {
+                ArrayStyle::Repeat => self.pairs.extend(values.into_iter().map(|value| (name.clone(), value))),
+                ArrayStyle::Brackets => {
+                    let name = name + "[]";
+                    self.pairs.extend(values.into_iter().map(|value| (name.clone(), value)));
+                }
+                ArrayStyle::Comma if values.is_empty() => (),
+                ArrayStyle::Comma => self.pairs.push((name, values.join(","))),
+            },
+        }
+    }
+}
+
+/// Serialize only the query struct or map itself, not its fields
+macro_rules! top_level_only {
+    ($($method:ident($($arg:ty),*)),*) => {
+        $(fn $method(self, $(_: $arg),*) -> Result<Self::Ok, Self::Error> {
+            Err(SerError::custom("query must be a struct or a map"))
+        })*
+    };
+}
+
+impl<'a> ser::Serializer for PairSerializer<'a> {
+    type Ok = ();
+    type Error = SerError;
+    type SerializeSeq = Impossible<(), SerError>;
+    type SerializeTuple = Impossible<(), SerError>;
+    type SerializeTupleStruct = Impossible<(), SerError>;
+    type SerializeTupleVariant = Impossible<(), SerError>;
+    type SerializeMap = MapSerializer<'a>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), SerError>;
+
+    top_level_only!(
+        serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32), serialize_i64(i64),
+        serialize_u8(u8), serialize_u16(u16), serialize_u32(u32), serialize_u64(u64), serialize_f32(f32),
+        serialize_f64(f64), serialize_char(char), serialize_str(&str), serialize_bytes(&[u8]),
+        serialize_unit_variant(&'static str, u32, &'static str)
+    );
+
+    fn serialize_none(self) -> Result<(), SerError> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), SerError> {
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<(), SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), SerError> {
+        unsupported()
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, SerError> {
+        unsupported()
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, SerError> {
+        unsupported()
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, SerError> {
+        unsupported()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerError> {
+        unsupported()
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, SerError> {
+        Ok(MapSerializer { inner: self, name: None })
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self, SerError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, SerError> {
+        unsupported()
+    }
+}
+
+impl ser::SerializeStruct for PairSerializer<'_> {
+    type Ok = ();
+    type Error = SerError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, name: &'static str, value: &T) -> Result<(), SerError> {
+        let value = value.serialize(ValueSerializer)?;
+        self.push(name.to_owned(), value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), SerError> {
+        Ok(())
+    }
+}
+
+struct MapSerializer<'a> {
+    inner: PairSerializer<'a>,
+    name: Option<String>,
+}
+
+impl ser::SerializeMap for MapSerializer<'_> {
+    type Ok = ();
+    type Error = SerError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerError> {
+        self.name = Some(key.serialize(ScalarSerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        let name = self.name.take().ok_or_else(|| SerError::custom("query value without name"))?;
+        let value = value.serialize(ValueSerializer)?;
+        self.inner.push(name, value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), SerError> {
+        Ok(())
+    }
+}
+
+/// Value of a query parameter
+enum Value {
+    Skip,
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Serialize scalar values with `ScalarSerializer`
+macro_rules! scalars {
+    ($ok:expr; $($method:ident($arg:ty)),*) => {
+        $(fn $method(self, value: $arg) -> Result<Self::Ok, Self::Error> {
+            ser::Serializer::$method(ScalarSerializer, value).map($ok)
+        })*
+    };
+}
+
+/// Serializer of the value of a query parameter, which may be a sequence of
+/// scalar values
+struct ValueSerializer;
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = SerError;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = Impossible<Value, SerError>;
+    type SerializeTupleVariant = Impossible<Value, SerError>;
+    type SerializeMap = Impossible<Value, SerError>;
+    type SerializeStruct = Impossible<Value, SerError>;
+    type SerializeStructVariant = Impossible<Value, SerError>;
+
+    scalars!(
+        Value::One; serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32),
+        serialize_i64(i64), serialize_u8(u8), serialize_u16(u16), serialize_u32(u32), serialize_u64(u64),
+        serialize_f32(f32), serialize_f64(f64), serialize_char(char), serialize_str(&str)
+    );
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<Value, SerError> {
+        unsupported()
+    }
+
+    fn serialize_none(self) -> Result<Value, SerError> {
+        Ok(Value::Skip)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value, SerError> {
+        Ok(Value::Skip)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Value, SerError> {
+        Ok(Value::Skip)
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<Value, SerError> {
+        Ok(Value::One(variant.to_owned()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<Value, SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Value, SerError> {
+        unsupported()
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, SerError> {
+        Ok(SeqSerializer(Vec::with_capacity(len.unwrap_or(0))))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, SerError> {
+        Ok(SeqSerializer(Vec::with_capacity(len)))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, SerError> {
+        unsupported()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerError> {
+        unsupported()
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, SerError> {
+        unsupported()
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, SerError> {
+        unsupported()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, SerError> {
+        unsupported()
+    }
+}
+
+/// Serializer of the elements of a sequence. Elements that are `None` are
+/// skipped.
+struct SeqSerializer(Vec<String>);
+
+impl SeqSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        match value.serialize(ValueSerializer)? {
+            Value::Skip => Ok(()),
+            Value::One(value) => {
+                self.0.push(value);
+                Ok(())
+            }
+            Value::Many(_) => unsupported(),
+        }
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Value;
+    type Error = SerError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerError> {
+        Ok(Value::Many(self.0))
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Value;
+    type Error = SerError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Value, SerError> {
+        Ok(Value::Many(self.0))
+    }
+}
+
+/// Serializer of a scalar value to text
+struct ScalarSerializer;
+
+/// Serialize the value with its `Display` implementation
+macro_rules! display {
+    ($($method:ident($arg:ty)),*) => {
+        $(fn $method(self, value: $arg) -> Result<String, SerError> {
+            Ok(value.to_string())
+        })*
+    };
+}
+
+impl ser::Serializer for ScalarSerializer {
+    type Ok = String;
+    type Error = SerError;
+    type SerializeSeq = Impossible<String, SerError>;
+    type SerializeTuple = Impossible<String, SerError>;
+    type SerializeTupleStruct = Impossible<String, SerError>;
+    type SerializeTupleVariant = Impossible<String, SerError>;
+    type SerializeMap = Impossible<String, SerError>;
+    type SerializeStruct = Impossible<String, SerError>;
+    type SerializeStructVariant = Impossible<String, SerError>;
+
+    display!(
+        serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32), serialize_i64(i64),
+        serialize_u8(u8), serialize_u16(u16), serialize_u32(u32), serialize_u64(u64), serialize_f32(f32),
+        serialize_f64(f64), serialize_char(char), serialize_str(&str)
+    );
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<String, SerError> {
+        unsupported()
+    }
+
+    fn serialize_none(self) -> Result<String, SerError> {
+        unsupported()
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, SerError> {
+        unsupported()
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<String, SerError> {
+        unsupported()
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<String, SerError> {
+        Ok(variant.to_owned())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<String, SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<String, SerError> {
+        unsupported()
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, SerError> {
+        unsupported()
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, SerError> {
+        unsupported()
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, SerError> {
+        unsupported()
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerError> {
+        unsupported()
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, SerError> {
+        unsupported()
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, SerError> {
+        unsupported()
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, SerError> {
+        unsupported()
+    }
+}
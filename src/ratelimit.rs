@@ -0,0 +1,76 @@
+//! Token bucket for limiting the rate of outgoing requests
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct Bucket {
+    /// Available tokens, negative when requests are waiting for their turn
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token bucket shared by all clones of the client
+#[derive(Debug)]
+pub(crate) struct RateLimiter {
+    rate: f64,
+    burst: f64,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(requests_per_second: f64, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        Self {
+            rate: requests_per_second,
+            burst,
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                updated: Instant::now(),
+            }),
+        }
+    }
+
+    /// Wait until a request can be sent. The reserved token is given back if
+    /// the waiting is cancelled.
+    pub(crate) async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock().unwrap();
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+            bucket.updated = now;
+
+            // Reserve a token so that waiting requests are served in order
+            bucket.tokens -= 1.0;
+            if bucket.tokens >= 0.0 {
+                return;
+            }
+            Duration::try_from_secs_f64(-bucket.tokens / self.rate).unwrap_or(Duration::MAX)
+        };
+
+        let reservation = Reservation { limiter: self, refund: true };
+        tokio::time::sleep(wait).await;
+        reservation.keep();
+    }
+}
+
+/// Token reserved by a waiting request
+struct Reservation<'a> {
+    limiter: &'a RateLimiter,
+    refund: bool,
+}
+
+impl Reservation<'_> {
+    fn keep(mut self) {
+        self.refund = false;
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if self.refund {
+            self.limiter.bucket.lock().unwrap().tokens += 1.0;
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Following of redirect responses
+
+use crate::{Error, ReplayBody, Response};
+use bytes::Bytes;
+use hyper::header::*;
+use hyper::{Body, Method, Request, StatusCode, Uri};
+use url::Url;
+
+/// Redirect that was followed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redirect {
+    /// URL of the request that was redirected
+    pub url: String,
+
+    /// Status of the redirect response
+    pub status: StatusCode,
+
+    /// URL where the request was redirected to
+    pub location: String,
+}
+
+/// Extension of a request to another origin (scheme, host or port) than where
+/// the credentials of the client are sent, e.g. a redirect or a link of a
+/// response to another host, whose credentials were removed. Middleware that
+/// adds credentials, such as `Session`, must not add them to these requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrossOrigin;
+
+/// Policy for following redirects.
+///
+/// Responses with 301, 302, 303, 307 or 308 status and `Location` header are
+/// followed until a response without redirect is received. POST requests
+/// redirected with 301 or 302 status, and all requests redirected with 303
+/// status, are sent again as GET without the body. Requests with streamed
+/// body are not redirected.
+///
+/// # Examples
+/// ```ignore
+/// let client = RestClient::builder()
+///     .redirect(RedirectPolicy::new(5).allow_cross_origin(false))
+///     .build("http://httpbin.org")
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RedirectPolicy {
+    max_redirects: usize,
+    cross_origin: bool,
+    strip_auth: bool,
+}
+
+impl Default for RedirectPolicy {
+    fn default() -> Self {
+        Self::new(10)
+    }
+}
+
+impl RedirectPolicy {
+    /// Create policy that follows at most `max_redirects` redirects for a
+    /// request before failing with `Error::TooManyRedirects`
+    pub fn new(max_redirects: usize) -> Self {
+        Self {
+            max_redirects,
+            cross_origin: true,
+            strip_auth: true,
+        }
+    }
+
+    /// Follow redirects to other origins (scheme, host and port). When not
+    /// allowed, the redirect response is returned as `Error::HttpError`.
+    ///
+    /// Default is yes
+    pub fn allow_cross_origin(mut self, value: bool) -> Self {
+        self.cross_origin = value;
+        self
+    }
+
+    /// Remove `Authorization`, `Proxy-Authorization` and `Cookie` headers, and
+    /// the headers set with `RestClient::set_header`, when redirected to
+    /// another origin (scheme, host or port), and mark the request with
+    /// `CrossOrigin` extension
+    ///
+    /// Default is yes
+    pub fn strip_auth(mut self, value: bool) -> Self {
+        self.strip_auth = value;
+        self
+    }
+
+    /// Next request for the redirect response, or `None` if the response is
+    /// not followed. `redirects` is the number of redirects followed so far.
+    pub(crate) fn follow(
+        &self,
+        mut req: Request<Body>,
+        res: &Response<Bytes>,
+        redirects: usize,
+    ) -> Result<Option<Request<Body>>, Error> {
+        let status = res.status();
+        if !matches!(status.as_u16(), 301 | 302 | 303 | 307 | 308) {
+            return Ok(None);
+        }
+        let location = match res.headers().get(LOCATION).and_then(|value| value.to_str().ok()) {
+            Some(location) => location,
+            None => return Ok(None),
+        };
+        if redirects >= self.max_redirects {
+            return Err(Error::TooManyRedirects);
+        }
+
+        let url = Url::parse(&req.uri().to_string())
+            .and_then(|url| url.join(location))
+            .map_err(|_| Error::UrlError)?;
+        let uri: Uri = url.as_str().parse().map_err(|_| Error::UrlError)?;
+
+        let same_origin = uri.host() == req.uri().host()
+            && uri.scheme() == req.uri().scheme()
+            && uri.port_u16() == req.uri().port_u16();
+        if !same_origin && !self.cross_origin {
+            return Ok(None);
+        }
+
+        let to_get = status == StatusCode::SEE_OTHER && req.method() != Method::HEAD
+            || matches!(status, StatusCode::MOVED_PERMANENTLY | StatusCode::FOUND) && req.method() == Method::POST;
+        if to_get {
+            *req.method_mut() = Method::GET;
+            *req.body_mut() = Body::empty();
+            req.extensions_mut().insert(ReplayBody(Bytes::new()));
+            req.headers_mut().remove(CONTENT_TYPE);
+            req.headers_mut().remove(CONTENT_LENGTH);
+        }
+
+        if !same_origin && self.strip_auth {
+            strip_credentials(&mut req);
+        }
+
+        *req.uri_mut() = uri;
+        Ok(Some(req))
+    }
+}
+
+/// Remove the credentials from a request to another origin and mark it with
+/// `CrossOrigin` extension
+pub(crate) fn strip_credentials(req: &mut Request<Body>) {
+    req.headers_mut().remove(AUTHORIZATION);
+    req.headers_mut().remove(PROXY_AUTHORIZATION);
+    req.headers_mut().remove(COOKIE);
+    req.extensions_mut().insert(CrossOrigin);
+}
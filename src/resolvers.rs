@@ -0,0 +1,308 @@
+//! Resolvers of the host names of the servers
+
+use crate::dns::{DnsConfig, Resolver};
+use crate::tls::{self, TlsConfig};
+use crate::Error;
+use futures::future::BoxFuture;
+use hyper::client::connect::dns::{GaiResolver, Name};
+use hyper::client::HttpConnector;
+use hyper::header::{ACCEPT, CONTENT_TYPE};
+use hyper::service::Service;
+use hyper::{Body, Client, Request, Uri};
+use std::collections::HashMap;
+use std::io;
+use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "native-tls")]
+use hyper_tls::HttpsConnector;
+#[cfg(feature = "rustls")]
+use hyper_rustls::HttpsConnector;
+
+/// Resolver of host names to IP addresses.
+///
+/// The resolver is set with `Builder::dns_resolver`. Host names set with
+/// `Builder::resolve` are not passed to the resolver.
+///
+/// # Examples
+/// ```ignore
+/// struct StaticResolver(IpAddr);
+///
+/// impl Resolve for StaticResolver {
+///     fn resolve<'a>(&'a self, _host: &'a str) -> BoxFuture<'a, io::Result<Vec<IpAddr>>> {
+///         Box::pin(async move { Ok(vec![self.0]) })
+///     }
+/// }
+///
+/// let client = RestClient::builder()
+///     .dns_resolver(StaticResolver(IpAddr::V4(Ipv4Addr::LOCALHOST)))
+///     .build("http://httpbin.org")
+///     .unwrap();
+/// ```
+pub trait Resolve: Send + Sync {
+    /// Resolve the host name to IP addresses, in the order they should be
+    /// connected to
+    fn resolve<'a>(&'a self, host: &'a str) -> BoxFuture<'a, io::Result<Vec<IpAddr>>>;
+}
+
+/// Resolver that uses `getaddrinfo` of the system in a blocking thread. This
+/// is the default resolver.
+#[derive(Debug, Clone)]
+pub struct SystemResolver {
+    gai: GaiResolver,
+}
+
+impl Default for SystemResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemResolver {
+    /// Create system resolver
+    pub fn new() -> Self {
+        Self { gai: GaiResolver::new() }
+    }
+}
+
+impl Resolve for SystemResolver {
+    fn resolve<'a>(&'a self, host: &'a str) -> BoxFuture<'a, io::Result<Vec<IpAddr>>> {
+        let mut gai = self.gai.clone();
+        Box::pin(async move {
+            let name = Name::from_str(host).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+            Ok(gai.call(name).await?.map(|addr| addr.ip()).collect())
+        })
+    }
+}
+
+/// Resolver that keeps the addresses returned by another resolver for the
+/// given time.
+///
+/// Caching avoids resolving the host name for each new connection, which
+/// helps clients that open many connections to the same few hosts. Failed
+/// lookups are not cached.
+///
+/// # Examples
+/// ```ignore
+/// let client = RestClient::builder()
+///     .dns_resolver(CachingResolver::new(SystemResolver::new(), Duration::from_secs(60)))
+///     .build("http://httpbin.org")
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct CachingResolver<R> {
+    inner: R,
+    ttl: Duration,
+
+    /// Resolved addresses and the time of the lookup by lowercase host name
+    entries: Mutex<HashMap<String, (Instant, Vec<IpAddr>)>>,
+}
+
+impl<R: Resolve> CachingResolver<R> {
+    /// Create resolver that caches the addresses returned by `inner` for `ttl`
+    pub fn new(inner: R, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Remove all cached addresses
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+impl<R: Resolve> Resolve for CachingResolver<R> {
+    fn resolve<'a>(&'a self, host: &'a str) -> BoxFuture<'a, io::Result<Vec<IpAddr>>> {
+        Box::pin(async move {
+            let key = host.to_lowercase();
+            if let Some((resolved, addrs)) = self.entries.lock().unwrap().get(&key) {
+                if resolved.elapsed() < self.ttl {
+                    return Ok(addrs.clone());
+                }
+            }
+
+            let addrs = self.inner.resolve(host).await?;
+            let mut entries = self.entries.lock().unwrap();
+            entries.retain(|_, (resolved, _)| resolved.elapsed() < self.ttl);
+            entries.insert(key, (Instant::now(), addrs.clone()));
+            Ok(addrs)
+        })
+    }
+}
+
+/// Record type of IPv4 address
+const TYPE_A: u16 = 1;
+
+/// Record type of IPv6 address
+const TYPE_AAAA: u16 = 28;
+
+/// Resolver that sends the queries to a DNS-over-HTTPS server (RFC 8484).
+///
+/// IPv4 and IPv6 addresses are queried in parallel, and the IPv6 addresses
+/// are returned first. If the URL of the server has a host name instead of an
+/// IP address, it is resolved with the system resolver, unless the address
+/// of the server is given with `with_bootstrap`.
+///
+/// # Examples
+/// ```ignore
+/// let resolver = DohResolver::with_bootstrap("https://dns.internal/dns-query", "10.0.0.53".parse()?)?;
+/// let client = RestClient::builder()
+///     .dns_resolver(CachingResolver::new(resolver, Duration::from_secs(60)))
+///     .build("https://api.internal")?;
+/// ```
+#[derive(Clone)]
+pub struct DohResolver {
+    client: Client<HttpsConnector<HttpConnector<Resolver>>>,
+    url: Uri,
+}
+
+impl std::fmt::Debug for DohResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DohResolver").field("url", &self.url).finish_non_exhaustive()
+    }
+}
+
+impl DohResolver {
+    /// Create resolver that uses the server at the URL, e.g.
+    /// `https://1.1.1.1/dns-query`
+    pub fn new(url: &str) -> Result<Self, Error> {
+        Self::with_config(url.parse().map_err(|_| Error::UrlError)?, DnsConfig::default())
+    }
+
+    /// Create resolver that uses the server at the URL, connecting to the
+    /// address instead of resolving the host name of the URL
+    pub fn with_bootstrap(url: &str, addr: IpAddr) -> Result<Self, Error> {
+        let mut config = DnsConfig::default();
+        let url: Uri = url.parse().map_err(|_| Error::UrlError)?;
+        let host = url.host().ok_or(Error::UrlError)?;
+        config.overrides.insert(host.to_lowercase(), addr);
+        Self::with_config(url, config)
+    }
+
+    /// Create resolver that uses the public server of Cloudflare
+    pub fn cloudflare() -> Result<Self, Error> {
+        Self::with_bootstrap("https://cloudflare-dns.com/dns-query", IpAddr::from([1, 1, 1, 1]))
+    }
+
+    /// Create resolver that uses the public server of Google
+    pub fn google() -> Result<Self, Error> {
+        Self::with_bootstrap("https://dns.google/dns-query", IpAddr::from([8, 8, 8, 8]))
+    }
+
+    fn with_config(url: Uri, config: DnsConfig) -> Result<Self, Error> {
+        if url.host().is_none() {
+            return Err(Error::UrlError);
+        }
+
+        let mut connector = HttpConnector::new_with_resolver(Resolver::new(&config));
+        connector.enforce_http(false);
+        let https = tls::https_connector(connector, &TlsConfig::default(), false)?;
+        Ok(Self {
+            client: Client::builder().build(https),
+            url,
+        })
+    }
+
+    /// Addresses of the record type returned by the server for the host
+    async fn query(&self, host: &str, record_type: u16) -> io::Result<Vec<IpAddr>> {
+        let req = Request::post(self.url.clone())
+            .header(CONTENT_TYPE, "application/dns-message")
+            .header(ACCEPT, "application/dns-message")
+            .body(Body::from(dns_query(host, record_type)?))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+
+        let res = self.client.request(req).await.map_err(io::Error::other)?;
+        if !res.status().is_success() {
+            return Err(io::Error::other(format!("DNS server returned status {}", res.status())));
+        }
+        let body = hyper::body::to_bytes(res.into_body()).await.map_err(io::Error::other)?;
+        dns_answer(&body)
+    }
+}
+
+impl Resolve for DohResolver {
+    fn resolve<'a>(&'a self, host: &'a str) -> BoxFuture<'a, io::Result<Vec<IpAddr>>> {
+        Box::pin(async move {
+            let (v6, v4) = futures::future::join(self.query(host, TYPE_AAAA), self.query(host, TYPE_A)).await;
+            let addrs: Vec<_> = match (v6, v4) {
+                (Err(err), Err(_)) => return Err(err),
+                (v6, v4) => v6.unwrap_or_default().into_iter().chain(v4.unwrap_or_default()).collect(),
+            };
+            match addrs.is_empty() {
+                true => Err(io::Error::new(io::ErrorKind::NotFound, format!("no addresses for {}", host))),
+                false => Ok(addrs),
+            }
+        })
+    }
+}
+
+/// DNS query message for the record type of the host, with recursion desired
+fn dns_query(host: &str, record_type: u16) -> io::Result<Vec<u8>> {
+    // ID is zero as recommended for DNS-over-HTTPS
+    let mut msg = vec![0, 0, 0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0];
+    for label in host.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid host name"));
+        }
+        msg.push(label.len() as u8);
+        msg.extend_from_slice(label.as_bytes());
+    }
+    msg.push(0);
+    msg.extend_from_slice(&record_type.to_be_bytes());
+    msg.extend_from_slice(&1u16.to_be_bytes());
+    Ok(msg)
+}
+
+/// Addresses in the answer section of DNS response message. Other records,
+/// e.g. CNAME, are skipped. Non-existing name gives no addresses.
+fn dns_answer(msg: &[u8]) -> io::Result<Vec<IpAddr>> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid DNS response");
+    let u16_at = |pos: usize| Some(u16::from_be_bytes([*msg.get(pos)?, *msg.get(pos + 1)?]));
+
+    let flags = u16_at(2).ok_or_else(invalid)?;
+    match flags & 0x000f {
+        0 => (),
+        3 => return Ok(Vec::new()),
+        code => return Err(io::Error::other(format!("DNS server returned error code {}", code))),
+    }
+
+    let questions = u16_at(4).ok_or_else(invalid)?;
+    let answers = u16_at(6).ok_or_else(invalid)?;
+    let mut pos = 12;
+    for _ in 0..questions {
+        pos = skip_name(msg, pos).ok_or_else(invalid)? + 4;
+    }
+
+    let mut addrs = Vec::new();
+    for _ in 0..answers {
+        pos = skip_name(msg, pos).ok_or_else(invalid)?;
+        let record_type = u16_at(pos).ok_or_else(invalid)?;
+        let len = u16_at(pos + 8).ok_or_else(invalid)? as usize;
+        let data = msg.get(pos + 10..pos + 10 + len).ok_or_else(invalid)?;
+        match record_type {
+            TYPE_A => addrs.push(IpAddr::from(<[u8; 4]>::try_from(data).map_err(|_| invalid())?)),
+            TYPE_AAAA => addrs.push(IpAddr::from(<[u8; 16]>::try_from(data).map_err(|_| invalid())?)),
+            _ => (),
+        }
+        pos += 10 + len;
+    }
+    Ok(addrs)
+}
+
+/// Position after the name that starts at the position, which is either a
+/// sequence of labels or ends with a pointer to another name
+fn skip_name(msg: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let len = *msg.get(pos)? as usize;
+        match len {
+            0 => return Some(pos + 1),
+            len if len & 0xc0 == 0xc0 => return Some(pos + 2),
+            len => pos += 1 + len,
+        }
+    }
+}
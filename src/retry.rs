@@ -0,0 +1,162 @@
+//! Automatic retries of failed requests
+
+use crate::{Error, Response};
+use bytes::Bytes;
+use hyper::header::{HeaderMap, RETRY_AFTER};
+use hyper::{Method, Request, StatusCode};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Policy for retrying failed requests.
+///
+/// Idempotent requests (GET, HEAD, PUT, DELETE and OPTIONS) and requests with
+/// `Idempotency-Key` header are retried when the server returns 5xx status or
+/// the connection to the server fails. The
+/// delay between the attempts grows exponentially from the initial backoff
+/// until it reaches the maximum backoff.
+///
+/// When the server responds with 429 or 503 status and `Retry-After` header,
+/// the next attempt is made after the delay requested by the server instead.
+/// If the requested delay is longer than the maximum backoff, the request is
+/// not retried.
+///
+/// # Examples
+/// ```ignore
+/// let client = RestClient::builder()
+///     .retry(RetryPolicy::new(5).initial_backoff(Duration::from_millis(200)))
+///     .build("http://httpbin.org")
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+impl RetryPolicy {
+    /// Create policy that makes at most `max_attempts` attempts for a request
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+
+    /// Set delay before the first retry
+    ///
+    /// Default is 100 ms
+    pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Set upper limit for the delay between the attempts
+    ///
+    /// Default is 10 s
+    pub fn max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Maximum number of attempts for a request
+    pub(crate) fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Delay before the next attempt, or `None` if the result should be returned
+    pub(crate) fn delay(&self, attempt: u32, res: &Result<Response<Bytes>, Error>) -> Option<Duration> {
+        match *res {
+            Ok(ref res) => match retry_after(res.status(), res.headers()) {
+                Some(delay) if delay <= self.max_backoff => Some(delay),
+                Some(_) => None,
+                None if res.status().is_server_error() => Some(self.backoff(attempt)),
+                None => None,
+            },
+            Err(ref err) if is_retryable(err) => Some(self.backoff(attempt)),
+            Err(_) => None,
+        }
+    }
+
+    /// Delay after the given failed attempt (starting from 1)
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .checked_mul(factor)
+            .map_or(self.max_backoff, |backoff| backoff.min(self.max_backoff))
+    }
+}
+
+/// Attempts of a request that did not complete before the deadline
+#[derive(Debug)]
+pub struct DeadlineInfo {
+    /// Number of attempts started, including the one that was interrupted by
+    /// the deadline
+    pub attempts: u32,
+
+    /// Time from the start of the first attempt
+    pub elapsed: Duration,
+
+    /// Status of the last completed attempt, if the server returned error
+    /// status
+    pub last_status: Option<u16>,
+
+    /// Error of the last completed attempt, if it failed before the response
+    pub last_error: Option<Box<Error>>,
+}
+
+impl DeadlineInfo {
+    pub(crate) fn new(attempts: u32, start: Instant, last: Option<Result<Response<Bytes>, Error>>) -> Self {
+        let (last_status, last_error) = match last {
+            Some(Ok(res)) => (Some(res.status().as_u16()), None),
+            Some(Err(err)) => (None, Some(Box::new(err))),
+            None => (None, None),
+        };
+        Self {
+            attempts,
+            elapsed: start.elapsed(),
+            last_status,
+            last_error,
+        }
+    }
+}
+
+/// Delay requested by the server in `Retry-After` header of 429 or 503 response
+fn retry_after(status: StatusCode, headers: &HeaderMap) -> Option<Duration> {
+    if status != StatusCode::TOO_MANY_REQUESTS && status != StatusCode::SERVICE_UNAVAILABLE {
+        return None;
+    }
+
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    match value.parse::<u64>() {
+        Ok(secs) => Some(Duration::from_secs(secs)),
+        Err(_) => {
+            let date = httpdate::parse_http_date(value).ok()?;
+            Some(date.duration_since(SystemTime::now()).unwrap_or_default())
+        }
+    }
+}
+
+/// Header that lets the server detect repeated attempts of the same request
+pub(crate) const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
+/// Request can be sent again without side effects: the method is idempotent or
+/// the request has `Idempotency-Key` header
+pub(crate) fn is_idempotent<B>(req: &Request<B>) -> bool {
+    let method = matches!(
+        *req.method(),
+        Method::GET | Method::HEAD | Method::PUT | Method::DELETE | Method::OPTIONS
+    );
+    method || req.headers().contains_key(IDEMPOTENCY_KEY)
+}
+
+/// Request failed before it reached the server or the connection was lost
+fn is_retryable(err: &Error) -> bool {
+    matches!(err, Error::ConnectError(_) | Error::ConnectionResetError(_))
+}
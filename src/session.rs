@@ -0,0 +1,156 @@
+//! Session that logs in to the server and re-authenticates when needed
+
+use crate::middleware::{Middleware, MiddlewareFuture, Next};
+use crate::{clone_request, http_error, CrossOrigin, Error, RequestInfo};
+use hyper::header::*;
+use hyper::{Body, Method, Request, StatusCode};
+use tokio::sync::Mutex;
+
+/// Login request of a `Session`
+#[derive(Debug, Clone)]
+pub struct Login {
+    path: String,
+    body: String,
+    token: Option<String>,
+}
+
+impl Login {
+    /// POST the credentials as JSON to the path, relative to the base URL of
+    /// the client. Cookies set by the server are sent in the later requests.
+    pub fn new<T: serde::Serialize>(path: &str, credentials: &T) -> Result<Self, Error> {
+        let body = serde_json::to_string(credentials).map_err(Error::SerializeParseError)?;
+        Ok(Self {
+            path: path.to_owned(),
+            body,
+            token: None,
+        })
+    }
+
+    /// Use the token in the JSON response as Bearer token instead of the
+    /// cookies. The field is given as JSON pointer, e.g. `/access_token`.
+    pub fn token_field(mut self, pointer: &str) -> Self {
+        self.token = Some(pointer.to_owned());
+        self
+    }
+}
+
+/// Credential received in the login response
+type Credential = (HeaderName, HeaderValue);
+
+/// Middleware that logs in before the first request and sends the received
+/// cookies or token with the requests.
+///
+/// When the server responds with 401 status, the session logs in again and
+/// the request is sent again with the new credential. Requests with streamed
+/// body are not sent again. Requests redirected to another origin are sent
+/// without the credential.
+///
+/// # Examples
+/// ```ignore
+/// let login = Login::new("login", &Credentials { user: "user", password: "secret" })?.token_field("/token");
+/// let client = RestClient::builder().with_middleware(Session::new(login)).build("http://example.com")?;
+/// ```
+#[derive(Debug)]
+pub struct Session {
+    login: Login,
+    credential: Mutex<Option<Credential>>,
+}
+
+impl Session {
+    /// Create session that logs in with the login request
+    pub fn new(login: Login) -> Self {
+        Self {
+            login,
+            credential: Mutex::new(None),
+        }
+    }
+
+    /// Current credential, logging in if there is none or if the current one
+    /// is the rejected one
+    async fn credential(&self, next: Next<'_>, rejected: Option<&Credential>) -> Result<Credential, Error> {
+        let mut credential = self.credential.lock().await;
+        match *credential {
+            Some(ref current) if Some(current) != rejected => return Ok(current.clone()),
+            _ => (),
+        }
+
+        let fresh = self.log_in(next).await?;
+        *credential = Some(fresh.clone());
+        Ok(fresh)
+    }
+
+    async fn log_in(&self, next: Next<'_>) -> Result<Credential, Error> {
+        let url = next.client().join_url(&self.login.path)?;
+        let mut req = Request::new(Body::from(self.login.body.clone()));
+        *req.method_mut() = Method::POST;
+        *req.uri_mut() = url.as_str().parse().map_err(|_| Error::UrlError)?;
+        req.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        let request = RequestInfo::from(&req);
+
+        let res = next.run(req).await?;
+        let (parts, body) = res.into_parts();
+        let body = hyper::body::to_bytes(body).await?;
+        if !parts.status.is_success() {
+            return Err(http_error(request, parts.status, &parts.headers, &body));
+        }
+
+        match self.login.token {
+            Some(ref pointer) => {
+                let body = String::from_utf8_lossy(&body).to_string();
+                let value: serde_json::Value = match serde_json::from_str(&body) {
+                    Ok(value) => value,
+                    Err(err) => return Err(Error::DeserializeParseError(err, body)),
+                };
+                let token = match value.pointer(pointer).and_then(|token| token.as_str()) {
+                    Some(token) => token.to_owned(),
+                    None => {
+                        let err = format!("token field {} not found", pointer);
+                        let err = <serde_json::Error as serde::de::Error>::custom(err);
+                        return Err(Error::DeserializeParseError(err, body));
+                    }
+                };
+                let token = HeaderValue::from_str(&format!("Bearer {}", token)).map_err(|_| Error::InvalidValue)?;
+                Ok((AUTHORIZATION, token))
+            }
+            None => {
+                let cookies: Vec<_> = parts
+                    .headers
+                    .get_all(SET_COOKIE)
+                    .iter()
+                    .filter_map(|value| value.to_str().ok())
+                    .filter_map(|value| value.split(';').next())
+                    .map(str::trim)
+                    .collect();
+                let cookies = HeaderValue::from_str(&cookies.join("; ")).map_err(|_| Error::InvalidValue)?;
+                Ok((COOKIE, cookies))
+            }
+        }
+    }
+}
+
+fn with_credential(mut req: Request<Body>, credential: &Credential) -> Request<Body> {
+    req.headers_mut().insert(credential.0.clone(), credential.1.clone());
+    req
+}
+
+impl Middleware for Session {
+    fn handle<'a>(&'a self, req: Request<Body>, next: Next<'a>) -> MiddlewareFuture<'a> {
+        Box::pin(async move {
+            if req.extensions().get::<CrossOrigin>().is_some() {
+                return next.run(req).await;
+            }
+
+            let used = self.credential(next, None).await?;
+            let retry = clone_request(&req);
+
+            let res = next.run(with_credential(req, &used)).await?;
+            match retry {
+                Some(req) if res.status() == StatusCode::UNAUTHORIZED => {
+                    let fresh = self.credential(next, Some(&used)).await?;
+                    next.run(with_credential(req, &fresh)).await
+                }
+                _ => Ok(res),
+            }
+        })
+    }
+}
@@ -0,0 +1,254 @@
+//! Server-Sent Events
+
+use crate::{clone_request, http_error, Error, RequestInfo, RestClient};
+use futures::stream::{self, BoxStream, Stream};
+use hyper::body::HttpBody;
+use hyper::header::HeaderValue;
+use hyper::{Body, Request, StatusCode};
+use log::{debug, trace};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// Time to wait before reconnecting, unless the server sets another time
+const DEFAULT_RETRY: Duration = Duration::from_secs(3);
+
+/// Event received from the server
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event<T> {
+    /// Type of the event, `message` if the server did not set it
+    pub event: String,
+
+    /// ID of the event, or the last ID set by the server
+    pub id: Option<String>,
+
+    /// Data of the event
+    pub data: T,
+}
+
+/// Stream of the events sent by the server, returned by `RestClient::sse`.
+///
+/// When the server closes the connection, the stream reconnects after the
+/// time set by the server with `retry` field, or after three seconds, and
+/// sends the ID of the last event in `Last-Event-ID` header. The stream ends
+/// if the server responds with 204 status, and ends with an error if the
+/// request fails or the server returns error status.
+pub struct EventStream<T> {
+    inner: BoxStream<'static, Result<Event<T>, Error>>,
+}
+
+impl<T> std::fmt::Debug for EventStream<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventStream").finish_non_exhaustive()
+    }
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = Result<Event<T>, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.inner.as_mut().poll_next(cx)
+    }
+}
+
+impl<T: serde::de::DeserializeOwned + Send + 'static> EventStream<T> {
+    pub(crate) fn new(client: RestClient, req: Request<Body>) -> Self {
+        let state = State {
+            client,
+            req,
+            body: None,
+            connected: false,
+            done: false,
+            retry: DEFAULT_RETRY,
+            last_id: None,
+            parser: Parser::default(),
+        };
+        let inner = stream::unfold(state, |mut state| async move {
+            let item = state.next_event().await?;
+            Some((item, state))
+        });
+        Self { inner: Box::pin(inner) }
+    }
+}
+
+struct State {
+    client: RestClient,
+
+    /// Request that is sent again when reconnecting
+    req: Request<Body>,
+
+    /// Body of the response being read
+    body: Option<Body>,
+
+    /// Stream has connected at least once
+    connected: bool,
+
+    /// Stream has ended
+    done: bool,
+
+    /// Time to wait before reconnecting
+    retry: Duration,
+
+    /// ID of the last event, sent in `Last-Event-ID` when reconnecting
+    last_id: Option<String>,
+
+    parser: Parser,
+}
+
+impl State {
+    async fn next_event<T: serde::de::DeserializeOwned>(&mut self) -> Option<Result<Event<T>, Error>> {
+        while !self.done {
+            if let Some(frame) = self.parser.next_frame() {
+                match frame {
+                    Frame::Retry(retry) => self.retry = retry,
+                    Frame::Event { event, id, data } => {
+                        // Empty ID resets the last event ID
+                        if let Some(id) = id {
+                            self.last_id = Some(id).filter(|id| !id.is_empty());
+                        }
+                        if let Some(data) = data {
+                            let event = event.unwrap_or_else(|| String::from("message"));
+                            let id = self.last_id.clone();
+                            return Some(self.client.parse_text(data).map(|data| Event { event, id, data }));
+                        }
+                    }
+                }
+                continue;
+            }
+
+            let body = match self.body {
+                Some(ref mut body) => body,
+                None => match self.connect().await {
+                    Ok(true) => continue,
+                    Ok(false) => return None,
+                    Err(err) => {
+                        self.done = true;
+                        return Some(Err(err));
+                    }
+                },
+            };
+
+            match body.data().await {
+                Some(Ok(chunk)) => self.parser.push(&chunk),
+                Some(Err(err)) => {
+                    debug!("event stream failed: {}", err);
+                    self.body = None;
+                }
+                None => {
+                    debug!("event stream closed by the server");
+                    self.body = None;
+                }
+            }
+        }
+        None
+    }
+
+    /// Send the request, after waiting for the retry time if reconnecting.
+    /// Returns false if the server does not want the client to reconnect.
+    async fn connect(&mut self) -> Result<bool, Error> {
+        if self.connected {
+            tokio::time::sleep(self.retry).await;
+        }
+        self.connected = true;
+        self.parser = Parser::default();
+
+        let mut req = clone_request(&self.req).ok_or(Error::RequestError)?;
+        if let Some(ref id) = self.last_id {
+            let id = HeaderValue::from_str(id).map_err(|_| Error::InvalidValue)?;
+            req.headers_mut().insert("Last-Event-ID", id);
+        }
+        debug!("{} {} (event stream)", req.method(), req.uri());
+        let request = RequestInfo::from(&req);
+
+        self.client.acquire_rate().await;
+        let res = self.client.send(req).await?;
+        let status = res.status();
+        if status == StatusCode::NO_CONTENT {
+            debug!("event stream ended by the server");
+            self.done = true;
+            return Ok(false);
+        }
+        if !status.is_success() {
+            let headers = res.headers().clone();
+            let body = hyper::body::to_bytes(res.into_body()).await.map_err(Error::BodyReadError)?;
+            return Err(http_error(request, status, &headers, &body));
+        }
+
+        self.body = Some(res.into_body());
+        Ok(true)
+    }
+}
+
+/// Frame parsed from the event stream
+#[derive(Debug)]
+enum Frame {
+    /// Event, or only the ID of the last event if there was no data
+    Event {
+        event: Option<String>,
+        id: Option<String>,
+        data: Option<String>,
+    },
+
+    /// Reconnection time set by the server
+    Retry(Duration),
+}
+
+/// Parser of the `text/event-stream` format
+#[derive(Debug, Default)]
+struct Parser {
+    /// Received bytes that do not form a complete line yet
+    buf: Vec<u8>,
+
+    event: Option<String>,
+    id: Option<String>,
+    data: Option<String>,
+}
+
+impl Parser {
+    fn push(&mut self, chunk: &[u8]) {
+        self.buf.extend_from_slice(chunk);
+    }
+
+    /// Next frame from the complete lines received so far
+    fn next_frame(&mut self) -> Option<Frame> {
+        while let Some(end) = self.buf.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = self.buf.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches('\n').trim_end_matches('\r');
+            trace!("event stream line: {}", line);
+
+            // Empty line dispatches the event
+            if line.is_empty() {
+                let (event, id, data) = (self.event.take(), self.id.take(), self.data.take());
+                if data.is_some() || id.is_some() {
+                    return Some(Frame::Event { event, id, data });
+                }
+                continue;
+            }
+
+            let (field, value) = match line.split_once(':') {
+                Some(("", _)) => continue,
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, ""),
+            };
+            match field {
+                "event" => self.event = Some(value.to_owned()),
+                "data" => match self.data {
+                    Some(ref mut data) => {
+                        data.push('\n');
+                        data.push_str(value);
+                    }
+                    None => self.data = Some(value.to_owned()),
+                },
+                "id" if !value.contains('\0') => self.id = Some(value.to_owned()),
+                "retry" => {
+                    if let Ok(ms) = value.parse() {
+                        return Some(Frame::Retry(Duration::from_millis(ms)));
+                    }
+                }
+                _ => (),
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,377 @@
+//! URI templates of RFC 6570
+
+use crate::Error;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+
+/// URI template of [RFC 6570](https://www.rfc-editor.org/rfc/rfc6570), e.g.
+/// `repos/{owner}/{repo}/issues{?state,labels}`. All four levels of the RFC
+/// are supported: the operators `+`, `#`, `.`, `/`, `;`, `?` and `&`, and
+/// the prefix (`{name:3}`) and explode (`{labels*}`) modifiers.
+///
+/// The template can be expanded in `get_path`, as an alternative to
+/// formatting the path by hand. The values are percent-encoded as the
+/// operator requires.
+///
+/// # Examples
+/// ```
+/// use restson::{TemplateParams, UriTemplate};
+///
+/// let template = UriTemplate::new("repos/{owner}/{repo}/issues{?state,labels}").unwrap();
+/// let params = TemplateParams::new()
+///     .set("owner", "spietika")
+///     .set("repo", "restson-rust")
+///     .set("labels", vec!["bug", "help wanted"]);
+///
+/// assert_eq!(
+///     template.expand(&params),
+///     "repos/spietika/restson-rust/issues?labels=bug,help%20wanted"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UriTemplate {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Expression(Operator, Vec<VarSpec>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VarSpec {
+    name: String,
+    prefix: Option<usize>,
+    explode: bool,
+}
+
+/// Expression operator and how its expansion is built (RFC 6570 appendix A)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Operator {
+    first: &'static str,
+    sep: &'static str,
+    named: bool,
+    ifemp: &'static str,
+    reserved: bool,
+}
+
+impl Operator {
+    fn from_char(c: char) -> Option<Self> {
+        let (first, sep, named, ifemp, reserved) = match c {
+            '+' => ("", ",", false, "", true),
+            '#' => ("#", ",", false, "", true),
+            '.' => (".", ".", false, "", false),
+            '/' => ("/", "/", false, "", false),
+            ';' => (";", ";", true, "", false),
+            '?' => ("?", "&", true, "=", false),
+            '&' => ("&", "&", true, "=", false),
+            _ => return None,
+        };
+        Some(Operator { first, sep, named, ifemp, reserved })
+    }
+
+    /// Simple string expansion without operator
+    const SIMPLE: Operator = Operator { first: "", sep: ",", named: false, ifemp: "", reserved: false };
+}
+
+impl UriTemplate {
+    /// Parse the template. Returns `Error::InvalidValue` if the template has
+    /// unmatched braces or invalid expressions.
+    pub fn new(template: &str) -> Result<Self, Error> {
+        let mut parts = Vec::new();
+        let mut rest = template;
+
+        while let Some(start) = rest.find(['{', '}']) {
+            if rest.as_bytes()[start] == b'}' {
+                return Err(Error::InvalidValue);
+            }
+            let end = rest[start..].find('}').ok_or(Error::InvalidValue)? + start;
+            if start > 0 {
+                parts.push(Part::Literal(rest[..start].to_string()));
+            }
+            parts.push(parse_expression(&rest[start + 1..end])?);
+            rest = &rest[end + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(Part::Literal(rest.to_string()));
+        }
+
+        Ok(UriTemplate { parts })
+    }
+
+    /// Names of the variables in the template
+    pub fn variables(&self) -> impl Iterator<Item = &str> {
+        self.parts
+            .iter()
+            .flat_map(|part| match part {
+                Part::Expression(_, vars) => vars.as_slice(),
+                Part::Literal(_) => &[],
+            })
+            .map(|var| var.name.as_str())
+    }
+
+    /// Expand the template with the values of the variables. Variables
+    /// without value are left out, as specified in the RFC.
+    pub fn expand(&self, params: &TemplateParams) -> String {
+        let mut out = String::new();
+        for part in &self.parts {
+            match part {
+                Part::Literal(literal) => encode(&mut out, literal, true),
+                Part::Expression(op, vars) => expand_expression(&mut out, *op, vars, params),
+            }
+        }
+        out
+    }
+}
+
+impl FromStr for UriTemplate {
+    type Err = Error;
+
+    fn from_str(template: &str) -> Result<Self, Error> {
+        UriTemplate::new(template)
+    }
+}
+
+fn parse_expression(expression: &str) -> Result<Part, Error> {
+    let mut chars = expression.chars();
+    let (op, vars) = match chars.next().and_then(Operator::from_char) {
+        Some(op) => (op, chars.as_str()),
+        None => (Operator::SIMPLE, expression),
+    };
+
+    let vars = vars
+        .split(',')
+        .map(|var| {
+            let (name, prefix, explode) = if let Some(name) = var.strip_suffix('*') {
+                (name, None, true)
+            } else if let Some((name, len)) = var.split_once(':') {
+                let len = len.parse::<usize>().ok().filter(|len| (1..10000).contains(len));
+                (name, Some(len.ok_or(Error::InvalidValue)?), false)
+            } else {
+                (var, None, false)
+            };
+
+            let valid = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '%';
+            if name.is_empty() || !name.chars().all(valid) {
+                return Err(Error::InvalidValue);
+            }
+            Ok(VarSpec { name: name.to_string(), prefix, explode })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(Part::Expression(op, vars))
+}
+
+fn expand_expression(out: &mut String, op: Operator, vars: &[VarSpec], params: &TemplateParams) {
+    let mut first = true;
+    for var in vars {
+        let value = match params.get(&var.name) {
+            Some(value) if !value.is_undefined() => value,
+            _ => continue,
+        };
+        out.push_str(if first { op.first } else { op.sep });
+        first = false;
+
+        match value {
+            TemplateValue::String(value) => {
+                let value = match var.prefix {
+                    Some(len) => value.char_indices().nth(len).map_or(value.as_str(), |(i, _)| &value[..i]),
+                    None => value,
+                };
+                if op.named {
+                    named(out, op, &var.name, value);
+                } else {
+                    encode(out, value, op.reserved);
+                }
+            }
+            TemplateValue::List(items) if var.explode => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(op.sep);
+                    }
+                    if op.named {
+                        named(out, op, &var.name, item);
+                    } else {
+                        encode(out, item, op.reserved);
+                    }
+                }
+            }
+            TemplateValue::Map(pairs) if var.explode => {
+                for (i, (key, item)) in pairs.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(op.sep);
+                    }
+                    if op.named {
+                        named(out, op, key, item);
+                    } else {
+                        encode(out, key, op.reserved);
+                        out.push('=');
+                        encode(out, item, op.reserved);
+                    }
+                }
+            }
+            TemplateValue::List(items) => {
+                if op.named {
+                    out.push_str(&var.name);
+                    out.push('=');
+                }
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    encode(out, item, op.reserved);
+                }
+            }
+            TemplateValue::Map(pairs) => {
+                if op.named {
+                    out.push_str(&var.name);
+                    out.push('=');
+                }
+                for (i, (key, item)) in pairs.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    encode(out, key, op.reserved);
+                    out.push(',');
+                    encode(out, item, op.reserved);
+                }
+            }
+        }
+    }
+}
+
+/// Named value, e.g. `name=value`, or `name` + `ifemp` if the value is empty
+fn named(out: &mut String, op: Operator, name: &str, value: &str) {
+    encode(out, name, op.reserved);
+    if value.is_empty() {
+        out.push_str(op.ifemp);
+    } else {
+        out.push('=');
+        encode(out, value, op.reserved);
+    }
+}
+
+/// Percent-encode everything but unreserved characters, and also reserved
+/// characters and percent-encoded triplets if `reserved` is allowed
+fn encode(out: &mut String, value: &str, reserved: bool) {
+    let bytes = value.as_bytes();
+    for (i, &b) in bytes.iter().enumerate() {
+        let unreserved = b.is_ascii_alphanumeric() || b"-._~".contains(&b);
+        let triplet = b == b'%' && bytes.len() > i + 2 && bytes[i + 1].is_ascii_hexdigit() && bytes[i + 2].is_ascii_hexdigit();
+        if unreserved || (reserved && (b":/?#[]@!$&'()*+,;=".contains(&b) || triplet)) {
+            out.push(b as char);
+        } else {
+            let _ = write!(out, "%{:02X}", b);
+        }
+    }
+}
+
+/// Value of a template variable: a string, a list or an associative array
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateValue {
+    String(String),
+    List(Vec<String>),
+    Map(Vec<(String, String)>),
+}
+
+impl TemplateValue {
+    /// Empty lists and maps are undefined, like variables without value
+    fn is_undefined(&self) -> bool {
+        match self {
+            TemplateValue::String(_) => false,
+            TemplateValue::List(items) => items.is_empty(),
+            TemplateValue::Map(pairs) => pairs.is_empty(),
+        }
+    }
+}
+
+impl From<&str> for TemplateValue {
+    fn from(value: &str) -> Self {
+        TemplateValue::String(value.to_string())
+    }
+}
+
+impl From<String> for TemplateValue {
+    fn from(value: String) -> Self {
+        TemplateValue::String(value)
+    }
+}
+
+impl From<&String> for TemplateValue {
+    fn from(value: &String) -> Self {
+        TemplateValue::String(value.clone())
+    }
+}
+
+macro_rules! from_display {
+    ($($ty:ty),*) => {
+        $(impl From<$ty> for TemplateValue {
+            fn from(value: $ty) -> Self {
+                TemplateValue::String(value.to_string())
+            }
+        })*
+    };
+}
+
+from_display!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char);
+
+impl<T: Display> From<Vec<T>> for TemplateValue {
+    fn from(items: Vec<T>) -> Self {
+        TemplateValue::List(items.iter().map(ToString::to_string).collect())
+    }
+}
+
+impl<T: Display> From<&[T]> for TemplateValue {
+    fn from(items: &[T]) -> Self {
+        TemplateValue::List(items.iter().map(ToString::to_string).collect())
+    }
+}
+
+impl<K: Display, V: Display> From<BTreeMap<K, V>> for TemplateValue {
+    fn from(pairs: BTreeMap<K, V>) -> Self {
+        TemplateValue::Map(pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect())
+    }
+}
+
+impl<K: Display, V: Display> From<HashMap<K, V>> for TemplateValue {
+    fn from(pairs: HashMap<K, V>) -> Self {
+        TemplateValue::Map(pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect())
+    }
+}
+
+/// `None` is undefined, like a variable without value
+impl<T: Into<TemplateValue>> From<Option<T>> for TemplateValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(TemplateValue::List(Vec::new()), Into::into)
+    }
+}
+
+/// Values of the variables of `UriTemplate`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateParams {
+    values: Vec<(String, TemplateValue)>,
+}
+
+impl TemplateParams {
+    /// Create empty parameters
+    pub fn new() -> Self {
+        TemplateParams::default()
+    }
+
+    /// Set the value of the variable, replacing the earlier value if any
+    pub fn set<V: Into<TemplateValue>>(mut self, name: &str, value: V) -> Self {
+        let value = value.into();
+        match self.values.iter_mut().find(|(key, _)| key == name) {
+            Some((_, old)) => *old = value,
+            None => self.values.push((name.to_string(), value)),
+        }
+        self
+    }
+
+    /// Value of the variable
+    pub fn get(&self, name: &str) -> Option<&TemplateValue> {
+        self.values.iter().find(|(key, _)| key == name).map(|(_, value)| value)
+    }
+}
@@ -0,0 +1,144 @@
+//! Timing of the phases of a request
+
+use futures::future::BoxFuture;
+use hyper::service::Service;
+use hyper::{Body, Response, Uri};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// Durations of the phases of a request.
+///
+/// Connection phases are recorded only when a new connection was opened for
+/// the request and the client was not given with `Builder::with_client`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Timings {
+    /// Time to resolve the host name
+    pub dns: Option<Duration>,
+
+    /// Time to open the TCP connection, after the host name was resolved
+    pub connect: Option<Duration>,
+
+    /// Time of the TLS handshake
+    pub tls: Option<Duration>,
+
+    /// Time until the response headers were received (time to first byte)
+    pub ttfb: Duration,
+
+    /// Time until the whole response was received
+    pub total: Duration,
+}
+
+impl Timings {
+    /// Timings of a response whose headers were just received
+    pub(crate) fn new<B>(res: &hyper::Response<B>, start: Instant) -> Self {
+        let mut timings = res.extensions().get::<Timings>().copied().unwrap_or_default();
+        timings.ttfb = start.elapsed();
+        timings.total = timings.ttfb;
+        timings
+    }
+}
+
+/// Phase of connecting to the server, each including the previous ones
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Phase {
+    Dns,
+    Tcp,
+    Connection,
+}
+
+/// Elapsed time of the connection phases
+#[derive(Debug, Default)]
+struct Phases {
+    dns: Option<Duration>,
+    tcp: Option<Duration>,
+    connection: Option<Duration>,
+}
+
+impl Phases {
+    fn timings(&self, uri: &Uri) -> Timings {
+        let dns = self.dns.unwrap_or_default();
+        let tcp = self.tcp.map(|tcp| tcp.saturating_sub(dns));
+        let tls = match (self.tcp, self.connection) {
+            (Some(tcp), Some(connection)) if uri.scheme_str() == Some("https") => {
+                Some(connection.saturating_sub(tcp))
+            }
+            _ => None,
+        };
+
+        Timings {
+            dns: self.dns,
+            connect: tcp,
+            tls,
+            ..Default::default()
+        }
+    }
+}
+
+tokio::task_local! {
+    static PHASES: Arc<Mutex<Phases>>;
+}
+
+/// Run the request future, recording the connection phases in `Timings`
+/// extension of the response
+pub(crate) async fn record<F>(uri: &Uri, fut: F) -> Result<Response<Body>, hyper::Error>
+where
+    F: Future<Output = Result<Response<Body>, hyper::Error>>,
+{
+    let phases = Arc::new(Mutex::new(Phases::default()));
+    let mut res = PHASES.scope(phases.clone(), fut).await?;
+
+    let timings = phases.lock().unwrap().timings(uri);
+    res.extensions_mut().insert(timings);
+    Ok(res)
+}
+
+/// Service that records the elapsed time of the inner service to the
+/// request being made in the current task
+#[derive(Debug, Clone)]
+pub(crate) struct Timed<S> {
+    inner: S,
+    phase: Phase,
+}
+
+impl<S> Timed<S> {
+    pub(crate) fn new(inner: S, phase: Phase) -> Self {
+        Timed { inner, phase }
+    }
+}
+
+impl<S, T> Service<T> for Timed<S>
+where
+    S: Service<T>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<S::Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, target: T) -> Self::Future {
+        let phases = PHASES.try_with(Arc::clone).ok();
+        let phase = self.phase;
+        let start = Instant::now();
+        let fut = self.inner.call(target);
+
+        Box::pin(async move {
+            let res = fut.await;
+            if let Some(phases) = phases {
+                let mut phases = phases.lock().unwrap();
+                let elapsed = Some(start.elapsed());
+                match phase {
+                    Phase::Dns => phases.dns = elapsed,
+                    Phase::Tcp => phases.tcp = elapsed,
+                    Phase::Connection => phases.connection = elapsed,
+                }
+            }
+            res
+        })
+    }
+}
@@ -0,0 +1,228 @@
+//! TLS configuration of the HTTPS connections
+
+use crate::Error;
+
+#[cfg(feature = "native-tls")]
+use hyper_tls::{native_tls, HttpsConnector};
+#[cfg(feature = "rustls")]
+use hyper_rustls::{ConfigBuilderExt, HttpsConnector, HttpsConnectorBuilder};
+
+/// Client certificate and private key for mutual TLS authentication
+///
+/// # Examples
+/// ```ignore
+/// let cert = std::fs::read("client.pem")?;
+/// let key = std::fs::read("client.key")?;
+/// let client = RestClient::builder()
+///     .identity(Identity::from_pem(&cert, &key))
+///     .build("https://example.com")?;
+/// ```
+#[derive(Clone)]
+pub struct Identity {
+    inner: IdentityInner,
+}
+
+#[derive(Clone)]
+enum IdentityInner {
+    Pem { cert: Vec<u8>, key: Vec<u8> },
+    #[cfg(feature = "native-tls")]
+    Pkcs12 { der: Vec<u8>, password: String },
+}
+
+impl std::fmt::Debug for Identity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Identity").finish_non_exhaustive()
+    }
+}
+
+impl Identity {
+    /// Identity from PEM encoded certificate chain and private key. The
+    /// certificate of the client is the first one in the chain. The key is
+    /// in PKCS#8 format, or with rustls also in PKCS#1 (RSA) or SEC1 (EC)
+    /// format.
+    ///
+    /// The identity is parsed when the client is built.
+    pub fn from_pem(cert: &[u8], key: &[u8]) -> Self {
+        Self {
+            inner: IdentityInner::Pem {
+                cert: cert.to_vec(),
+                key: key.to_vec(),
+            },
+        }
+    }
+
+    /// Identity from DER encoded PKCS#12 archive, encrypted with the password
+    ///
+    /// The identity is parsed when the client is built.
+    #[cfg(feature = "native-tls")]
+    pub fn from_pkcs12(der: &[u8], password: &str) -> Self {
+        Self {
+            inner: IdentityInner::Pkcs12 {
+                der: der.to_vec(),
+                password: password.to_owned(),
+            },
+        }
+    }
+}
+
+/// Version of the TLS protocol
+///
+/// TLS 1.0 and 1.1 are not supported with rustls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TlsVersion {
+    Tls1_0,
+    Tls1_1,
+    Tls1_2,
+    Tls1_3,
+}
+
+/// TLS settings given with the builder
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TlsConfig {
+    /// Client identity for mutual TLS
+    pub(crate) identity: Option<Identity>,
+
+    /// Minimum allowed protocol version
+    pub(crate) min_version: Option<TlsVersion>,
+
+    /// Maximum allowed protocol version
+    pub(crate) max_version: Option<TlsVersion>,
+
+    /// Names of the allowed cipher suites
+    #[cfg(feature = "rustls")]
+    pub(crate) cipher_suites: Option<Vec<String>>,
+}
+
+impl TlsConfig {
+    /// Protocol versions between the minimum and maximum version, or error
+    /// if the range is empty
+    fn versions(&self) -> Result<Vec<TlsVersion>, Error> {
+        let min = self.min_version.unwrap_or(TlsVersion::Tls1_0);
+        let max = self.max_version.unwrap_or(TlsVersion::Tls1_3);
+        let versions: Vec<_> = [TlsVersion::Tls1_0, TlsVersion::Tls1_1, TlsVersion::Tls1_2, TlsVersion::Tls1_3]
+            .into_iter()
+            .filter(|version| (min..=max).contains(version))
+            .collect();
+        match versions.is_empty() {
+            true => Err(Error::HttpClientError),
+            false => Ok(versions),
+        }
+    }
+}
+
+#[cfg(feature = "native-tls")]
+fn protocol(version: TlsVersion) -> native_tls::Protocol {
+    match version {
+        TlsVersion::Tls1_0 => native_tls::Protocol::Tlsv10,
+        TlsVersion::Tls1_1 => native_tls::Protocol::Tlsv11,
+        TlsVersion::Tls1_2 => native_tls::Protocol::Tlsv12,
+        TlsVersion::Tls1_3 => native_tls::Protocol::Tlsv13,
+    }
+}
+
+/// HTTPS connector with the TLS settings, wrapping the connector of the TCP
+/// connections
+#[cfg(feature = "native-tls")]
+pub(crate) fn https_connector<C>(http: C, config: &TlsConfig, _http2_only: bool) -> Result<HttpsConnector<C>, Error> {
+    config.versions()?;
+    let mut tls = native_tls::TlsConnector::builder();
+    tls.min_protocol_version(config.min_version.map(protocol));
+    tls.max_protocol_version(config.max_version.map(protocol));
+    if let Some(ref identity) = config.identity {
+        let identity = match identity.inner {
+            IdentityInner::Pem { ref cert, ref key } => native_tls::Identity::from_pkcs8(cert, key),
+            IdentityInner::Pkcs12 { ref der, ref password } => native_tls::Identity::from_pkcs12(der, password),
+        };
+        tls.identity(identity.map_err(|_| Error::HttpClientError)?);
+    }
+
+    let tls = tls.build().map_err(|_| Error::HttpClientError)?;
+    Ok(HttpsConnector::from((http, tls.into())))
+}
+
+/// HTTPS connector with the TLS settings, wrapping the connector of the TCP
+/// connections. With `http2_only`, only HTTP/2 is offered in ALPN.
+#[cfg(feature = "rustls")]
+pub(crate) fn https_connector<C>(http: C, config: &TlsConfig, http2_only: bool) -> Result<HttpsConnector<C>, Error> {
+    let builder = HttpsConnectorBuilder::new().with_tls_config(client_config(config)?).https_or_http();
+    let builder = match http2_only {
+        true => builder.enable_http2(),
+        false => builder.enable_all_versions(),
+    };
+    Ok(builder.wrap_connector(http))
+}
+
+/// rustls configuration with the TLS settings, without ALPN protocols
+#[cfg(feature = "rustls")]
+pub(crate) fn client_config(config: &TlsConfig) -> Result<rustls::ClientConfig, Error> {
+    let versions: Vec<_> = config
+        .versions()?
+        .into_iter()
+        .filter_map(|version| match version {
+            TlsVersion::Tls1_2 => Some(&rustls::version::TLS12),
+            TlsVersion::Tls1_3 => Some(&rustls::version::TLS13),
+            _ => None,
+        })
+        .collect();
+    let suites = match config.cipher_suites {
+        Some(ref names) => cipher_suites(names)?,
+        None => rustls::DEFAULT_CIPHER_SUITES.to_vec(),
+    };
+
+    let builder = rustls::ClientConfig::builder()
+        .with_cipher_suites(&suites)
+        .with_safe_default_kx_groups()
+        .with_protocol_versions(&versions)
+        .map_err(|_| Error::HttpClientError)?
+        .with_native_roots();
+    match config.identity {
+        Some(Identity {
+            inner: IdentityInner::Pem { ref cert, ref key },
+        }) => {
+            let (certs, key) = pem_identity(cert, key).ok_or(Error::HttpClientError)?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(|_| Error::HttpClientError)
+        }
+        None => Ok(builder.with_no_client_auth()),
+    }
+}
+
+/// Cipher suites by their IANA names, e.g. `TLS13_AES_256_GCM_SHA384`
+#[cfg(feature = "rustls")]
+fn cipher_suites(names: &[String]) -> Result<Vec<rustls::SupportedCipherSuite>, Error> {
+    names
+        .iter()
+        .map(|name| {
+            rustls::ALL_CIPHER_SUITES
+                .iter()
+                .find(|suite| format!("{:?}", suite.suite()).eq_ignore_ascii_case(name))
+                .copied()
+                .ok_or(Error::HttpClientError)
+        })
+        .collect()
+}
+
+/// Certificate chain and the first private key in the PEM data
+#[cfg(feature = "rustls")]
+fn pem_identity(cert: &[u8], key: &[u8]) -> Option<(Vec<rustls::Certificate>, rustls::PrivateKey)> {
+    use rustls_pemfile::Item;
+
+    let certs: Vec<_> = rustls_pemfile::certs(&mut &*cert)
+        .ok()?
+        .into_iter()
+        .map(rustls::Certificate)
+        .collect();
+    let key = rustls_pemfile::read_all(&mut &*key)
+        .ok()?
+        .into_iter()
+        .find_map(|item| match item {
+            Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key) => Some(rustls::PrivateKey(key)),
+            _ => None,
+        })?;
+
+    match certs.is_empty() {
+        true => None,
+        false => Some((certs, key)),
+    }
+}
@@ -0,0 +1,34 @@
+//! Replaceable transport of the requests
+
+use crate::Error;
+use futures::future::BoxFuture;
+use hyper::{Body, Request, Response};
+
+/// Sender of the requests to the server.
+///
+/// By default the requests are sent with hyper. The transport set with
+/// `Builder::transport` replaces it, so that the requests made by the client
+/// can be answered without a server, e.g. with canned responses in unit
+/// tests. Middleware, retries and the other features of the client work as
+/// with the default transport, but the connection settings of the builder
+/// (proxy, TLS, DNS and the connection pool) are not used.
+///
+/// # Examples
+/// ```ignore
+/// struct Canned(&'static str);
+///
+/// impl Transport for Canned {
+///     fn send<'a>(&'a self, _req: Request<Body>) -> BoxFuture<'a, Result<Response<Body>, Error>> {
+///         Box::pin(async move { Ok(Response::new(Body::from(self.0))) })
+///     }
+/// }
+///
+/// let client = RestClient::builder()
+///     .transport(Canned(r#"{"id": 1}"#))
+///     .build("http://api.test")
+///     .unwrap();
+/// ```
+pub trait Transport: Send + Sync {
+    /// Send the request and return the response of the server
+    fn send<'a>(&'a self, req: Request<Body>) -> BoxFuture<'a, Result<Response<Body>, Error>>;
+}
@@ -0,0 +1,129 @@
+//! WebSocket connections upgraded from HTTP requests
+
+use crate::format::BodyFormat;
+use crate::{http_error, parse_body, parse_with_format, serialize_body, Error, RequestInfo, RestClient};
+use futures::{Sink, Stream};
+use hyper::header::{HeaderValue, CONNECTION, SEC_WEBSOCKET_ACCEPT, SEC_WEBSOCKET_KEY, SEC_WEBSOCKET_VERSION, UPGRADE};
+use hyper::upgrade::Upgraded;
+use hyper::{Body, Request, StatusCode};
+use log::debug;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio_tungstenite::tungstenite::error::ProtocolError;
+use tokio_tungstenite::tungstenite::handshake::client::generate_key;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+use tokio_tungstenite::tungstenite::{self, Message};
+use tokio_tungstenite::WebSocketStream;
+
+/// WebSocket connection returned by `RestClient::websocket`, which sends
+/// messages of type `T` and receives messages of type `K`.
+///
+/// Messages are serialized with the body format of the client, JSON by
+/// default. They are sent as text messages, or as binary messages if the
+/// format does not produce UTF-8. The stream of the received messages ends
+/// when the connection is closed. Ping messages are answered automatically
+/// while the stream is polled.
+pub struct WebSocket<T, K> {
+    stream: WebSocketStream<Upgraded>,
+    format: Option<Arc<dyn BodyFormat>>,
+    _types: PhantomData<fn(T) -> K>,
+}
+
+impl<T, K> std::fmt::Debug for WebSocket<T, K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebSocket").finish_non_exhaustive()
+    }
+}
+
+impl<T, K> WebSocket<T, K> {
+    /// Send the upgrade request and open the connection if the server
+    /// accepts it
+    pub(crate) async fn connect(client: &RestClient, mut req: Request<Body>) -> Result<Self, Error> {
+        let key = generate_key();
+        let headers = req.headers_mut();
+        headers.insert(CONNECTION, HeaderValue::from_static("Upgrade"));
+        headers.insert(UPGRADE, HeaderValue::from_static("websocket"));
+        headers.insert(SEC_WEBSOCKET_VERSION, HeaderValue::from_static("13"));
+        headers.insert(SEC_WEBSOCKET_KEY, HeaderValue::from_str(&key).map_err(|_| Error::RequestError)?);
+
+        debug!("{} {} (websocket)", req.method(), req.uri());
+        let request = RequestInfo::from(&req);
+        client.acquire_rate().await;
+        let res = client.run_with_timeout(client.send(req)).await?;
+
+        let status = res.status();
+        if status != StatusCode::SWITCHING_PROTOCOLS {
+            let headers = res.headers().clone();
+            let body = hyper::body::to_bytes(res.into_body()).await.map_err(Error::BodyReadError)?;
+            return Err(http_error(request, status, &headers, &body));
+        }
+        if res.headers().get(SEC_WEBSOCKET_ACCEPT).map(HeaderValue::as_bytes) != Some(derive_accept_key(key.as_bytes()).as_bytes()) {
+            return Err(websocket_error(tungstenite::Error::Protocol(ProtocolError::SecWebSocketAcceptKeyMismatch)));
+        }
+
+        let upgraded = hyper::upgrade::on(res).await.map_err(Error::HyperError)?;
+        Ok(Self {
+            stream: WebSocketStream::from_raw_socket(upgraded, Role::Client, None).await,
+            format: client.format.clone(),
+            _types: PhantomData,
+        })
+    }
+}
+
+impl<T, K: serde::de::DeserializeOwned> Stream for WebSocket<T, K> {
+    type Item = Result<K, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            let data = match Pin::new(&mut self.stream).poll_next(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Some(Ok(Message::Text(text)))) => text.into_bytes(),
+                Poll::Ready(Some(Ok(Message::Binary(data)))) => data,
+                Poll::Ready(Some(Ok(Message::Close(_)))) | Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Ready(Some(Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed))) => {
+                    return Poll::Ready(None)
+                }
+                Poll::Ready(Some(Ok(_))) => continue,
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(websocket_error(err)))),
+            };
+
+            let msg = match self.format {
+                Some(ref format) => parse_with_format(&**format, &data),
+                None => parse_body(String::from_utf8_lossy(&data).into_owned()),
+            };
+            return Poll::Ready(Some(msg));
+        }
+    }
+}
+
+impl<T: serde::Serialize, K> Sink<T> for WebSocket<T, K> {
+    type Error = Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Pin::new(&mut self.stream).poll_ready(cx).map_err(websocket_error)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: T) -> Result<(), Error> {
+        let (data, _) = serialize_body(self.format.as_deref(), &item)?;
+        let msg = match String::from_utf8(data) {
+            Ok(text) => Message::Text(text),
+            Err(err) => Message::Binary(err.into_bytes()),
+        };
+        Pin::new(&mut self.stream).start_send(msg).map_err(websocket_error)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Pin::new(&mut self.stream).poll_flush(cx).map_err(websocket_error)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Error>> {
+        Pin::new(&mut self.stream).poll_close(cx).map_err(websocket_error)
+    }
+}
+
+fn websocket_error(err: tungstenite::Error) -> Error {
+    Error::WebSocketError(Box::new(err))
+}
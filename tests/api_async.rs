@@ -0,0 +1,84 @@
+#![cfg(feature = "derive")]
+
+use restson::{Error, QueryPairs, RestClient};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+struct HttpBinAnything {
+    method: String,
+    url: String,
+    args: HashMap<String, String>,
+    json: Option<HttpBinData>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct HttpBinData {
+    data: String,
+}
+
+#[restson::api]
+trait HttpBin {
+    #[get("anything")]
+    async fn anything(&self) -> Result<HttpBinAnything, Error>;
+
+    #[get("anything/{id}/sensors/{name}")]
+    async fn sensor(&self, id: u32, name: &str, #[query] query: &QueryPairs) -> Result<HttpBinAnything, Error>;
+
+    #[post("anything/{id}")]
+    async fn post(&self, id: u32, #[body] data: &HttpBinData) -> Result<HttpBinAnything, Error>;
+
+    #[delete("anything/{id}")]
+    async fn delete(&self, id: u32) -> Result<(), Error>;
+
+    #[get("status/{code}")]
+    async fn status(&self, code: u16) -> Result<(), Error>;
+}
+
+#[tokio::test]
+async fn api_get() {
+    let api = HttpBinClient::new(RestClient::new("https://httpbin.org").unwrap());
+
+    let data = api.anything().await.unwrap();
+    assert_eq!(data.method, "GET");
+    assert_eq!(data.url, "https://httpbin.org/anything");
+}
+
+#[tokio::test]
+async fn api_path_and_query() {
+    let api = HttpBinClient::new(RestClient::new("https://httpbin.org").unwrap());
+
+    let query = QueryPairs::new().push("limit", 10);
+    let data = api.sensor(1234, "a b/c", &query).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything/1234/sensors/a%20b%2Fc?limit=10");
+    assert_eq!(data.args.get("limit").map(String::as_str), Some("10"));
+}
+
+#[tokio::test]
+async fn api_body() {
+    let api = HttpBinClient::new(RestClient::new("https://httpbin.org").unwrap());
+
+    let body = HttpBinData { data: "test data".to_string() };
+    let data = api.post(1, &body).await.unwrap();
+    assert_eq!(data.method, "POST");
+    assert_eq!(data.json, Some(body));
+
+    api.delete(1).await.unwrap();
+}
+
+#[tokio::test]
+async fn api_error_status() {
+    let api = HttpBinClient::new(RestClient::new("https://httpbin.org").unwrap());
+
+    match api.status(404).await {
+        Err(Error::HttpError(404, _, _)) => (),
+        _ => panic!("Expected 404 error status"),
+    };
+}
+
+#[tokio::test]
+async fn api_dot_segments() {
+    let api = HttpBinClient::new(RestClient::new("https://httpbin.org").unwrap());
+
+    assert!(matches!(api.sensor(1234, "..", &QueryPairs::new()).await, Err(Error::InvalidValue)));
+}
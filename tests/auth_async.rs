@@ -28,7 +28,50 @@ async fn basic_auth_fail() {
 
     client.set_auth("username", "wrong_passwd");
     match client.get::<_, HttpBinBasicAuth>(("username", "passwd")).await {
-        Err(Error::HttpError(s, _)) if s == 401 || s == 403 => (),
+        Err(Error::HttpError(s, ..)) if s == 401 || s == 403 => (),
         _ => panic!("Expected Unauthorized/Forbidden HTTP error"),
     };
 }
+
+#[derive(Deserialize)]
+struct HttpBinBearerAuth {
+    authenticated: bool,
+    token: String,
+}
+
+impl RestPath<()> for HttpBinBearerAuth {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("bearer"))
+    }
+}
+
+#[tokio::test]
+async fn bearer_auth() {
+    let mut client = RestClient::new("http://httpbin.org").unwrap();
+
+    client.set_auth_bearer("abcd1234");
+    let data = client.get::<_, HttpBinBearerAuth>(()).await.unwrap();
+    assert!(data.authenticated);
+    assert_eq!(data.token, "abcd1234");
+}
+
+#[tokio::test]
+async fn bearer_auth_builder() {
+    let client = RestClient::builder()
+        .auth_bearer("abcd1234")
+        .build("http://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinBearerAuth>(()).await.unwrap();
+    assert_eq!(data.token, "abcd1234");
+}
+
+#[tokio::test]
+async fn bearer_auth_fail() {
+    let client = RestClient::new("http://httpbin.org").unwrap();
+
+    match client.get::<_, HttpBinBearerAuth>(()).await {
+        Err(Error::HttpError(401, ..)) => (),
+        _ => panic!("Expected Unauthorized HTTP error"),
+    };
+}
@@ -0,0 +1,83 @@
+use hyper::Method;
+use restson::mock::MockClient;
+use restson::{BalanceStrategy, CircuitBreaker, Error, LoadBalancer, RestClient, RestPath};
+use serde_derive::Deserialize;
+use std::time::Duration;
+
+#[derive(Debug, Deserialize)]
+struct Pod {
+    name: String,
+}
+
+impl RestPath<()> for Pod {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("pod"))
+    }
+}
+
+#[tokio::test]
+async fn load_balancer_round_robin() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/a/pod").times(2).respond(200, r#"{"name": "a"}"#);
+    mock.when(Method::GET, "/b/pod").times(2).respond(200, r#"{"name": "b"}"#);
+    let client = RestClient::builder()
+        .transport(mock.clone())
+        .load_balancer(LoadBalancer::new(&["http://api.test/a/", "http://api.test/b/"]))
+        .build("http://api.test/a/")
+        .unwrap();
+
+    let mut names = Vec::new();
+    for _ in 0..4 {
+        names.push(client.get::<_, Pod>(()).await.unwrap().into_inner().name);
+    }
+    assert_eq!(names, ["a", "b", "a", "b"]);
+    mock.verify();
+}
+
+#[tokio::test]
+async fn load_balancer_unhealthy_host() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/a/pod").times(3).respond(200, r#"{"name": "a"}"#);
+    mock.when(Method::GET, "/b/pod").times(1).respond(503, "");
+    let client = RestClient::builder()
+        .transport(mock.clone())
+        .load_balancer(
+            LoadBalancer::new(&["http://api.test/a/", "http://api.test/b/"])
+                .strategy(BalanceStrategy::LeastInFlight)
+                .failure_threshold(1),
+        )
+        .build("http://api.test/a/")
+        .unwrap();
+
+    assert_eq!(client.get::<_, Pod>(()).await.unwrap().name, "a");
+    assert!(matches!(client.get::<_, Pod>(()).await, Err(Error::HttpError(503, _, _))));
+    assert_eq!(client.get::<_, Pod>(()).await.unwrap().name, "a");
+    assert_eq!(client.get::<_, Pod>(()).await.unwrap().name, "a");
+    mock.verify();
+}
+
+#[tokio::test]
+async fn load_balancer_invalid_url() {
+    let res = RestClient::builder().load_balancer(LoadBalancer::new(&["not a url"])).build("http://api.test/");
+    assert!(matches!(res, Err(Error::UrlError)));
+}
+
+#[tokio::test]
+async fn load_balancer_circuit_per_host() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/a/pod").times(2).respond(200, r#"{"name": "a"}"#);
+    mock.when(Method::GET, "/b/pod").times(1).respond(503, "");
+    let client = RestClient::builder()
+        .transport(mock.clone())
+        .load_balancer(LoadBalancer::new(&["http://a.test/a/", "http://b.test/b/"]))
+        .circuit_breaker(CircuitBreaker::new(1, Duration::from_secs(60)))
+        .build("http://a.test/a/")
+        .unwrap();
+
+    // Failure of host b opens only the circuit of b
+    assert_eq!(client.get::<_, Pod>(()).await.unwrap().name, "a");
+    assert!(matches!(client.get::<_, Pod>(()).await, Err(Error::HttpError(503, _, _))));
+    assert_eq!(client.get::<_, Pod>(()).await.unwrap().name, "a");
+    assert!(matches!(client.get::<_, Pod>(()).await, Err(Error::CircuitOpen)));
+    mock.verify();
+}
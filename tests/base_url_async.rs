@@ -0,0 +1,79 @@
+use hyper::Method;
+use restson::mock::MockClient;
+use restson::{Error, PathJoinMode, RestClient, RestPath};
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Device {
+    name: String,
+}
+
+impl RestPath<&str> for Device {
+    fn get_path(path: &str) -> Result<String, Error> {
+        Ok(String::from(path))
+    }
+}
+
+#[tokio::test]
+async fn path_join_mode_url() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/api/devices").respond(200, r#"{"name": "relative"}"#);
+    mock.when(Method::GET, "/devices").respond(200, r#"{"name": "absolute"}"#);
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test/api/v2").unwrap();
+
+    assert_eq!(client.get::<_, Device>("devices").await.unwrap().name, "relative");
+    assert_eq!(client.get::<_, Device>("/devices").await.unwrap().name, "absolute");
+    mock.verify();
+}
+
+#[tokio::test]
+async fn path_join_mode_append() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/api/v2/devices").times(3).respond(200, r#"{"name": "appended"}"#);
+    mock.when(Method::GET, "/other").respond(200, r#"{"name": "other"}"#);
+    let client = RestClient::builder()
+        .transport(mock.clone())
+        .path_join_mode(PathJoinMode::Append)
+        .build("http://api.test/api/v2")
+        .unwrap();
+
+    assert_eq!(client.get::<_, Device>("devices").await.unwrap().name, "appended");
+    assert_eq!(client.get::<_, Device>("/devices").await.unwrap().name, "appended");
+    assert_eq!(client.get::<_, Device>("devices?limit=1").await.unwrap().name, "appended");
+    assert_eq!(client.get::<_, Device>("http://api.test/other").await.unwrap().name, "other");
+    mock.verify();
+}
+
+#[tokio::test]
+async fn set_base_url() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/v1/devices").respond(200, r#"{"name": "v1"}"#);
+    mock.when(Method::GET, "/v2/devices").respond(200, r#"{"name": "v2"}"#);
+    let mut client = RestClient::builder().transport(mock.clone()).https_only(true).build("https://api.test/v1/").unwrap();
+    assert_eq!(client.base_url(), "https://api.test/v1/");
+    assert_eq!(client.get::<_, Device>("devices").await.unwrap().name, "v1");
+
+    client.set_base_url("https://api.test/v2/").unwrap();
+    assert_eq!(client.base_url(), "https://api.test/v2/");
+    assert_eq!(client.get::<_, Device>("devices").await.unwrap().name, "v2");
+    mock.verify();
+
+    assert!(matches!(client.set_base_url("not a url"), Err(Error::UrlError)));
+    assert!(matches!(client.set_base_url("http://api.test/v1/"), Err(Error::InsecureUrl)));
+    assert_eq!(client.base_url(), "https://api.test/v2/");
+}
+
+#[tokio::test]
+async fn absolute_url() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/v2/devices/1").header("Authorization", "Bearer token").respond(200, r#"{"name": "located"}"#);
+    mock.when(Method::PUT, "/v2/devices/1").body(r#"{"name":"renamed"}"#).respond(204, "");
+    mock.when(Method::DELETE, "/api/devices/1").respond(204, "");
+    let client = RestClient::builder().transport(mock.clone()).auth_bearer("token").build("http://api.test/api/").unwrap();
+
+    let data = client.get_url::<Device>("http://other.test/v2/devices/1").await.unwrap();
+    assert_eq!(data.name, "located");
+    client.put_url("http://other.test/v2/devices/1", &Device { name: "renamed".to_string() }).await.unwrap();
+    client.delete_url("devices/1").await.unwrap();
+    mock.verify();
+}
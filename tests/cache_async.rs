@@ -0,0 +1,103 @@
+#![cfg(feature = "cache")]
+
+use futures::future::BoxFuture;
+use hyper::{Method, StatusCode};
+use restson::{CacheStore, Error, MemoryStore, Metrics, RestClient, RestPath};
+use serde_derive::Deserialize;
+use std::io;
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+struct HttpBinCache {
+    url: String,
+}
+
+impl RestPath<()> for HttpBinCache {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("cache"))
+    }
+}
+
+impl RestPath<u32> for HttpBinCache {
+    fn get_path(max_age: u32) -> Result<String, Error> {
+        Ok(format!("cache/{}", max_age))
+    }
+}
+
+/// Store shared by several clients
+#[derive(Clone, Default)]
+struct SharedStore(Arc<MemoryStore>);
+
+impl CacheStore for SharedStore {
+    fn get<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<Option<Vec<u8>>>> {
+        self.0.get(key)
+    }
+
+    fn put<'a>(&'a self, key: &'a str, value: Vec<u8>) -> BoxFuture<'a, io::Result<()>> {
+        self.0.put(key, value)
+    }
+
+    fn remove<'a>(&'a self, key: &'a str) -> BoxFuture<'a, io::Result<()>> {
+        self.0.remove(key)
+    }
+}
+
+#[tokio::test]
+async fn fresh_response() {
+    let metrics = Metrics::new();
+    let client = RestClient::builder()
+        .cache(true)
+        .metrics(metrics.clone())
+        .build("http://httpbin.org")
+        .unwrap();
+
+    let first = client.get::<_, HttpBinCache>(60).await.unwrap();
+    let second = client.get::<_, HttpBinCache>(60).await.unwrap();
+    assert_eq!(first.url, second.url);
+
+    // Second response is served from the cache
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.requests[&(Method::GET, Some(200))], 1);
+}
+
+#[tokio::test]
+async fn revalidate_response() {
+    let metrics = Metrics::new();
+    let client = RestClient::builder()
+        .cache(true)
+        .metrics(metrics.clone())
+        .build("http://httpbin.org")
+        .unwrap();
+
+    let first = client.get::<_, HttpBinCache>(()).await.unwrap();
+    let second = client.get::<_, HttpBinCache>(()).await.unwrap();
+    assert_eq!(second.status(), StatusCode::OK);
+    assert_eq!(first.url, second.url);
+
+    // Response without max-age is revalidated with the ETag
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.requests[&(Method::GET, Some(200))], 1);
+    assert_eq!(snapshot.requests[&(Method::GET, Some(304))], 1);
+}
+
+#[tokio::test]
+async fn custom_store() {
+    let metrics = Metrics::new();
+    let store = SharedStore::default();
+    let build = || {
+        RestClient::builder()
+            .cache_store(store.clone())
+            .metrics(metrics.clone())
+            .build("http://httpbin.org")
+            .unwrap()
+    };
+
+    let first = build().get::<_, HttpBinCache>(60).await.unwrap();
+    let second = build().get::<_, HttpBinCache>(60).await.unwrap();
+    assert_eq!(first.url, second.url);
+    assert_eq!(first.headers(), second.headers());
+
+    // Response stored by the first client is used by the second one
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.requests[&(Method::GET, Some(200))], 1);
+}
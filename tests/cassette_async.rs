@@ -0,0 +1,103 @@
+#![cfg(feature = "vcr")]
+
+use restson::{Error, RestClient, RestPath};
+use serde_derive::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+mod common;
+
+#[derive(Serialize, Deserialize)]
+struct Counter {
+    count: u32,
+}
+
+impl RestPath<()> for Counter {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("counter"))
+    }
+}
+
+impl RestPath<u32> for Counter {
+    fn get_path(id: u32) -> Result<String, Error> {
+        Ok(format!("counter/{}", id))
+    }
+}
+
+/// Serve the number of the request
+fn serve() -> common::Server {
+    let mut count = 0;
+    common::serve(move |_| {
+        count += 1;
+        common::json(&format!(r#"{{"count": {}}}"#, count))
+    })
+}
+
+/// Path of the cassette file in the temporary directory, removed if it exists
+fn cassette(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(name);
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[tokio::test]
+async fn cassette_record_and_replay() {
+    let path = cassette("restson_cassette_replay_async.json");
+    let server = serve();
+
+    let client = RestClient::builder().cassette(&path).build(&server.url).unwrap();
+    assert_eq!(client.get::<_, Counter>(()).await.unwrap().count, 1);
+    assert_eq!(client.get::<_, Counter>(()).await.unwrap().count, 2);
+    assert!(path.exists());
+
+    // Responses are replayed in the recorded order and the last one is repeated
+    let client = RestClient::builder().cassette(&path).build(&server.url).unwrap();
+    assert_eq!(client.get::<_, Counter>(()).await.unwrap().count, 1);
+    assert_eq!(client.get::<_, Counter>(()).await.unwrap().count, 2);
+    assert_eq!(client.get::<_, Counter>(()).await.unwrap().count, 2);
+    assert_eq!(server.requests.try_iter().count(), 2);
+}
+
+#[tokio::test]
+async fn cassette_request_body() {
+    let path = cassette("restson_cassette_body_async.json");
+    let server = serve();
+
+    let client = RestClient::builder().cassette(&path).build(&server.url).unwrap();
+    let first = client.post_capture::<_, _, Counter>((), &Counter { count: 10 }).await.unwrap().into_inner();
+    let second = client.post_capture::<_, _, Counter>((), &Counter { count: 20 }).await.unwrap().into_inner();
+    assert_eq!((first.count, second.count), (1, 2));
+
+    let client = RestClient::builder().cassette(&path).build(&server.url).unwrap();
+    let second = client.post_capture::<_, _, Counter>((), &Counter { count: 20 }).await.unwrap().into_inner();
+    let first = client.post_capture::<_, _, Counter>((), &Counter { count: 10 }).await.unwrap().into_inner();
+    assert_eq!((first.count, second.count), (1, 2));
+    assert_eq!(server.requests.try_iter().count(), 2);
+}
+
+#[tokio::test]
+async fn cassette_not_recorded() {
+    let path = cassette("restson_cassette_missing_async.json");
+    let server = serve();
+
+    let client = RestClient::builder().cassette(&path).build(&server.url).unwrap();
+    client.get::<_, Counter>(()).await.unwrap();
+
+    let client = RestClient::builder().cassette(&path).build(&server.url).unwrap();
+    match client.get::<_, Counter>(1).await {
+        Err(Error::IoError(err)) => assert_eq!(err.kind(), std::io::ErrorKind::NotFound),
+        _ => panic!("Expected IO error"),
+    };
+}
+
+#[tokio::test]
+async fn cassette_invalid() {
+    let path = cassette("restson_cassette_invalid.json");
+    std::fs::write(&path, "not json").unwrap();
+
+    // File is read on the first request
+    let client = RestClient::builder().cassette(&path).build("http://127.0.0.1:1").unwrap();
+    match client.get::<_, Counter>(()).await {
+        Err(Error::IoError(err)) => assert_eq!(err.kind(), std::io::ErrorKind::InvalidData),
+        _ => panic!("Expected IO error"),
+    };
+}
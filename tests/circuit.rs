@@ -0,0 +1,105 @@
+use restson::{CircuitBreaker, Error, RestClient, RestPath};
+use serde_derive::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize)]
+struct HttpBinStatus {}
+
+impl RestPath<u16> for HttpBinStatus {
+    fn get_path(code: u16) -> Result<String, Error> {
+        Ok(format!("status/{}", code))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct HttpBinDelay {}
+
+impl RestPath<u16> for HttpBinDelay {
+    fn get_path(delay: u16) -> Result<String, Error> {
+        Ok(format!("delay/{}", delay))
+    }
+}
+
+#[test]
+fn circuit_open_server_error() {
+    let client = RestClient::builder()
+        .circuit_breaker(CircuitBreaker::new(2, Duration::from_secs(60)))
+        .blocking("http://httpbin.org")
+        .unwrap();
+
+    for _ in 0..2 {
+        match client.get::<_, HttpBinStatus>(500) {
+            Err(Error::HttpError(500, ..)) => (),
+            _ => panic!("Expected 500 error status"),
+        };
+    }
+
+    match client.get::<_, HttpBinStatus>(200) {
+        Err(Error::CircuitOpen) => (),
+        _ => panic!("Expected open circuit"),
+    };
+}
+
+#[test]
+fn circuit_success_resets() {
+    let client = RestClient::builder()
+        .circuit_breaker(CircuitBreaker::new(2, Duration::from_secs(60)))
+        .blocking("http://httpbin.org")
+        .unwrap();
+
+    for _ in 0..3 {
+        assert!(client.get::<_, HttpBinStatus>(500).is_err());
+        assert!(client.get::<_, HttpBinStatus>(404).is_err());
+    }
+
+    match client.get::<_, HttpBinStatus>(500) {
+        Err(Error::HttpError(500, ..)) => (),
+        _ => panic!("Expected 500 error status"),
+    };
+}
+
+#[test]
+fn circuit_half_open() {
+    let client = RestClient::builder()
+        .circuit_breaker(CircuitBreaker::new(1, Duration::from_millis(200)))
+        .blocking("http://127.0.0.1:1")
+        .unwrap();
+
+    match client.get::<_, HttpBinStatus>(200) {
+        Err(Error::ConnectError(_)) => (),
+        _ => panic!("Expected connect error"),
+    };
+    match client.get::<_, HttpBinStatus>(200) {
+        Err(Error::CircuitOpen) => (),
+        _ => panic!("Expected open circuit"),
+    };
+
+    // Probe request is let through after the open duration
+    std::thread::sleep(Duration::from_millis(300));
+    match client.get::<_, HttpBinStatus>(200) {
+        Err(Error::ConnectError(_)) => (),
+        _ => panic!("Expected connect error"),
+    };
+    match client.get::<_, HttpBinStatus>(200) {
+        Err(Error::CircuitOpen) => (),
+        _ => panic!("Expected open circuit"),
+    };
+}
+
+#[test]
+fn circuit_open_timeout() {
+    let client = RestClient::builder()
+        .circuit_breaker(CircuitBreaker::new(1, Duration::from_secs(60)))
+        .timeout(Duration::from_millis(100))
+        .blocking("http://httpbin.org")
+        .unwrap();
+
+    match client.get::<_, HttpBinDelay>(1) {
+        Err(Error::TimeoutError) => (),
+        _ => panic!("Expected timeout"),
+    };
+    match client.get::<_, HttpBinStatus>(200) {
+        Err(Error::CircuitOpen) => (),
+        _ => panic!("Expected open circuit"),
+    };
+}
@@ -0,0 +1,121 @@
+use restson::{CircuitBreaker, Error, RestClient, RestPath};
+use serde_derive::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize)]
+struct HttpBinStatus {}
+
+impl RestPath<u16> for HttpBinStatus {
+    fn get_path(code: u16) -> Result<String, Error> {
+        Ok(format!("status/{}", code))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct HttpBinDelay {}
+
+impl RestPath<u16> for HttpBinDelay {
+    fn get_path(delay: u16) -> Result<String, Error> {
+        Ok(format!("delay/{}", delay))
+    }
+}
+
+#[tokio::test]
+async fn circuit_open_server_error() {
+    let client = RestClient::builder()
+        .circuit_breaker(CircuitBreaker::new(2, Duration::from_secs(60)))
+        .build("http://httpbin.org")
+        .unwrap();
+
+    for _ in 0..2 {
+        match client.get::<_, HttpBinStatus>(500).await {
+            Err(Error::HttpError(500, ..)) => (),
+            _ => panic!("Expected 500 error status"),
+        };
+    }
+
+    match client.get::<_, HttpBinStatus>(200).await {
+        Err(Error::CircuitOpen) => (),
+        _ => panic!("Expected open circuit"),
+    };
+}
+
+#[tokio::test]
+async fn circuit_success_resets() {
+    let client = RestClient::builder()
+        .circuit_breaker(CircuitBreaker::new(2, Duration::from_secs(60)))
+        .build("http://httpbin.org")
+        .unwrap();
+
+    for _ in 0..3 {
+        assert!(client.get::<_, HttpBinStatus>(500).await.is_err());
+        assert!(client.get::<_, HttpBinStatus>(404).await.is_err());
+    }
+
+    match client.get::<_, HttpBinStatus>(500).await {
+        Err(Error::HttpError(500, ..)) => (),
+        _ => panic!("Expected 500 error status"),
+    };
+}
+
+#[tokio::test]
+async fn circuit_half_open() {
+    let client = RestClient::builder()
+        .circuit_breaker(CircuitBreaker::new(1, Duration::from_millis(200)))
+        .build("http://127.0.0.1:1")
+        .unwrap();
+
+    match client.get::<_, HttpBinStatus>(200).await {
+        Err(Error::ConnectError(_)) => (),
+        _ => panic!("Expected connect error"),
+    };
+    match client.get::<_, HttpBinStatus>(200).await {
+        Err(Error::CircuitOpen) => (),
+        _ => panic!("Expected open circuit"),
+    };
+
+    // Probe request is let through after the open duration
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    match client.get::<_, HttpBinStatus>(200).await {
+        Err(Error::ConnectError(_)) => (),
+        _ => panic!("Expected connect error"),
+    };
+    match client.get::<_, HttpBinStatus>(200).await {
+        Err(Error::CircuitOpen) => (),
+        _ => panic!("Expected open circuit"),
+    };
+}
+
+#[tokio::test]
+async fn circuit_open_timeout() {
+    let client = RestClient::builder()
+        .circuit_breaker(CircuitBreaker::new(1, Duration::from_secs(60)))
+        .timeout(Duration::from_millis(100))
+        .build("http://httpbin.org")
+        .unwrap();
+
+    match client.get::<_, HttpBinDelay>(1).await {
+        Err(Error::TimeoutError) => (),
+        _ => panic!("Expected timeout"),
+    };
+    match client.get::<_, HttpBinStatus>(200).await {
+        Err(Error::CircuitOpen) => (),
+        _ => panic!("Expected open circuit"),
+    };
+}
+
+#[tokio::test]
+async fn circuit_cancelled() {
+    let client = RestClient::builder()
+        .circuit_breaker(CircuitBreaker::new(1, Duration::from_secs(60)))
+        .build("http://httpbin.org")
+        .unwrap();
+
+    // Dropped request is not a failure
+    let cancelled = tokio::time::timeout(Duration::from_millis(100), client.get::<_, HttpBinDelay>(1));
+    assert!(cancelled.await.is_err());
+    match client.get::<_, HttpBinStatus>(500).await {
+        Err(Error::HttpError(500, ..)) => (),
+        _ => panic!("Expected 500 error status"),
+    };
+}
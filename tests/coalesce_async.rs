@@ -0,0 +1,55 @@
+use restson::{Error, RestClient, RestPath};
+use serde_derive::{Deserialize, Serialize};
+use std::thread;
+use std::time::Duration;
+
+mod common;
+
+#[derive(Serialize, Deserialize)]
+struct Counter {
+    count: u32,
+}
+
+impl RestPath<()> for Counter {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("counter"))
+    }
+}
+
+/// Serve one response per connection after a delay, counting up in the body
+fn serve() -> common::Server {
+    let mut count = 0;
+    common::serve(move |_| {
+        thread::sleep(Duration::from_millis(100));
+        count += 1;
+        let body = format!("{{\"count\":{}}}", count);
+        format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body)
+    })
+}
+
+#[tokio::test]
+async fn coalesce_requests() {
+    let server = serve();
+    let client = RestClient::builder().coalesce_requests(true).build(&server.url).unwrap();
+
+    let (a, b, c) = tokio::join!(
+        client.get::<_, Counter>(()),
+        client.get::<_, Counter>(()),
+        client.get::<_, Counter>(())
+    );
+    assert_eq!(a.unwrap().count, 1);
+    assert_eq!(b.unwrap().count, 1);
+    assert_eq!(c.unwrap().count, 1);
+
+    // Requests made after the response has been received are sent again
+    assert_eq!(client.get::<_, Counter>(()).await.unwrap().count, 2);
+}
+
+#[tokio::test]
+async fn coalesce_requests_disabled() {
+    let server = serve();
+    let client = RestClient::new(&server.url).unwrap();
+
+    let (a, b) = tokio::join!(client.get::<_, Counter>(()), client.get::<_, Counter>(()));
+    assert_ne!(a.unwrap().count, b.unwrap().count);
+}
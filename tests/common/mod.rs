@@ -0,0 +1,146 @@
+//! HTTP/1.1 server on localhost for the tests that need responses that
+//! httpbin does not provide
+
+#![allow(dead_code)]
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Request received by the server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: String,
+    pub target: String,
+    /// Header names are in lowercase
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Value of the header, the first one if there are several
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find(|(key, _)| key == name).map(|(_, value)| value.as_str())
+    }
+
+    /// Request line without the HTTP version, e.g. `GET /data`
+    pub fn line(&self) -> String {
+        format!("{} {}", self.method, self.target)
+    }
+}
+
+/// Server started with `serve`
+pub struct Server {
+    pub url: String,
+    pub port: u16,
+    /// Requests in the order they were received
+    pub requests: Receiver<Request>,
+    connections: Arc<AtomicUsize>,
+}
+
+impl Server {
+    /// Number of connections accepted so far
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+/// Start server that responds to each request with the raw HTTP response
+/// returned by the function. The connections are kept alive unless the
+/// response has `Connection: close`.
+pub fn serve<F, R>(respond: F) -> Server
+where
+    F: FnMut(&Request) -> R + Send + 'static,
+    R: Into<Vec<u8>>,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, rx) = mpsc::channel();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let counter = connections.clone();
+    let respond = Arc::new(Mutex::new(respond));
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            counter.fetch_add(1, Ordering::SeqCst);
+            let (tx, respond) = (tx.clone(), respond.clone());
+            thread::spawn(move || {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                while let Some(request) = read_request(&mut reader) {
+                    let response: Vec<u8> = (respond.lock().unwrap())(&request).into();
+                    let _ = tx.send(request);
+                    if stream.write_all(&response).is_err() || closes(&response) {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    Server { url: format!("http://{}", addr), port: addr.port(), requests: rx, connections }
+}
+
+/// Start server that sends the raw responses in turn, and 404 status after
+/// them
+pub fn serve_responses(responses: &[&'static str]) -> Server {
+    let mut responses: VecDeque<_> = responses.iter().copied().collect();
+    serve(move |_| responses.pop_front().unwrap_or(NOT_FOUND))
+}
+
+pub const NOT_FOUND: &str = "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+/// Response with 200 status and the JSON body
+pub fn json(body: &str) -> String {
+    format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}", body.len(), body)
+}
+
+/// Read request with its body from the stream. Returns `None` when the
+/// client closes the connection.
+pub fn read_request(reader: &mut impl BufRead) -> Option<Request> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).ok()? == 0 {
+        return None;
+    }
+    let mut parts = line.split(' ');
+    let method = parts.next()?.to_owned();
+    let target = parts.next()?.to_owned();
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).ok()? <= 2 {
+            break;
+        }
+        let (name, value) = line.split_once(':')?;
+        headers.push((name.to_lowercase(), value.trim().to_owned()));
+    }
+
+    let mut request = Request { method, target, headers, body: Vec::new() };
+    if request.header("transfer-encoding") == Some("chunked") {
+        loop {
+            line.clear();
+            reader.read_line(&mut line).ok()?;
+            let len = usize::from_str_radix(line.trim(), 16).ok()?;
+            let mut chunk = vec![0; len + 2];
+            reader.read_exact(&mut chunk).ok()?;
+            if len == 0 {
+                break;
+            }
+            request.body.extend_from_slice(&chunk[..len]);
+        }
+    } else if let Some(len) = request.header("content-length") {
+        request.body = vec![0; len.parse().ok()?];
+        reader.read_exact(&mut request.body).ok()?;
+    }
+    Some(request)
+}
+
+fn closes(response: &[u8]) -> bool {
+    let end = response.windows(4).position(|w| w == b"\r\n\r\n").unwrap_or(response.len());
+    String::from_utf8_lossy(&response[..end]).to_lowercase().contains("\r\nconnection: close")
+}
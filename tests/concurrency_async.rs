@@ -0,0 +1,93 @@
+use hyper::Method;
+use restson::mock::MockClient;
+use restson::{Error, RestClient, RestPath};
+use serde_derive::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+#[derive(Serialize, Deserialize)]
+struct HttpBinDelay {}
+
+impl RestPath<u16> for HttpBinDelay {
+    fn get_path(delay: u16) -> Result<String, Error> {
+        Ok(format!("delay/{}", delay))
+    }
+}
+
+#[tokio::test]
+async fn max_concurrent_requests() {
+    let client = RestClient::builder()
+        .max_concurrent_requests(1)
+        .timeout(Duration::from_secs(2))
+        .build("http://httpbin.org")
+        .unwrap();
+
+    // Requests are sent one after another and waiting does not count towards the timeout
+    let start = Instant::now();
+    let (a, b) = tokio::join!(
+        client.get::<_, HttpBinDelay>(1),
+        client.get::<_, HttpBinDelay>(1)
+    );
+    a.unwrap();
+    b.unwrap();
+    assert!(start.elapsed() >= Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn max_concurrent_requests_zero() {
+    // No request could ever be sent
+    let res = RestClient::builder().max_concurrent_requests(0).build("http://httpbin.org");
+    assert!(matches!(res, Err(Error::InvalidValue)));
+}
+
+#[tokio::test]
+async fn max_concurrent_requests_parallel() {
+    let client = RestClient::builder()
+        .max_concurrent_requests(2)
+        .build("http://httpbin.org")
+        .unwrap();
+
+    let start = Instant::now();
+    let (a, b) = tokio::join!(
+        client.get::<_, HttpBinDelay>(1),
+        client.get::<_, HttpBinDelay>(1)
+    );
+    a.unwrap();
+    b.unwrap();
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn get_many_concurrency() {
+    let client = RestClient::new("http://httpbin.org").unwrap();
+
+    let start = Instant::now();
+    let results = client.get_many::<_, HttpBinDelay, _>([1, 1, 1], 3).await;
+    assert_eq!(results.len(), 3);
+    assert!(results.iter().all(Result::is_ok));
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
+
+#[derive(Deserialize)]
+struct Item {
+    id: u32,
+}
+
+impl RestPath<u32> for Item {
+    fn get_path(id: u32) -> Result<String, Error> {
+        Ok(format!("items/{}", id))
+    }
+}
+
+#[tokio::test]
+async fn get_many_order() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/items/1").respond(200, r#"{"id": 1}"#);
+    mock.when(Method::GET, "/items/2").respond(404, "");
+    mock.when(Method::GET, "/items/3").respond(200, r#"{"id": 3}"#);
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test").unwrap();
+
+    let results = client.get_many::<_, Item, _>(1..=3, 2).await;
+    assert_eq!(results[0].as_ref().unwrap().id, 1);
+    assert!(matches!(results[1], Err(Error::HttpError(404, _, _))));
+    assert_eq!(results[2].as_ref().unwrap().id, 3);
+}
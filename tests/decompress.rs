@@ -0,0 +1,120 @@
+#![cfg(any(feature = "gzip", feature = "deflate"))]
+
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+
+mod common;
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    name: String,
+    tags: Vec<String>,
+}
+
+impl RestPath<()> for Project {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("project"))
+    }
+}
+
+struct Export;
+
+impl RestPath<()> for Export {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("export"))
+    }
+}
+
+/// `{"name": "restson", "tags": ["rest", "json"]}` compressed with gzip
+#[cfg(feature = "gzip")]
+const GZIP: &[u8] = &[
+    0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xab, 0x56, 0xca, 0x4b, 0xcc, 0x4d, 0x55, 0xb2, 0x52,
+    0x50, 0x2a, 0x4a, 0x2d, 0x2e, 0x29, 0xce, 0xcf, 0x53, 0xd2, 0x51, 0x50, 0x2a, 0x49, 0x4c, 0x2f, 0x06, 0x0a, 0x45,
+    0x83, 0xc5, 0x40, 0x02, 0x59, 0x20, 0x89, 0xd8, 0x5a, 0x00, 0x54, 0x0b, 0x93, 0xc6, 0x2d, 0x00, 0x00, 0x00,
+];
+
+/// The same body compressed with deflate (zlib)
+#[cfg(feature = "deflate")]
+const DEFLATE: &[u8] = &[
+    0x78, 0x9c, 0xab, 0x56, 0xca, 0x4b, 0xcc, 0x4d, 0x55, 0xb2, 0x52, 0x50, 0x2a, 0x4a, 0x2d, 0x2e, 0x29, 0xce, 0xcf,
+    0x53, 0xd2, 0x51, 0x50, 0x2a, 0x49, 0x4c, 0x2f, 0x06, 0x0a, 0x45, 0x83, 0xc5, 0x40, 0x02, 0x59, 0x20, 0x89, 0xd8,
+    0x5a, 0x00, 0x46, 0x5e, 0x0e, 0x27,
+];
+
+/// Serve the body with the content coding
+fn serve(encoding: &'static str, body: &'static [u8]) -> common::Server {
+    common::serve(move |_| {
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Encoding: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            encoding,
+            body.len()
+        );
+        [head.as_bytes(), body].concat()
+    })
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn decompress_gzip() {
+    let server = serve("gzip", GZIP);
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    let res = client.get::<_, Project>(()).unwrap();
+    assert!(server.requests.recv().unwrap().header("accept-encoding").unwrap().contains("gzip"));
+    assert!(res.headers().get("content-encoding").is_none());
+    let project = res.into_inner();
+    assert_eq!(project.name, "restson");
+    assert_eq!(project.tags, ["rest", "json"]);
+}
+
+#[cfg(feature = "deflate")]
+#[test]
+fn decompress_deflate() {
+    let server = serve("deflate", DEFLATE);
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    let project = client.get::<_, Project>(()).unwrap().into_inner();
+    assert!(server.requests.recv().unwrap().header("accept-encoding").unwrap().contains("deflate"));
+    assert_eq!(project.name, "restson");
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn decompress_invalid() {
+    let server = serve("gzip", b"{\"name\": \"restson\"}");
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    assert!(matches!(client.get::<_, Project>(()), Err(Error::BodyReadError(_))));
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn decompress_raw() {
+    let server = serve("gzip", GZIP);
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    // Raw body is returned as received
+    let res = client.get_raw::<_, Project>(()).unwrap();
+    assert_eq!(server.requests.recv().unwrap().header("accept-encoding"), Some("identity"));
+    assert_eq!(res.headers().get("content-encoding").unwrap(), "gzip");
+    assert_eq!(&res.into_inner()[..], GZIP);
+}
+
+#[test]
+fn decompress_download_range() {
+    let server = common::serve(|_| {
+        "HTTP/1.1 206 Partial Content\r\nContent-Range: bytes 3-5/6\r\nContent-Length: 3\r\n\r\ndef".to_string()
+    });
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    let path = std::env::temp_dir().join("restson_decompress_download_range");
+    std::fs::write(&path, b"abc").unwrap();
+
+    // Range counts the bytes in the file, so the body must not be encoded
+    let len = client.download_resumable::<_, Export, _>((), &path).unwrap();
+    let req = server.requests.recv().unwrap();
+    assert_eq!(req.header("range"), Some("bytes=3-"));
+    assert_eq!(req.header("accept-encoding"), Some("identity"));
+    assert_eq!(*len, 6);
+    assert_eq!(std::fs::read(&path).unwrap(), b"abcdef");
+}
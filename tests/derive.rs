@@ -0,0 +1,41 @@
+#![cfg(feature = "derive")]
+
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+
+#[derive(Deserialize, RestPath)]
+#[rest_path("anything")]
+#[rest_path("anything/{id: u32}")]
+#[rest_path("anything/{id: u32}/sensors/{name: &str}")]
+struct HttpBinAnything {
+    url: String,
+}
+
+#[test]
+fn derive_rest_path() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+
+    let data = client.get::<_, HttpBinAnything>(1234).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything/1234");
+
+    let data = client.get::<_, HttpBinAnything>((1234, "temp")).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything/1234/sensors/temp");
+}
+
+#[test]
+fn derive_rest_path_encoding() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    let data = client.get::<_, HttpBinAnything>((1, "a b/c?d")).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything/1/sensors/a%20b%2Fc%3Fd");
+}
+
+#[test]
+fn derive_rest_path_dot_segments() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    assert!(matches!(client.get::<_, HttpBinAnything>((1, "..")), Err(Error::InvalidValue)));
+}
@@ -0,0 +1,202 @@
+use futures::future::BoxFuture;
+use restson::resolvers::{CachingResolver, DohResolver, Resolve};
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+mod common;
+
+#[derive(Deserialize)]
+struct HttpBinAnything {
+    url: String,
+}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+/// Serve JSON responses, one per connection
+fn serve() -> common::Server {
+    common::serve(|_| {
+        let body = r#"{"url": "http://restson.test/anything"}"#;
+        format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body)
+    })
+}
+
+/// Serve DNS-over-HTTPS queries on plain HTTP, resolving `restson.test` to
+/// localhost, and return the URL of the server
+fn serve_doh() -> String {
+    let server = common::serve(|req| {
+        let body = dns_response(&req.body);
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/dns-message\r\nContent-Length: {}\r\n\r\n",
+            body.len()
+        );
+        [head.into_bytes(), body].concat()
+    });
+    format!("{}/dns-query", server.url)
+}
+
+/// DNS response to the query, with 127.0.0.1 as the A record of `restson.test`
+/// and non-existing domain for the other names
+fn dns_response(query: &[u8]) -> Vec<u8> {
+    let record_type = u16::from_be_bytes([query[query.len() - 4], query[query.len() - 3]]);
+    let known = query[12..].starts_with(b"\x07restson\x04test\x00");
+
+    let mut msg = query[..12].to_vec();
+    msg[2] = 0x81;
+    msg[3] = if known { 0x80 } else { 0x83 };
+    msg[7] = (known && record_type == 1) as u8;
+    msg.extend_from_slice(&query[12..]);
+    if msg[7] == 1 {
+        msg.extend_from_slice(&[0xc0, 12, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 127, 0, 0, 1]);
+    }
+    msg
+}
+
+/// Resolver that resolves all names to localhost and counts the lookups
+struct CountingResolver(Arc<AtomicUsize>);
+
+impl Resolve for CountingResolver {
+    fn resolve<'a>(&'a self, _host: &'a str) -> BoxFuture<'a, io::Result<Vec<IpAddr>>> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Box::pin(async { Ok(vec![IpAddr::V4(Ipv4Addr::LOCALHOST)]) })
+    }
+}
+
+/// Resolver that fails with an IO error of the kind
+struct FailingResolver(io::ErrorKind);
+
+impl Resolve for FailingResolver {
+    fn resolve<'a>(&'a self, _host: &'a str) -> BoxFuture<'a, io::Result<Vec<IpAddr>>> {
+        Box::pin(async move { Err(io::Error::from(self.0)) })
+    }
+}
+
+/// Resolver that resolves all names to the addresses
+struct StaticResolver(Vec<IpAddr>);
+
+impl Resolve for StaticResolver {
+    fn resolve<'a>(&'a self, _host: &'a str) -> BoxFuture<'a, io::Result<Vec<IpAddr>>> {
+        Box::pin(async move { Ok(self.0.clone()) })
+    }
+}
+
+#[test]
+fn resolve_host() {
+    let server = serve();
+    let client = RestClient::builder()
+        .resolve("restson.test", IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .blocking(&format!("http://restson.test:{}", server.port))
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.url, "http://restson.test/anything");
+    assert_eq!(server.requests.recv().unwrap().header("host").unwrap(), format!("restson.test:{}", server.port));
+}
+
+#[test]
+fn resolve_other_host() {
+    let client = RestClient::builder()
+        .resolve("restson.test", IpAddr::V4(Ipv4Addr::LOCALHOST))
+        .blocking("http://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.url, "http://httpbin.org/anything");
+}
+
+#[test]
+fn custom_resolver() {
+    let server = serve();
+    let lookups = Arc::new(AtomicUsize::new(0));
+    let client = RestClient::builder()
+        .dns_resolver(CountingResolver(lookups.clone()))
+        .blocking(&format!("http://restson.test:{}", server.port))
+        .unwrap();
+
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(lookups.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn caching_resolver() {
+    let server = serve();
+    let lookups = Arc::new(AtomicUsize::new(0));
+    let resolver = CachingResolver::new(CountingResolver(lookups.clone()), Duration::from_millis(500));
+    let client = RestClient::builder()
+        .dns_resolver(resolver)
+        .blocking(&format!("http://restson.test:{}", server.port))
+        .unwrap();
+
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(lookups.load(Ordering::SeqCst), 1);
+
+    thread::sleep(Duration::from_millis(600));
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(lookups.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn doh_resolver() {
+    let server = serve();
+    let client = RestClient::builder()
+        .dns_resolver(DohResolver::new(&serve_doh()).unwrap())
+        .blocking(&format!("http://restson.test:{}", server.port))
+        .unwrap();
+
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(server.requests.recv().unwrap().header("host").unwrap(), format!("restson.test:{}", server.port));
+}
+
+#[test]
+fn doh_resolver_unknown_host() {
+    let client = RestClient::builder()
+        .dns_resolver(DohResolver::new(&serve_doh()).unwrap())
+        .blocking("http://unknown.test")
+        .unwrap();
+
+    match client.get::<_, HttpBinAnything>(()) {
+        Err(Error::DnsError(_)) => (),
+        _ => panic!("Expected DNS error"),
+    };
+}
+
+#[test]
+fn resolver_error() {
+    // Resolver that can not reach its server is not a connect error
+    let client = RestClient::builder()
+        .dns_resolver(FailingResolver(io::ErrorKind::ConnectionRefused))
+        .blocking("http://restson.test")
+        .unwrap();
+
+    match client.get::<_, HttpBinAnything>(()) {
+        Err(Error::DnsError(_)) => (),
+        _ => panic!("Expected DNS error"),
+    };
+}
+
+#[test]
+fn happy_eyeballs() {
+    let server = serve();
+    // IPv6 address from the discard prefix where the connection does not complete
+    let discard = IpAddr::V6(Ipv6Addr::new(0x100, 0, 0, 0, 0, 0, 0, 1));
+    let client = RestClient::builder()
+        .dns_resolver(StaticResolver(vec![discard, IpAddr::V4(Ipv4Addr::LOCALHOST)]))
+        .happy_eyeballs_timeout(Duration::from_millis(100))
+        .blocking(&format!("http://restson.test:{}", server.port))
+        .unwrap();
+
+    let start = Instant::now();
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
@@ -1,7 +1,9 @@
-use restson::{Error, RestClient, RestPath};
+use restson::{Error, RestClient, RestPath, TypedError};
 use serde_derive::{Deserialize, Serialize};
 use std::time::{Duration, Instant};
 
+mod common;
+
 #[derive(Serialize, Deserialize)]
 struct InvalidResource {}
 
@@ -29,6 +31,9 @@ impl RestPath<u16> for HttpBinStatus {
     }
 }
 
+#[derive(Deserialize)]
+struct ApiError {}
+
 #[derive(Serialize, Deserialize)]
 struct HttpBinDelay {}
 
@@ -41,6 +46,25 @@ impl RestPath<u16> for HttpBinDelay {
 #[derive(Serialize, Deserialize)]
 struct HttpBinBase64 {}
 
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct HttpBinAnything {
+    headers: HttpBinHeaders,
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct HttpBinHeaders {
+    #[serde(rename = "Host")]
+    host: u32,
+}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
 impl RestPath<String> for HttpBinBase64 {
     fn get_path(data: String) -> Result<String, Error> {
         Ok(format!("base64/{}", data))
@@ -90,7 +114,7 @@ fn http_error() {
     let client = RestClient::new_blocking("http://httpbin.org").unwrap();
 
     match client.get::<_, HttpBinStatus>(418) {
-        Err(Error::HttpError(s, body)) => {
+        Err(Error::HttpError(s, body, _)) => {
             assert_eq!(s, 418);
             assert!(!body.is_empty());
         }
@@ -140,4 +164,136 @@ fn deserialize_error() {
     } else {
         panic!("expected serialized error");
     }
-}
\ No newline at end of file
+}
+
+#[test]
+#[cfg(feature = "path-to-error")]
+fn deserialize_error_path() {
+    let client = RestClient::new_blocking("http://httpbin.org").unwrap();
+
+    match client.get::<_, HttpBinAnything>(()) {
+        Err(err @ Error::DeserializeParseError(..)) => {
+            assert!(err.to_string().contains("headers.Host: invalid type"));
+        }
+        _ => panic!("expected deserialize error"),
+    };
+}
+
+#[test]
+fn typed_error_fallback() {
+    let client = RestClient::new_blocking("http://httpbin.org").unwrap();
+
+    // Body of the error is not JSON so the original error is returned
+    match client.get_typed_err::<_, HttpBinStatus, ApiError>(418) {
+        Err(TypedError::Client(Error::HttpError(s, body, _))) => {
+            assert_eq!(s, 418);
+            assert!(!body.is_empty());
+        }
+        _ => panic!("Expected 418 error status with response body"),
+    };
+}
+
+#[test]
+fn get_opt_not_found() {
+    let client = RestClient::new_blocking("http://httpbin.org").unwrap();
+
+    assert!(client.get_opt::<_, HttpBinStatus>(404).unwrap().is_none());
+
+    match client.get_opt::<_, HttpBinStatus>(500) {
+        Err(Error::HttpError(500, ..)) => (),
+        _ => panic!("Expected 500 error status"),
+    };
+}
+
+#[test]
+fn http_error_request() {
+    let client = RestClient::new_blocking("http://httpbin.org").unwrap();
+
+    match client.get::<_, HttpBinStatus>(500) {
+        Err(err @ Error::HttpError(500, _, _)) => {
+            assert!(err.to_string().contains("GET http://httpbin.org/status/500"));
+            if let Error::HttpError(_, _, req) = err {
+                assert_eq!(req.method, "GET");
+                assert_eq!(req.url, "http://httpbin.org/status/500");
+            }
+        }
+        _ => panic!("Expected 500 error status"),
+    };
+}
+
+#[test]
+fn dns_error() {
+    let client = RestClient::new_blocking("http://restson.invalid").unwrap();
+
+    match client.get::<_, HttpBinStatus>(200) {
+        Err(Error::DnsError(_)) => (),
+        _ => panic!("Expected DNS error"),
+    };
+}
+
+#[test]
+fn connect_error() {
+    let client = RestClient::new_blocking("http://127.0.0.1:1").unwrap();
+
+    match client.get::<_, HttpBinStatus>(200) {
+        Err(Error::ConnectError(_)) => (),
+        _ => panic!("Expected connect error"),
+    };
+}
+
+#[test]
+fn tls_error() {
+    // Plain HTTP port does not speak TLS
+    let client = RestClient::new_blocking("https://httpbin.org:80").unwrap();
+
+    match client.get::<_, HttpBinStatus>(200) {
+        Err(Error::TlsError(_)) => (),
+        _ => panic!("Expected TLS error"),
+    };
+}
+
+#[test]
+fn http_error_request_id() {
+    let client = RestClient::builder().request_id_header("X-Request-Id").blocking("http://httpbin.org").unwrap();
+
+    match client.get::<_, HttpBinStatus>(500) {
+        Err(Error::HttpError(500, _, req)) => assert!(req.request_id.is_some()),
+        _ => panic!("Expected 500 error status"),
+    };
+}
+
+#[derive(Deserialize)]
+struct ApiProblem {
+    title: String,
+}
+
+/// Serve the problem details with the status
+fn serve_problem(status: &'static str, body: &'static str) -> common::Server {
+    common::serve(move |_| {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/problem+json\r\nContent-Length: {}\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+    })
+}
+
+#[test]
+fn get_opt_problem_not_found() {
+    let server = serve_problem("404 Not Found", r#"{"title": "Not Found"}"#);
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    assert!(client.get_opt::<_, HttpBinStatus>(404).unwrap().is_none());
+}
+
+#[test]
+fn typed_error_problem() {
+    let server = serve_problem("409 Conflict", r#"{"title": "Conflict", "status": 409}"#);
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    match client.get_typed_err::<_, HttpBinStatus, ApiProblem>(409) {
+        Err(TypedError::Api(409, problem)) => assert_eq!(problem.title, "Conflict"),
+        _ => panic!("Expected problem details as API error"),
+    };
+}
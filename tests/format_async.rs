@@ -0,0 +1,190 @@
+use hyper::Method;
+use restson::mock::MockClient;
+use restson::{BodyFormat, Error, RestClient, RestPath, Visit};
+use serde_derive::{Deserialize, Serialize};
+use serde::de::IntoDeserializer;
+
+/// JSON encoded in base64, to check that the format is used instead of JSON
+struct Base64Json;
+
+impl BodyFormat for Base64Json {
+    fn content_type(&self) -> &str {
+        "application/x-base64-json"
+    }
+
+    fn serialize(&self, data: &dyn erased_serde::Serialize) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(base64::encode(serde_json::to_vec(data)?).into_bytes())
+    }
+
+    fn deserialize(&self, body: &[u8], visit: Visit<'_>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let json = base64::decode(body)?;
+        let mut de = serde_json::Deserializer::from_slice(&json);
+        Ok(visit(&mut <dyn erased_serde::Deserializer>::erase(&mut de))?)
+    }
+}
+
+/// Text body as JSON string
+struct PlainText;
+
+impl BodyFormat for PlainText {
+    fn content_type(&self) -> &str {
+        "text/plain"
+    }
+
+    fn serialize(&self, data: &dyn erased_serde::Serialize) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+        let text: String = serde_json::from_slice(&serde_json::to_vec(data)?)?;
+        Ok(text.into_bytes())
+    }
+
+    fn deserialize(&self, body: &[u8], visit: Visit<'_>) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let de = IntoDeserializer::<serde::de::value::Error>::into_deserializer(std::str::from_utf8(body)?);
+        Ok(visit(&mut <dyn erased_serde::Deserializer>::erase(de))?)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Device {
+    id: u32,
+    name: String,
+}
+
+impl RestPath<u32> for Device {
+    fn get_path(id: u32) -> Result<String, Error> {
+        Ok(format!("devices/{}", id))
+    }
+}
+
+impl RestPath<()> for Device {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("devices"))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Counter {
+    value: u128,
+}
+
+impl RestPath<()> for Counter {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("counters"))
+    }
+}
+
+#[tokio::test]
+async fn format_response() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/devices/1").respond(200, &base64::encode(r#"{"id":1,"name":"sensor"}"#));
+    let client = RestClient::builder().transport(mock.clone()).body_format(Base64Json).build("http://api.test").unwrap();
+
+    let device = client.get::<_, Device>(1).await.unwrap().into_inner();
+    assert_eq!(device.id, 1);
+    assert_eq!(device.name, "sensor");
+    mock.verify();
+}
+
+#[tokio::test]
+async fn format_request_body() {
+    let mock = MockClient::new();
+    mock.when(Method::POST, "/devices")
+        .header("content-type", "application/x-base64-json")
+        .body(&base64::encode(r#"{"id":2,"name":"valve"}"#))
+        .times(1)
+        .respond(201, &base64::encode(r#"{"id":2,"name":"valve"}"#));
+    let client = RestClient::builder().transport(mock.clone()).body_format(Base64Json).build("http://api.test").unwrap();
+
+    let device = Device { id: 2, name: String::from("valve") };
+    let res = client.post_capture::<_, _, Device>((), &device).await.unwrap();
+    assert_eq!(res.into_inner().name, "valve");
+    mock.verify();
+}
+
+#[tokio::test]
+async fn format_u128() {
+    let body = base64::encode(r#"{"value":18446744073709551616}"#);
+    let mock = MockClient::new();
+    mock.when(Method::POST, "/counters").body(&body).times(1).respond(201, &body);
+    let client = RestClient::builder().transport(mock.clone()).body_format(Base64Json).build("http://api.test").unwrap();
+
+    // Integer larger than u64 is serialized and deserialized by the format
+    let counter = Counter { value: u64::MAX as u128 + 1 };
+    let res = client.post_capture::<_, _, Counter>((), &counter).await.unwrap();
+    assert_eq!(res.into_inner().value, u64::MAX as u128 + 1);
+    mock.verify();
+}
+
+#[tokio::test]
+async fn format_parse_error() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/devices/1").respond(200, r#"{"id":1,"name":"sensor"}"#);
+    let client = RestClient::builder().transport(mock.clone()).body_format(Base64Json).build("http://api.test").unwrap();
+
+    match client.get::<_, Device>(1).await {
+        Err(Error::DeserializeParseError(_, body)) => assert_eq!(body, r#"{"id":1,"name":"sensor"}"#),
+        _ => panic!("Expected deserialize error"),
+    };
+}
+
+#[tokio::test]
+async fn format_with_body_format() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/devices/1").respond(200, &base64::encode(r#"{"id":1,"name":"sensor"}"#));
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test").unwrap();
+
+    assert!(client.get::<_, Device>(1).await.is_err());
+    let client = client.with_body_format(Base64Json);
+    assert_eq!(client.get::<_, Device>(1).await.unwrap().into_inner().name, "sensor");
+}
+
+#[tokio::test]
+async fn format_default_accept() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/devices/1")
+        .header("accept", "application/json")
+        .respond(200, r#"{"id":1,"name":"sensor"}"#);
+    mock.when(Method::GET, "/devices/2")
+        .header("accept", "application/x-base64-json, text/plain")
+        .respond(200, &base64::encode(r#"{"id":2,"name":"valve"}"#));
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test").unwrap();
+    assert_eq!(client.get::<_, Device>(1).await.unwrap().into_inner().id, 1);
+
+    let client = RestClient::builder()
+        .transport(mock.clone())
+        .body_format(Base64Json)
+        .response_format(PlainText)
+        .build("http://api.test")
+        .unwrap();
+    assert_eq!(client.get::<_, Device>(2).await.unwrap().into_inner().id, 2);
+}
+
+#[tokio::test]
+async fn format_response_content_type() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/devices/1")
+        .respond_header("content-type", "application/x-base64-json; charset=utf-8")
+        .respond(200, &base64::encode(r#"{"id":1,"name":"sensor"}"#));
+    mock.when(Method::GET, "/devices/2")
+        .respond_header("content-type", "application/json")
+        .respond(200, r#"{"id":2,"name":"valve"}"#);
+    let client = RestClient::builder().transport(mock.clone()).response_format(Base64Json).build("http://api.test").unwrap();
+
+    assert_eq!(client.get::<_, Device>(1).await.unwrap().into_inner().name, "sensor");
+    assert_eq!(client.get::<_, Device>(2).await.unwrap().into_inner().name, "valve");
+}
+
+#[tokio::test]
+async fn format_accept() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/devices/1")
+        .header("accept", "application/vnd.devices.v2+json")
+        .respond(200, r#"{"id":1,"name":"sensor"}"#);
+    let client = RestClient::builder()
+        .transport(mock.clone())
+        .accept("application/vnd.devices.v1+json")
+        .build("http://api.test")
+        .unwrap();
+
+    assert!(client.get::<_, Device>(1).await.is_err());
+    let client = client.with_accept("application/vnd.devices.v2+json").unwrap();
+    assert_eq!(client.get::<_, Device>(1).await.unwrap().into_inner().name, "sensor");
+}
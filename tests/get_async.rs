@@ -1,5 +1,8 @@
-use restson::{Error, RestClient, RestPath};
-use serde_derive::Deserialize;
+use hyper::body::Bytes;
+use hyper::StatusCode;
+use restson::{ArrayStyle, Conditional, Error, QueryPairs, RestClient, RestPath};
+use serde_derive::{Deserialize, Serialize};
+use std::fmt::Display;
 use std::time::Duration;
 
 #[derive(Deserialize)]
@@ -8,11 +11,57 @@ struct HttpBinAnything {
     args: HttpBinAnythingArgs,
 }
 
+#[derive(Deserialize)]
+struct HttpBinEtag {
+    url: String,
+}
+
+impl RestPath<&str> for HttpBinEtag {
+    fn get_path(etag: &str) -> Result<String, Error> {
+        Ok(format!("etag/{}", etag))
+    }
+}
+
+#[derive(Deserialize)]
+struct HttpBinText {
+    text: String,
+}
+
+impl RestPath<&str> for HttpBinText {
+    fn get_path(body: &str) -> Result<String, Error> {
+        Ok(format!("base64/{}", base64::encode_config(body, base64::URL_SAFE)))
+    }
+}
+
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct HttpBinIds {
+    ids: Vec<u32>,
+}
+
+impl RestPath<()> for HttpBinIds {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
 #[derive(Deserialize)]
 struct HttpRelativePath {
     url: String,
 }
 
+#[derive(Serialize)]
+struct AnythingQuery<'a> {
+    a: u32,
+    b: Option<&'a str>,
+    c: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct TagsQuery<'a> {
+    tags: Vec<&'a str>,
+}
+
 #[derive(Deserialize)]
 struct HttpBinAnythingArgs {
     #[serde(default)]
@@ -33,13 +82,60 @@ impl RestPath<u32> for HttpBinAnything {
     }
 }
 
-impl<'a> RestPath<(u32, &'a str)> for HttpBinAnything {
+impl RestPath<(u32, &str)> for HttpBinAnything {
     fn get_path(param: (u32, &str)) -> Result<String, Error> {
         let (a, b) = param;
         Ok(format!("anything/{}/{}", a, b))
     }
 }
 
+struct HttpBinBytes;
+
+struct HttpBinRobots;
+
+struct HttpBinRange;
+
+impl RestPath<u32> for HttpBinRange {
+    fn get_path(len: u32) -> Result<String, Error> {
+        Ok(format!("range/{}", len))
+    }
+}
+
+impl RestPath<()> for HttpBinRobots {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("robots.txt"))
+    }
+}
+
+impl RestPath<u32> for HttpBinBytes {
+    fn get_path(len: u32) -> Result<String, Error> {
+        Ok(format!("bytes/{}", len))
+    }
+}
+
+#[derive(Deserialize)]
+struct HttpBinVersioned {
+    url: String,
+}
+
+impl RestPath<()> for HttpBinVersioned {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+
+    fn get_query(_: &()) -> Vec<(String, String)> {
+        vec![(String::from("version"), String::from("2"))]
+    }
+}
+
+struct HttpBinStatus;
+
+impl RestPath<u16> for HttpBinStatus {
+    fn get_path(status: u16) -> Result<String, Error> {
+        Ok(format!("status/{}", status))
+    }
+}
+
 impl RestPath<()> for HttpRelativePath {
     fn get_path(_: ()) -> Result<String, Error> {
         Ok(String::from("test"))
@@ -109,6 +205,89 @@ async fn get_query_params() {
     assert_eq!(data.args.b, "abcd");
 }
 
+#[tokio::test]
+async fn get_query_params_display() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let params = [("a", 2), ("b", 3)];
+    let data = client.get_with::<_, HttpBinAnything>((), &params[..]).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything?a=2&b=3");
+
+    let params: Vec<(&str, &dyn Display)> = vec![("a", &2.5), ("b", &true)];
+    let data = client.get_with::<_, HttpBinAnything>((), &params[..]).await.unwrap();
+    assert_eq!(data.args.a, "2.5");
+    assert_eq!(data.args.b, "true");
+
+    let params: QueryPairs = [("a", 2), ("b", 3)].into_iter().collect();
+    let data = client.get_with::<_, HttpBinAnything>((), &params).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything?a=2&b=3");
+}
+
+#[tokio::test]
+async fn get_query_params_empty() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let data = client.get_with::<_, HttpBinAnything>((), &vec![]).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+}
+
+#[tokio::test]
+async fn get_query_pairs() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let mut query = QueryPairs::new().push("a", 2);
+    for tag in ["x", "y"] {
+        query = query.push("tag", tag);
+    }
+    let data = client.get_with::<_, HttpBinAnything>((), &query).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything?a=2&tag=x&tag=y");
+}
+
+#[tokio::test]
+async fn get_endpoint_query() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let data = client.get::<_, HttpBinVersioned>(()).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything?version=2");
+
+    let data = client.get_with::<_, HttpBinVersioned>((), &[("a", "1")]).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything?version=2&a=1");
+}
+
+#[tokio::test]
+async fn get_with_query() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let query = AnythingQuery { a: 2, b: Some("ab cd"), c: None };
+    let data = client.get_with_query::<_, HttpBinAnything, _>((), &query).await.unwrap();
+
+    assert_eq!(data.url, "https://httpbin.org/anything?a=2&b=ab+cd");
+    assert_eq!(data.args.a, "2");
+    assert_eq!(data.args.b, "ab cd");
+}
+
+#[tokio::test]
+async fn get_with_query_array() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let query = TagsQuery { tags: vec!["a", "b c"] };
+    let data = client.get_with_query::<_, HttpBinAnything, _>((), &query).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything?tags=a&tags=b+c");
+}
+
+#[tokio::test]
+async fn get_with_query_array_style() {
+    let query = TagsQuery { tags: vec!["a", "b"] };
+
+    let client = RestClient::builder().query_array_style(ArrayStyle::Comma).build("https://httpbin.org").unwrap();
+    let data = client.get_with_query::<_, HttpBinAnything, _>((), &query).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything?tags=a%2Cb");
+
+    let client = RestClient::builder().query_array_style(ArrayStyle::Brackets).build("https://httpbin.org").unwrap();
+    let data = client.get_with_query::<_, HttpBinAnything, _>((), &query).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything?tags%5B%5D=a&tags%5B%5D=b");
+}
+
 #[tokio::test]
 async fn relative_path() {
     // When using relative paths, the base path should end with '/'. Otherwise
@@ -135,3 +314,210 @@ async fn body_wash_fn() {
     let data = client.get::<_, HttpBinAnything>(()).await.unwrap();
     assert_eq!(data.url, "from body wash fn");
 }
+
+#[tokio::test]
+async fn body_wash_closure() {
+    let mut client = RestClient::new("https://httpbin.org").unwrap();
+
+    // XSSI guard prefix captured by the closure
+    let prefix = String::from(")]}'\n");
+    client.set_body_wash_fn(move |body: String| match body.strip_prefix(&prefix) {
+        Some(body) => body.to_owned(),
+        None => body,
+    });
+
+    let data = client.get::<_, HttpBinText>(")]}'\n{\"text\": \"guarded\"}").await.unwrap();
+    assert_eq!(data.text, "guarded");
+}
+
+#[tokio::test]
+async fn async_body_wash_fn() {
+    let mut client = RestClient::new("https://httpbin.org").unwrap();
+
+    // Body is sent base64 encoded and decoded by the async body wash fn
+    client.set_async_body_wash_fn(|body: Bytes| async move {
+        let body = base64::decode(&body).map_err(|_| Error::InvalidValue)?;
+        Ok(Bytes::from(body))
+    });
+
+    let body = base64::encode("{\"text\": \"encoded\"}");
+    let data = client.get::<_, HttpBinText>(body.as_str()).await.unwrap();
+    assert_eq!(data.text, "encoded");
+}
+
+#[tokio::test]
+async fn async_body_wash_fn_error() {
+    let mut client = RestClient::new("https://httpbin.org").unwrap();
+
+    client.set_async_body_wash_fn(|_| async { Err(Error::InvalidValue) });
+
+    let res = client.get::<_, HttpBinText>("{}").await;
+    assert!(matches!(res, Err(Error::InvalidValue)));
+}
+
+#[tokio::test]
+async fn get_raw() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let data = client.get_raw::<_, HttpBinBytes>(64).await.unwrap();
+    assert_eq!(data.len(), 64);
+    assert_eq!(data.headers()["content-type"], "application/octet-stream");
+}
+
+#[tokio::test]
+async fn get_raw_skips_body_wash() {
+    let mut client = RestClient::new("https://httpbin.org").unwrap();
+
+    client.set_body_wash_fn(|_| String::new());
+
+    let data = client.get_raw::<_, HttpBinBytes>(16).await.unwrap();
+    assert_eq!(data.len(), 16);
+}
+
+#[tokio::test]
+async fn get_hyper() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let res = client.get_hyper::<_, HttpBinBytes>(64).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()["content-type"], "application/octet-stream");
+    let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+    assert_eq!(body.len(), 64);
+}
+
+#[tokio::test]
+async fn get_hyper_error_status() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    // Error status is returned in the response instead of an error
+    let res = client.get_hyper::<_, HttpBinStatus>(418).await.unwrap();
+    assert_eq!(res.status(), StatusCode::IM_A_TEAPOT);
+}
+
+#[tokio::test]
+async fn get_multibyte_text() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let text = "äö€ ".repeat(200);
+    let body = format!(r#"{{"text": "{}"}}"#, text);
+    let data = client.get::<_, HttpBinText>(body.as_str()).await.unwrap();
+    assert_eq!(data.text, text);
+}
+
+#[tokio::test]
+async fn get_incremental() {
+    let mut client = RestClient::new("https://httpbin.org").unwrap();
+
+    // Body wash is not applied to the body deserialized as it is received
+    client.set_body_wash_fn(|_| String::new());
+
+    let params = vec![("a", "2"), ("b", "abcd")];
+    let data = client.get_incremental_with::<_, HttpBinAnything>((), &params).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything?a=2&b=abcd");
+    assert_eq!(data.args.b, "abcd");
+}
+
+#[tokio::test]
+async fn get_incremental_parse_error() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    match client.get_incremental::<_, HttpBinIds>(()).await {
+        Err(Error::DeserializeParseError(err, body)) => {
+            assert!(err.to_string().contains("missing field `ids`"));
+            assert!(body.is_empty());
+        }
+        _ => panic!("Expected deserialize error"),
+    }
+}
+
+#[tokio::test]
+async fn get_text() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let data = client.get_text::<_, HttpBinRobots>(()).await.unwrap();
+    assert!(data.starts_with("User-agent: *"));
+}
+
+#[tokio::test]
+async fn get_to_writer() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let mut data = Vec::new();
+    let written = client.get_to_writer::<_, HttpBinBytes, _>(1024, &mut data).await.unwrap();
+    assert_eq!(*written, 1024);
+    assert_eq!(data.len(), 1024);
+}
+
+#[tokio::test]
+async fn get_to_writer_with_progress() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let mut data = Vec::new();
+    let mut reported = Vec::new();
+    client
+        .get_to_writer_with_progress::<_, HttpBinBytes, _, _>(1024, &mut data, |received, total| {
+            reported.push((received, total))
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(reported.last(), Some(&(1024, Some(1024))));
+}
+
+#[tokio::test]
+async fn download_resumable() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let path = std::env::temp_dir().join("restson_download_resumable_async");
+    let _ = std::fs::remove_file(&path);
+
+    let len = client.download_resumable::<_, HttpBinRange, _>(26, &path).await.unwrap();
+    assert_eq!(*len, 26);
+    assert_eq!(std::fs::read(&path).unwrap(), b"abcdefghijklmnopqrstuvwxyz");
+}
+
+#[tokio::test]
+async fn download_resumable_partial() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let path = std::env::temp_dir().join("restson_download_resumable_partial_async");
+    std::fs::write(&path, b"abcdefghij").unwrap();
+
+    let len = client.download_resumable::<_, HttpBinRange, _>(26, &path).await.unwrap();
+    assert_eq!(*len, 26);
+    assert_eq!(std::fs::read(&path).unwrap(), b"abcdefghijklmnopqrstuvwxyz");
+}
+
+#[tokio::test]
+async fn get_status() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).await.unwrap();
+    assert_eq!(data.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn get_opt() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let data = client.get_opt::<_, HttpBinAnything>(()).await.unwrap().unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+}
+
+#[tokio::test]
+async fn get_if_none_match() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let data = client.get::<_, HttpBinEtag>("abc").await.unwrap();
+    assert_eq!(data.etag(), Some("\"abc\""));
+
+    match client.get_if_none_match::<_, HttpBinEtag>("abc", data.etag().unwrap()).await.unwrap() {
+        Conditional::NotModified => (),
+        _ => panic!("Expected not modified"),
+    };
+
+    match client.get_if_none_match::<_, HttpBinEtag>("abc", "\"xyz\"").await.unwrap() {
+        Conditional::Modified(data) => assert_eq!(data.url, "https://httpbin.org/etag/abc"),
+        _ => panic!("Expected modified resource"),
+    };
+}
@@ -0,0 +1,92 @@
+use hyper::Method;
+use restson::hal::Hal;
+use restson::mock::MockClient;
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Order {
+    total: f64,
+}
+
+impl RestPath<u32> for Order {
+    fn get_path(id: u32) -> Result<String, Error> {
+        Ok(format!("api/orders/{}", id))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Customer {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Item {
+    sku: String,
+}
+
+const ORDER: &str = r#"{
+    "total": 30.5,
+    "_links": {
+        "self": {"href": "/api/orders/1"},
+        "customer": {"href": "http://api.test/api/customers/5"},
+        "find": {"href": "/api/orders{?id}", "templated": true},
+        "invoices": [{"href": "/api/invoices/1", "name": "first"}, {"href": "/api/invoices/2", "name": "second"}]
+    },
+    "_embedded": {
+        "items": [
+            {"sku": "A-1", "_links": {"self": {"href": "/api/items/1"}}},
+            {"sku": "B-2", "_links": {"self": {"href": "/api/items/2"}}}
+        ]
+    }
+}"#;
+
+#[tokio::test]
+async fn hal_links() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/api/orders/1").respond(200, ORDER);
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test").unwrap();
+
+    let order = client.get::<_, Hal<Order>>(1).await.unwrap().into_inner();
+    assert_eq!(order.total, 30.5);
+    assert_eq!(order.link("self").unwrap().href, "/api/orders/1");
+    assert!(order.link("find").unwrap().templated);
+    let invoices: Vec<_> = order.links("invoices").iter().map(|link| link.name.as_deref().unwrap()).collect();
+    assert_eq!(invoices, ["first", "second"]);
+    assert!(order.links("next").is_empty());
+
+    let items = order.embedded::<Vec<Hal<Item>>>("items").unwrap().unwrap();
+    assert_eq!(items[1].sku, "B-2");
+    assert_eq!(items[1].link("self").unwrap().href, "/api/items/2");
+}
+
+#[tokio::test]
+async fn hal_follow() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/api/orders/1").respond(200, ORDER);
+    mock.when(Method::GET, "/api/customers/5")
+        .header("authorization", "Bearer token")
+        .respond(200, r#"{"name": "Jane", "_links": {"self": {"href": "/api/customers/5"}}}"#);
+    let client = RestClient::builder().transport(mock.clone()).auth_bearer("token").build("http://api.test").unwrap();
+
+    let order = client.get::<_, Hal<Order>>(1).await.unwrap();
+    let customer = client.follow::<_, Hal<Customer>>(&order, "customer").await.unwrap().unwrap();
+    assert_eq!(customer.name, "Jane");
+    assert!(client.follow::<_, Customer>(&order, "next").await.unwrap().is_none());
+    assert!(matches!(client.follow::<_, Order>(&order, "find").await, Err(Error::UrlError)));
+    mock.verify();
+}
+
+#[tokio::test]
+async fn hal_follow_relative() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/api/orders/1").respond(200, r#"{"total": 30.5, "_links": {"previous": {"href": "2"}}}"#);
+    mock.when(Method::GET, "/api/orders/2").respond(200, r#"{"total": 12.0}"#);
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test").unwrap();
+
+    // Link is relative to the URL of the response, not to the base URL
+    let order = client.get::<_, Hal<Order>>(1).await.unwrap();
+    let previous = client.follow::<_, Order>(&order, "previous").await.unwrap().unwrap();
+    assert_eq!(previous.total, 12.0);
+    mock.verify();
+}
@@ -16,6 +16,10 @@ struct TestHeaders {
     #[serde(default)]
     #[serde(rename = "X-Test")]
     test: String,
+
+    #[serde(default)]
+    #[serde(rename = "X-Request-Id")]
+    request_id: String,
 }
 
 impl RestPath<()> for HttpBinAnything {
@@ -69,3 +73,60 @@ fn response_headers() {
     let data = client.get::<_, HttpBinAnything>(()).unwrap();
     assert_eq!(data.headers()["content-type"], "application/json");
 }
+
+#[test]
+fn response_into_parts() {
+    let client = RestClient::new_blocking("http://httpbin.org").unwrap();
+
+    let (data, headers, status) = client.get::<_, HttpBinAnything>(()).unwrap().into_parts();
+    assert_eq!(data.headers.user_agent, "restson/".to_owned() + env!("CARGO_PKG_VERSION"));
+    assert_eq!(headers["content-type"], "application/json");
+    assert_eq!(status, 200);
+}
+
+#[test]
+fn response_map() {
+    let client = RestClient::new_blocking("http://httpbin.org").unwrap();
+
+    let data = client
+        .get::<_, HttpBinAnything>(())
+        .unwrap()
+        .map(|data| data.headers.user_agent);
+    assert_eq!(*data, "restson/".to_owned() + env!("CARGO_PKG_VERSION"));
+    assert_eq!(data.headers()["content-type"], "application/json");
+}
+
+#[test]
+fn response_timings() {
+    let client = RestClient::new_blocking("http://httpbin.org").unwrap();
+
+    let timings = client.get::<_, HttpBinAnything>(()).unwrap().timings();
+    assert!(timings.dns.is_some());
+    assert!(timings.connect.is_some());
+    assert!(timings.tls.is_none());
+    assert!(timings.total >= timings.ttfb);
+
+    // Pooled connection is used for the next request
+    let timings = client.get::<_, HttpBinAnything>(()).unwrap().timings();
+    assert!(timings.dns.is_none());
+    assert!(timings.connect.is_none());
+}
+
+#[test]
+fn request_id() {
+    let mut client = RestClient::builder().request_id_header("X-Request-Id").blocking("http://httpbin.org").unwrap();
+
+    let first = client.get::<_, HttpBinAnything>(()).unwrap();
+    let id = first.request_id().unwrap().to_owned();
+    assert_eq!(id.len(), 36);
+    assert_eq!(first.into_inner().headers.request_id, id);
+
+    let second = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_ne!(second.request_id().unwrap(), id);
+
+    // Value set by the user is used as the ID
+    client.set_header("X-Request-Id", "my-id").unwrap();
+    let res = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(res.request_id(), Some("my-id"));
+    assert_eq!(res.into_inner().headers.request_id, "my-id");
+}
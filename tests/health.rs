@@ -0,0 +1,61 @@
+use hyper::Method;
+use restson::mock::MockClient;
+use restson::{Error, HostHealth, LoadBalancer, RestClient, RestPath};
+use serde_derive::Deserialize;
+use std::time::Duration;
+
+#[derive(Debug, Deserialize)]
+struct Pod {
+    name: String,
+}
+
+impl RestPath<()> for Pod {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("pod"))
+    }
+}
+
+#[test]
+fn healthcheck() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/health").times(1).respond(200, "");
+    mock.when(Method::GET, "/health").respond(503, "");
+    let client = RestClient::builder().transport(mock.clone()).blocking("http://api.test/").unwrap();
+
+    let mut checks = client.healthcheck("health", 200, Duration::from_millis(10)).unwrap();
+    assert_eq!(checks.next(), Some(HostHealth { url: "http://api.test/health".to_string(), healthy: true }));
+    assert_eq!(checks.next(), Some(HostHealth { url: "http://api.test/health".to_string(), healthy: false }));
+}
+
+#[test]
+fn healthcheck_load_balancer() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/a/health").respond(200, "");
+    mock.when(Method::GET, "/b/health").respond(503, "");
+    mock.when(Method::GET, "/a/pod").times(3).respond(200, r#"{"name": "a"}"#);
+    let client = RestClient::builder()
+        .transport(mock.clone())
+        .load_balancer(LoadBalancer::new(&["http://api.test/a/", "http://api.test/b/"]))
+        .blocking("http://api.test/a/")
+        .unwrap();
+
+    let checks: Vec<HostHealth> = client.healthcheck("health", 200, Duration::from_secs(60)).unwrap().take(2).collect();
+    assert_eq!(
+        checks,
+        [
+            HostHealth { url: "http://api.test/a/health".to_string(), healthy: true },
+            HostHealth { url: "http://api.test/b/health".to_string(), healthy: false },
+        ]
+    );
+
+    for _ in 0..3 {
+        assert_eq!(client.get::<_, Pod>(()).unwrap().name, "a");
+    }
+    mock.verify();
+}
+
+#[test]
+fn healthcheck_invalid_status() {
+    let client = RestClient::new_blocking("http://api.test/").unwrap();
+    assert!(matches!(client.healthcheck("health", 1000, Duration::from_secs(1)), Err(Error::InvalidValue)));
+}
@@ -0,0 +1,49 @@
+use hyper::header::HeaderValue;
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+struct HttpBinAnything {
+    headers: TestHeaders,
+}
+
+#[derive(Deserialize)]
+struct TestHeaders {
+    #[serde(default)]
+    #[serde(rename = "X-Test")]
+    test: String,
+}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+#[test]
+fn on_request() {
+    let client = RestClient::builder()
+        .on_request(|req| {
+            req.headers_mut().insert("X-Test", HeaderValue::from_static("hook"));
+        })
+        .blocking("http://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.headers.test, "hook");
+}
+
+#[test]
+fn on_response() {
+    let status = Arc::new(AtomicU16::new(0));
+    let recorded = status.clone();
+    let client = RestClient::builder()
+        .on_response(move |res| recorded.store(res.status().as_u16(), Ordering::SeqCst))
+        .blocking("http://httpbin.org")
+        .unwrap();
+
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(status.load(Ordering::SeqCst), 200);
+}
@@ -0,0 +1,87 @@
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+use std::io::Read;
+use std::net::TcpListener;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+/// Connection preface sent by HTTP/2 clients
+const PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+
+#[derive(Deserialize)]
+struct HttpBinAnything {}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+/// Accept one connection and return the base URL and receiver for the first
+/// bytes sent by the client, followed by the payload of the SETTINGS frame
+/// for HTTP/2 clients. The connection is closed without response.
+fn serve() -> (String, Receiver<Vec<u8>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut buf = vec![0; PREFACE.len()];
+        stream.read_exact(&mut buf).unwrap();
+        let http2 = buf == PREFACE;
+        let _ = tx.send(buf);
+        if !http2 {
+            return;
+        }
+
+        let mut header = [0; 9];
+        stream.read_exact(&mut header).unwrap();
+        let mut payload = vec![0; u32::from_be_bytes([0, header[0], header[1], header[2]]) as usize];
+        stream.read_exact(&mut payload).unwrap();
+        let _ = tx.send(payload);
+    });
+    (url, rx)
+}
+
+#[test]
+fn http2_prior_knowledge() {
+    let (url, rx) = serve();
+    let client = RestClient::builder().http2_prior_knowledge().blocking(&url).unwrap();
+
+    assert!(client.get::<_, HttpBinAnything>(()).is_err());
+    assert_eq!(rx.recv().unwrap(), PREFACE);
+}
+
+#[test]
+fn http1_by_default() {
+    let (url, rx) = serve();
+    let client = RestClient::new_blocking(&url).unwrap();
+
+    assert!(client.get::<_, HttpBinAnything>(()).is_err());
+    assert!(rx.recv().unwrap().starts_with(b"GET /anything HTTP/1.1\r\n"));
+}
+
+#[test]
+fn http2_options() {
+    let (url, rx) = serve();
+    let client = RestClient::builder()
+        .http2_prior_knowledge()
+        .http2_initial_stream_window_size(1 << 20)
+        .http2_keep_alive_interval(Duration::from_secs(30))
+        .http2_keep_alive_while_idle(true)
+        .blocking(&url)
+        .unwrap();
+
+    assert!(client.get::<_, HttpBinAnything>(()).is_err());
+    assert_eq!(rx.recv().unwrap(), PREFACE);
+
+    // SETTINGS_INITIAL_WINDOW_SIZE
+    let settings = rx.recv().unwrap();
+    let window = settings
+        .chunks(6)
+        .find(|setting| setting[..2] == [0, 4])
+        .map(|setting| u32::from_be_bytes([setting[2], setting[3], setting[4], setting[5]]));
+    assert_eq!(window, Some(1 << 20));
+}
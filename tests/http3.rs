@@ -0,0 +1,35 @@
+#![cfg(feature = "http3")]
+
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+
+#[derive(Deserialize)]
+struct HttpBinAnything {
+    url: String,
+}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+#[test]
+fn http3_fallback() {
+    // Request is sent over TCP when the server does not accept QUIC
+    let client = RestClient::builder().http3(true).blocking("https://httpbin.org").unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+}
+
+#[test]
+fn http3_plain_http() {
+    let client = RestClient::builder().http3(true).blocking("http://httpbin.org").unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.url, "http://httpbin.org/anything");
+}
@@ -0,0 +1,92 @@
+use hyper::Method;
+use restson::jsonapi::{Params, Related};
+use restson::mock::MockClient;
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Article {
+    title: String,
+}
+
+impl RestPath<u32> for Article {
+    fn get_path(id: u32) -> Result<String, Error> {
+        Ok(format!("articles/{}", id))
+    }
+}
+
+impl RestPath<()> for Article {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("articles"))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Person {
+    name: String,
+}
+
+const ARTICLE: &str = r#"{
+    "data": {
+        "type": "articles",
+        "id": "1",
+        "attributes": {"title": "JSON:API paints my bikeshed!"},
+        "relationships": {
+            "author": {"data": {"type": "people", "id": "9"}, "links": {"related": "/articles/1/author"}},
+            "tags": {"data": []}
+        },
+        "links": {"self": "/articles/1"}
+    },
+    "included": [
+        {"type": "people", "id": "9", "attributes": {"name": "Dan"}}
+    ]
+}"#;
+
+const ARTICLES: &str = r#"{
+    "data": [
+        {"type": "articles", "id": "1", "attributes": {"title": "First"}},
+        {"type": "articles", "id": "2", "attributes": {"title": "Second"}}
+    ],
+    "links": {"next": {"href": "/articles?page[number]=2", "meta": {"count": 10}}},
+    "meta": {"total": 4}
+}"#;
+
+#[tokio::test]
+async fn jsonapi_resource() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/articles/1?include=author&fields%5Barticles%5D=title%2Cauthor")
+        .header("accept", "application/vnd.api+json")
+        .respond(200, ARTICLE);
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test").unwrap();
+
+    let params = Params::new().include("author").fields("articles", &["title", "author"]);
+    let doc = client.get_jsonapi::<_, Article>(1, &params).await.unwrap().into_inner();
+    assert_eq!(doc.data.id.as_deref(), Some("1"));
+    assert_eq!(doc.data.attributes.title, "JSON:API paints my bikeshed!");
+    assert_eq!(doc.data.links["self"].href(), "/articles/1");
+
+    let author = &doc.data.relationships["author"];
+    assert!(matches!(author.data, Some(Related::One(_))));
+    assert_eq!(author.links["related"].href(), "/articles/1/author");
+    assert!(doc.data.relationships["tags"].identifiers().is_empty());
+
+    let authors = doc.related::<_, Person>(&doc.data, "author").unwrap();
+    assert_eq!(authors.len(), 1);
+    assert_eq!(authors[0].attributes.name, "Dan");
+    mock.verify();
+}
+
+#[tokio::test]
+async fn jsonapi_collection() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/articles?sort=-created&page%5Bnumber%5D=1").respond(200, ARTICLES);
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test").unwrap();
+
+    let params = Params::new().sort("-created").page("number", "1");
+    let doc = client.get_jsonapi_collection::<_, Article>((), &params).await.unwrap().into_inner();
+    let titles: Vec<_> = doc.data.iter().map(|article| article.attributes.title.as_str()).collect();
+    assert_eq!(titles, ["First", "Second"]);
+    assert_eq!(doc.links["next"].href(), "/articles?page[number]=2");
+    assert_eq!(doc.meta.unwrap()["total"], 4);
+    mock.verify();
+}
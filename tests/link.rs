@@ -0,0 +1,108 @@
+use hyper::Method;
+use restson::mock::MockClient;
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+
+mod common;
+
+#[derive(Debug, Deserialize)]
+struct Repo {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Repos(Vec<Repo>);
+
+impl RestPath<()> for Repos {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("user/repos"))
+    }
+}
+
+#[test]
+fn link_header() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/user/repos")
+        .respond_header(
+            "Link",
+            r#"<http://api.test/user/repos?page=2>; rel="next", <http://api.test/user/repos?page=5>; rel="last"; title="Last, page""#,
+        )
+        .respond_header("Link", "</help>; rel=help")
+        .respond(200, "[]");
+    let client = RestClient::builder().transport(mock.clone()).blocking("http://api.test").unwrap();
+
+    let res = client.get::<_, Repos>(()).unwrap();
+    let rels: Vec<_> = res.links().into_iter().map(|link| link.rel).collect();
+    assert_eq!(rels, ["next", "last", "help"]);
+    assert_eq!(res.link("next").unwrap().href, "http://api.test/user/repos?page=2");
+    assert_eq!(res.link("last").unwrap().param("title"), Some("Last, page"));
+    assert_eq!(res.link("help").unwrap().href, "/help");
+    assert!(res.link("prev").is_none());
+}
+
+#[test]
+fn link_get_next() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/user/repos?page=2")
+        .header("authorization", "Bearer token")
+        .respond_header("Link", r#"</user/repos?page=1>; rel="prev first""#)
+        .respond(200, r#"[{"name": "second"}]"#);
+    mock.when(Method::GET, "/user/repos")
+        .respond_header("Link", r#"</user/repos?page=2>; rel="next""#)
+        .respond(200, r#"[{"name": "first"}]"#);
+    let client = RestClient::builder().transport(mock.clone()).auth_bearer("token").blocking("http://api.test").unwrap();
+
+    let first = client.get::<_, Repos>(()).unwrap();
+    let second = client.get_next(&first).unwrap().unwrap();
+    assert_eq!(second.into_inner().0[0].name, "second");
+    mock.verify();
+}
+
+#[test]
+fn link_get_next_last_page() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/user/repos")
+        .respond_header("Link", r#"</user/repos?page=1>; rel="prev first""#)
+        .respond(200, "[]");
+    let client = RestClient::builder().transport(mock.clone()).blocking("http://api.test").unwrap();
+
+    let res = client.get::<_, Repos>(()).unwrap();
+    assert_eq!(res.link("first").unwrap().href, "/user/repos?page=1");
+    assert!(client.get_next(&res).unwrap().is_none());
+}
+
+#[test]
+fn link_get_next_relative() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/user/repos?page=2").respond(200, r#"[{"name": "second"}]"#);
+    mock.when(Method::GET, "/user/repos")
+        .respond_header("Link", r#"<?page=2>; rel="next""#)
+        .respond(200, r#"[{"name": "first"}]"#);
+    let client = RestClient::builder().transport(mock.clone()).blocking("http://api.test").unwrap();
+
+    // Link is relative to the URL of the response, not to the base URL
+    let first = client.get::<_, Repos>(()).unwrap();
+    assert_eq!(first.url(), "http://api.test/user/repos");
+    let second = client.get_next(&first).unwrap().unwrap();
+    assert_eq!(second.into_inner().0[0].name, "second");
+    mock.verify();
+}
+
+#[test]
+fn link_get_next_cross_origin() {
+    let other = common::serve(|_| common::json("[]"));
+    let next = format!("{}/user/repos?page=2", other.url);
+    let server = common::serve(move |_| {
+        format!("HTTP/1.1 200 OK\r\nLink: <{}>; rel=\"next\"\r\nContent-Length: 2\r\n\r\n[]", next)
+    });
+    let client = RestClient::builder().auth_bearer("token").blocking(&server.url).unwrap();
+
+    let first = client.get::<_, Repos>(()).unwrap();
+    client.get_next(&first).unwrap().unwrap();
+
+    // Link to another origin is requested without the token
+    assert_eq!(server.requests.recv().unwrap().header("authorization"), Some("Bearer token"));
+    let req = other.requests.recv().unwrap();
+    assert_eq!(req.line(), "GET /user/repos?page=2");
+    assert_eq!(req.header("authorization"), None);
+}
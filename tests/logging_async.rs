@@ -0,0 +1,54 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+use std::sync::Mutex;
+
+#[derive(Deserialize)]
+struct HttpBinAnything {}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+struct Capture(Mutex<Vec<String>>);
+
+impl Log for Capture {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= Level::Trace
+    }
+
+    fn log(&self, record: &Record) {
+        self.0.lock().unwrap().push(record.args().to_string());
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: Capture = Capture(Mutex::new(Vec::new()));
+
+#[tokio::test]
+async fn redact_sensitive_headers() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Trace);
+
+    let mut client = RestClient::builder()
+        .sensitive_headers(&["authorization", "x-secret"])
+        .build("http://httpbin.org")
+        .unwrap();
+    client.set_auth_bearer("secret-token");
+    client.set_header("X-Secret", "secret-value").unwrap();
+    client.set_header("X-Public", "public-value").unwrap();
+
+    client.get::<_, HttpBinAnything>(()).await.unwrap();
+
+    // httpbin echoes the headers in the body
+    let logs = LOGGER.0.lock().unwrap();
+    let logs: Vec<_> = logs.iter().filter(|line| !line.starts_with("response body")).collect();
+    let logs = format!("{:?}", logs);
+    assert!(!logs.contains("secret-token"));
+    assert!(!logs.contains("secret-value"));
+    assert!(logs.contains("<redacted>"));
+    assert!(logs.contains("public-value"));
+}
@@ -0,0 +1,60 @@
+use hyper::Method;
+use restson::{Error, Metrics, RestClient, RestPath, RetryPolicy};
+use serde_derive::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Serialize, Deserialize)]
+struct HttpBinAnything {}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct HttpBinStatus {}
+
+impl RestPath<u16> for HttpBinStatus {
+    fn get_path(code: u16) -> Result<String, Error> {
+        Ok(format!("status/{}", code))
+    }
+}
+
+#[test]
+fn metrics() {
+    let metrics = Metrics::new();
+    let client = RestClient::builder()
+        .metrics(metrics.clone())
+        .blocking("http://httpbin.org")
+        .unwrap();
+
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    client.post((), &HttpBinAnything {}).unwrap();
+    assert!(client.get::<_, HttpBinStatus>(404).is_err());
+
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.requests[&(Method::GET, Some(200))], 1);
+    assert_eq!(snapshot.requests[&(Method::POST, Some(200))], 1);
+    assert_eq!(snapshot.requests[&(Method::GET, Some(404))], 1);
+    assert_eq!(snapshot.bytes_sent, 2);
+    assert!(snapshot.bytes_received > 0);
+    assert_eq!(snapshot.latency.count, 3);
+    assert_eq!(snapshot.latency.buckets.iter().map(|(_, count)| count).sum::<u64>(), 3);
+}
+
+#[test]
+fn metrics_retries() {
+    let metrics = Metrics::new();
+    let client = RestClient::builder()
+        .metrics(metrics.clone())
+        .retry(RetryPolicy::new(3).initial_backoff(Duration::from_millis(10)))
+        .blocking("http://127.0.0.1:1")
+        .unwrap();
+
+    assert!(client.get::<_, HttpBinStatus>(200).is_err());
+
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.requests[&(Method::GET, None)], 3);
+    assert_eq!(snapshot.retries, 2);
+}
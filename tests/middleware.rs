@@ -0,0 +1,94 @@
+use hyper::header::HeaderValue;
+use hyper::{Body, Request, Response};
+use restson::{Error, Middleware, MiddlewareFuture, Next, RestClient, RestPath};
+use serde_derive::Deserialize;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::Arc;
+
+#[derive(Deserialize)]
+struct HttpBinAnything {
+    headers: TestHeaders,
+}
+
+#[derive(Deserialize)]
+struct TestHeaders {
+    #[serde(default)]
+    #[serde(rename = "X-Test")]
+    test: String,
+}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+struct SetHeader(&'static str);
+
+impl Middleware for SetHeader {
+    fn handle<'a>(&'a self, mut req: Request<Body>, next: Next<'a>) -> MiddlewareFuture<'a> {
+        let value = match req.headers().get("X-Test") {
+            Some(value) => format!("{},{}", value.to_str().unwrap(), self.0),
+            None => self.0.to_owned(),
+        };
+        req.headers_mut().insert("X-Test", HeaderValue::from_str(&value).unwrap());
+        next.run(req)
+    }
+}
+
+struct RecordStatus(Arc<AtomicU16>);
+
+impl Middleware for RecordStatus {
+    fn handle<'a>(&'a self, req: Request<Body>, next: Next<'a>) -> MiddlewareFuture<'a> {
+        Box::pin(async move {
+            let res = next.run(req).await?;
+            self.0.store(res.status().as_u16(), Ordering::SeqCst);
+            Ok(res)
+        })
+    }
+}
+
+struct Canned;
+
+impl Middleware for Canned {
+    fn handle<'a>(&'a self, _: Request<Body>, _: Next<'a>) -> MiddlewareFuture<'a> {
+        Box::pin(async move {
+            Ok(Response::new(Body::from(r#"{"headers":{"X-Test":"canned"}}"#)))
+        })
+    }
+}
+
+#[test]
+fn middleware_order() {
+    let client = RestClient::builder()
+        .with_middleware(SetHeader("first"))
+        .with_middleware(SetHeader("second"))
+        .blocking("http://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.headers.test, "first,second");
+}
+
+#[test]
+fn middleware_response() {
+    let status = Arc::new(AtomicU16::new(0));
+    let client = RestClient::builder()
+        .with_middleware(RecordStatus(status.clone()))
+        .blocking("http://httpbin.org")
+        .unwrap();
+
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(status.load(Ordering::SeqCst), 200);
+}
+
+#[test]
+fn middleware_short_circuit() {
+    let client = RestClient::builder()
+        .with_middleware(Canned)
+        .blocking("http://127.0.0.1:1")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.headers.test, "canned");
+}
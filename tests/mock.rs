@@ -0,0 +1,75 @@
+use hyper::Method;
+use restson::mock::MockClient;
+use restson::{Error, RestClient, RestPath};
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct Device {
+    id: u32,
+    name: String,
+}
+
+impl RestPath<u32> for Device {
+    fn get_path(id: u32) -> Result<String, Error> {
+        Ok(format!("devices/{}", id))
+    }
+}
+
+impl RestPath<()> for Device {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("devices"))
+    }
+}
+
+#[test]
+fn mock_response() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/devices/1")
+        .respond_header("ETag", "\"v1\"")
+        .respond(200, r#"{"id": 1, "name": "sensor"}"#);
+    let client = RestClient::builder().transport(mock.clone()).blocking("http://api.test").unwrap();
+
+    let res = client.get::<_, Device>(1).unwrap();
+    assert_eq!(res.headers()["etag"], "\"v1\"");
+    assert_eq!(res.into_inner().name, "sensor");
+    mock.verify();
+}
+
+#[test]
+fn mock_request_body() {
+    let mock = MockClient::new();
+    mock.when(Method::POST, "/devices")
+        .header("content-type", "application/json")
+        .body(r#"{"id":2,"name":"valve"}"#)
+        .times(1)
+        .respond(201, "");
+    let client = RestClient::builder().transport(mock.clone()).blocking("http://api.test").unwrap();
+
+    let device = Device { id: 2, name: String::from("valve") };
+    client.post((), &device).unwrap();
+    mock.verify();
+}
+
+#[test]
+fn mock_unexpected_request() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/devices/1").times(1).respond(200, r#"{"id": 1, "name": "sensor"}"#);
+    let client = RestClient::builder().transport(mock.clone()).blocking("http://api.test").unwrap();
+
+    client.get::<_, Device>(1).unwrap();
+    match client.get::<_, Device>(1) {
+        Err(Error::HttpError(404, body, _)) => assert_eq!(body, "no mock for GET /devices/1"),
+        _ => panic!("Expected 404 error status"),
+    };
+}
+
+#[test]
+#[should_panic(expected = "expected GET /devices/1 2 times, got 1")]
+fn mock_verify() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/devices/1").times(2).respond(200, r#"{"id": 1, "name": "sensor"}"#);
+    let client = RestClient::builder().transport(mock.clone()).blocking("http://api.test").unwrap();
+
+    client.get::<_, Device>(1).unwrap();
+    mock.verify();
+}
@@ -0,0 +1,141 @@
+use futures::StreamExt;
+use hyper::Method;
+use restson::mock::MockClient;
+use restson::{Error, Paginated, RestClient, RestPath};
+use serde_derive::Deserialize;
+
+mod common;
+
+#[derive(Debug, Deserialize)]
+struct Items {
+    items: Vec<u32>,
+    cursor: Option<String>,
+}
+
+impl RestPath<()> for Items {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("items"))
+    }
+}
+
+#[tokio::test]
+async fn pages_link_header() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/items?page=2")
+        .respond_header("Link", r#"</items?page=3>; rel="next""#)
+        .respond(200, r#"{"items": [3, 4]}"#);
+    mock.when(Method::GET, "/items?page=3")
+        .respond_header("Link", r#"</items?page=1>; rel="first""#)
+        .respond(200, r#"{"items": [5]}"#);
+    mock.when(Method::GET, "/items")
+        .respond_header("Link", r#"</items?page=2>; rel="next""#)
+        .respond(200, r#"{"items": [1, 2]}"#);
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test").unwrap();
+
+    let pages = client.get_pages::<_, Items>(()).unwrap();
+    let items: Vec<_> = pages.map(|page| page.unwrap().into_inner().items).collect().await;
+    assert_eq!(items, [vec![1, 2], vec![3, 4], vec![5]]);
+}
+
+#[tokio::test]
+async fn pages_relative_link() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/v1/items?page=2").respond(200, r#"{"items": [2]}"#);
+    mock.when(Method::GET, "/v1/items")
+        .respond_header("Link", r#"<?page=2>; rel="next""#)
+        .respond(200, r#"{"items": [1]}"#);
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test/v1/").unwrap();
+
+    // Link is relative to the URL of the page
+    let pages = client.get_pages::<_, Items>(()).unwrap();
+    let items: Vec<_> = pages.map(|page| page.unwrap().into_inner().items).collect().await;
+    assert_eq!(items, [vec![1], vec![2]]);
+}
+
+#[tokio::test]
+async fn pages_custom_pagination() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/items?cursor=abc").respond(200, r#"{"items": [2], "cursor": null}"#);
+    mock.when(Method::GET, "/items").respond(200, r#"{"items": [1], "cursor": "abc"}"#);
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test").unwrap();
+
+    let pages = client
+        .get_pages_with::<_, Items, _>((), |res: &restson::Response<Items>| {
+            res.cursor.as_ref().map(|cursor| format!("items?cursor={}", cursor))
+        })
+        .unwrap();
+    let items: Vec<_> = pages.map(|page| page.unwrap().into_inner().items).collect().await;
+    assert_eq!(items, [vec![1], vec![2]]);
+}
+
+#[tokio::test]
+async fn pages_error() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/items?page=2").respond(500, "");
+    mock.when(Method::GET, "/items")
+        .respond_header("Link", r#"</items?page=2>; rel="next""#)
+        .respond(200, r#"{"items": [1]}"#);
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test").unwrap();
+
+    let mut pages = client.get_pages::<_, Items>(()).unwrap();
+    assert_eq!(pages.next().await.unwrap().unwrap().into_inner().items, [1]);
+    assert!(matches!(pages.next().await, Some(Err(Error::HttpError(500, _, _)))));
+    assert!(pages.next().await.is_none());
+}
+
+impl Paginated for Items {
+    type Item = u32;
+
+    fn into_items(self) -> Vec<u32> {
+        self.items
+    }
+}
+
+#[tokio::test]
+async fn pages_get_all() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/items?page=2")
+        .respond_header("Link", r#"</items?page=3>; rel="next""#)
+        .respond(200, r#"{"items": [3, 4]}"#);
+    mock.when(Method::GET, "/items?page=3").respond(200, r#"{"items": [5]}"#);
+    mock.when(Method::GET, "/items")
+        .respond_header("Link", r#"</items?page=2>; rel="next""#)
+        .respond(200, r#"{"items": [1, 2]}"#);
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test").unwrap();
+
+    let items = client.get_all::<_, Items>(()).await.unwrap();
+    assert_eq!(items, [1, 2, 3, 4, 5]);
+}
+
+#[tokio::test]
+async fn pages_get_all_max_pages() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/items")
+        .respond_header("Link", r#"</items>; rel="next""#)
+        .times(3)
+        .respond(200, r#"{"items": [1]}"#);
+    let client = RestClient::builder().transport(mock.clone()).max_pages(3).build("http://api.test").unwrap();
+
+    assert!(matches!(client.get_all::<_, Items>(()).await, Err(Error::TooManyPages)));
+    mock.verify();
+}
+
+#[tokio::test]
+async fn pages_get_all_cross_origin() {
+    let other = common::serve(|_| common::json(r#"{"items": [2]}"#));
+    let next = format!("{}/items?page=2", other.url);
+    let server = common::serve(move |_| {
+        let body = r#"{"items": [1]}"#;
+        format!("HTTP/1.1 200 OK\r\nLink: <{}>; rel=\"next\"\r\nContent-Length: {}\r\n\r\n{}", next, body.len(), body)
+    });
+    let client = RestClient::builder().auth_bearer("token").build(&server.url).unwrap();
+
+    let items = client.get_all::<_, Items>(()).await.unwrap();
+    assert_eq!(items, [1, 2]);
+
+    // Page on another origin is requested without the token
+    assert_eq!(server.requests.recv().unwrap().header("authorization"), Some("Bearer token"));
+    let req = other.requests.recv().unwrap();
+    assert_eq!(req.line(), "GET /items?page=2");
+    assert_eq!(req.header("authorization"), None);
+}
@@ -65,3 +65,12 @@ fn patch_capture_query_params() {
     assert_eq!(resp.json.data, "test data");
     assert_eq!(resp.url, "https://httpbin.org/patch?a=2&b=abcd");
 }
+
+#[test]
+fn patch_raw() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    client
+        .patch_raw::<_, HttpBinPatch>((), "a,b\n1,2\n", "text/csv")
+        .unwrap();
+}
@@ -66,3 +66,13 @@ async fn patch_capture_query_params() {
     assert_eq!(resp.json.data, "test data");
     assert_eq!(resp.url, "https://httpbin.org/patch?a=2&b=abcd");
 }
+
+#[tokio::test]
+async fn patch_raw() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    client
+        .patch_raw::<_, HttpBinPatch>((), "a,b\n1,2\n", "text/csv")
+        .await
+        .unwrap();
+}
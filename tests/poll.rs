@@ -0,0 +1,80 @@
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+use std::time::Duration;
+
+mod common;
+
+#[derive(Deserialize)]
+struct Job {
+    state: String,
+}
+
+impl RestPath<u32> for Job {
+    fn get_path(id: u32) -> Result<String, Error> {
+        Ok(format!("jobs/{}", id))
+    }
+}
+
+/// Serve job 1, which is running for the given number of requests and then
+/// done. Other jobs are not found.
+fn serve(running: usize) -> common::Server {
+    let mut requests = 0;
+    common::serve(move |req| {
+        requests += 1;
+        match req.target == "/jobs/1" {
+            true => common::json(&format!(r#"{{"state": "{}"}}"#, if requests <= running { "running" } else { "done" })),
+            false => common::NOT_FOUND.to_owned(),
+        }
+    })
+}
+
+#[test]
+fn poll_until_done() {
+    let server = serve(2);
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    let job = client
+        .poll_until::<_, Job, _>(1, Duration::from_millis(10), Duration::from_secs(5), |job| job.state == "done")
+        .unwrap();
+    assert_eq!(job.state, "done");
+    assert_eq!(server.requests.try_iter().count(), 3);
+}
+
+#[test]
+fn poll_until_timeout() {
+    let server = serve(usize::MAX);
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    // Polls at 0, 100 and 300 ms, and the next poll would be after the timeout
+    let res = client.poll_until::<_, Job, _>(1, Duration::from_millis(100), Duration::from_millis(500), |job| job.state == "done");
+    match res {
+        Err(Error::DeadlineExceeded(info)) => {
+            assert_eq!(info.attempts, 3);
+            assert_eq!(info.last_status, Some(200));
+        }
+        _ => panic!("Expected deadline exceeded error"),
+    };
+    assert_eq!(server.requests.try_iter().count(), 3);
+}
+
+#[test]
+fn poll_until_long_interval() {
+    let server = serve(usize::MAX);
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    // Interval longer than any timeout does not overflow
+    let res = client.poll_until::<_, Job, _>(1, Duration::MAX, Duration::from_secs(5), |job| job.state == "done");
+    match res {
+        Err(Error::DeadlineExceeded(info)) => assert_eq!(info.attempts, 1),
+        _ => panic!("Expected deadline exceeded error"),
+    };
+}
+
+#[test]
+fn poll_until_error() {
+    let server = serve(0);
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    let res = client.poll_until::<_, Job, _>(2, Duration::from_millis(10), Duration::from_secs(5), |job| job.state == "done");
+    assert!(matches!(res, Err(Error::HttpError(404, ..))));
+}
@@ -0,0 +1,119 @@
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+use std::net::TcpListener;
+use std::thread;
+use std::time::Duration;
+
+mod common;
+
+#[derive(Deserialize)]
+struct Empty {}
+
+impl RestPath<()> for Empty {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("empty"))
+    }
+}
+
+/// Serve empty JSON objects on keep-alive connections
+fn serve() -> common::Server {
+    common::serve(|req| match req.method.as_str() {
+        "HEAD" => "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n",
+        _ => "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}",
+    })
+}
+
+#[test]
+fn pool_reuse() {
+    let server = serve();
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    for _ in 0..3 {
+        client.get::<_, Empty>(()).unwrap();
+    }
+    assert_eq!(server.connections(), 1);
+}
+
+#[test]
+fn pool_clients_in_threads() {
+    let server = serve();
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let url = server.url.clone();
+            thread::spawn(move || {
+                let client = RestClient::new_blocking(&url).unwrap();
+                for _ in 0..3 {
+                    client.get::<_, Empty>(()).unwrap();
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(server.connections(), 4);
+}
+
+#[test]
+fn pool_max_idle_per_host() {
+    let server = serve();
+    let client = RestClient::builder().pool_max_idle_per_host(0).blocking(&server.url).unwrap();
+
+    for _ in 0..3 {
+        client.get::<_, Empty>(()).unwrap();
+    }
+    assert_eq!(server.connections(), 3);
+}
+
+#[test]
+fn pool_idle_timeout() {
+    let server = serve();
+    let client = RestClient::builder()
+        .pool_idle_timeout(Duration::from_millis(100))
+        .blocking(&server.url)
+        .unwrap();
+
+    client.get::<_, Empty>(()).unwrap();
+    thread::sleep(Duration::from_millis(300));
+    client.get::<_, Empty>(()).unwrap();
+    assert_eq!(server.connections(), 2);
+}
+
+#[test]
+fn connect() {
+    let server = serve();
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    client.connect().unwrap();
+    assert_eq!(server.connections(), 1);
+
+    // Request uses the connection that was opened
+    client.get::<_, Empty>(()).unwrap();
+    assert_eq!(server.connections(), 1);
+}
+
+#[test]
+fn connect_refused() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let client = RestClient::new_blocking(&url).unwrap();
+    match client.connect() {
+        Err(Error::ConnectError(_)) => (),
+        _ => panic!("Expected ConnectError"),
+    };
+}
+
+#[test]
+fn connect_timeout() {
+    let server = serve();
+    let client = RestClient::builder()
+        .connect_timeout(Duration::from_secs(5))
+        .blocking(&server.url)
+        .unwrap();
+
+    client.get::<_, Empty>(()).unwrap();
+    assert_eq!(server.connections(), 1);
+}
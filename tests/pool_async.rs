@@ -0,0 +1,97 @@
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+use std::net::TcpListener;
+use std::time::Duration;
+
+mod common;
+
+#[derive(Deserialize)]
+struct Empty {}
+
+impl RestPath<()> for Empty {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("empty"))
+    }
+}
+
+/// Serve empty JSON objects on keep-alive connections
+fn serve() -> common::Server {
+    common::serve(|req| match req.method.as_str() {
+        "HEAD" => "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n",
+        _ => "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}",
+    })
+}
+
+#[tokio::test]
+async fn pool_reuse() {
+    let server = serve();
+    let client = RestClient::new(&server.url).unwrap();
+
+    for _ in 0..3 {
+        client.get::<_, Empty>(()).await.unwrap();
+    }
+    assert_eq!(server.connections(), 1);
+}
+
+#[tokio::test]
+async fn pool_max_idle_per_host() {
+    let server = serve();
+    let client = RestClient::builder().pool_max_idle_per_host(0).build(&server.url).unwrap();
+
+    for _ in 0..3 {
+        client.get::<_, Empty>(()).await.unwrap();
+    }
+    assert_eq!(server.connections(), 3);
+}
+
+#[tokio::test]
+async fn pool_idle_timeout() {
+    let server = serve();
+    let client = RestClient::builder()
+        .pool_idle_timeout(Duration::from_millis(100))
+        .build(&server.url)
+        .unwrap();
+
+    client.get::<_, Empty>(()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    client.get::<_, Empty>(()).await.unwrap();
+    assert_eq!(server.connections(), 2);
+}
+
+#[tokio::test]
+async fn connect() {
+    let server = serve();
+    let client = RestClient::new(&server.url).unwrap();
+
+    client.connect().await.unwrap();
+    assert_eq!(server.connections(), 1);
+
+    // Request uses the connection that was opened
+    client.get::<_, Empty>(()).await.unwrap();
+    assert_eq!(server.connections(), 1);
+}
+
+#[tokio::test]
+async fn connect_refused() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    drop(listener);
+
+    let client = RestClient::new(&url).unwrap();
+    match client.connect().await {
+        Err(Error::ConnectError(_)) => (),
+        _ => panic!("Expected ConnectError"),
+    };
+}
+
+#[tokio::test]
+async fn connect_timeout() {
+    let server = serve();
+    let client = RestClient::builder()
+        .connect_timeout(Duration::from_secs(5))
+        .build(&server.url)
+        .unwrap();
+
+    client.get::<_, Empty>(()).await.unwrap();
+    assert_eq!(server.connections(), 1);
+}
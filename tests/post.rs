@@ -1,4 +1,6 @@
-use restson::{Error, RestClient, RestPath};
+use hyper::StatusCode;
+use restson::{Error, Multipart, RestClient, RestPath};
+use std::collections::HashMap;
 use serde_derive::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -12,6 +14,26 @@ struct HttpBinPostResp {
     url: String,
 }
 
+#[derive(Deserialize)]
+struct HttpBinPostFormResp {
+    form: HttpBinPost,
+}
+
+#[derive(Deserialize)]
+struct HttpBinPostMultipartResp {
+    form: HashMap<String, String>,
+    files: HashMap<String, String>,
+}
+
+#[derive(Serialize)]
+struct HttpBinStatus {}
+
+impl RestPath<u16> for HttpBinStatus {
+    fn get_path(code: u16) -> Result<String, Error> {
+        Ok(format!("status/{}", code))
+    }
+}
+
 impl RestPath<()> for HttpBinPost {
     fn get_path(_: ()) -> Result<String, Error> {
         Ok(String::from("post"))
@@ -52,6 +74,19 @@ fn post_capture() {
     assert_eq!(resp.url, "https://httpbin.org/post");
 }
 
+#[test]
+fn post_capture_long_string() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    // String fields longer than 16 KB are sent intact
+    let data = HttpBinPost {
+        data: "0123456789abcdef€".repeat(4096),
+    };
+    let resp = client.post_capture::<_, _, HttpBinPostResp>((), &data).unwrap();
+
+    assert_eq!(resp.json.data, data.data);
+}
+
 #[test]
 fn post_capture_query_params() {
     let client = RestClient::new_blocking("https://httpbin.org").unwrap();
@@ -65,3 +100,109 @@ fn post_capture_query_params() {
     assert_eq!(resp.json.data, "test data");
     assert_eq!(resp.url, "https://httpbin.org/post?a=2&b=abcd");
 }
+
+#[test]
+fn post_capture_text() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    let data = HttpBinPost {
+        data: String::from("test data"),
+    };
+    let resp = client.post_capture_text((), &data).unwrap();
+
+    assert!(resp.contains("\"url\": \"https://httpbin.org/post\""));
+}
+
+#[test]
+fn post_raw() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    client
+        .post_raw::<_, HttpBinPost>((), "a,b\n1,2\n", "text/csv")
+        .unwrap();
+}
+
+#[test]
+fn post_raw_invalid_content_type() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    match client.post_raw::<_, HttpBinPost>((), "data", "text/csv\n") {
+        Err(Error::InvalidValue) => (),
+        _ => panic!("Expected invalid value error"),
+    };
+}
+
+#[test]
+fn post_form() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    let data = HttpBinPost {
+        data: String::from("test data"),
+    };
+    client.post_form((), &data).unwrap();
+}
+
+#[test]
+fn post_form_capture() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    let data = HttpBinPost {
+        data: String::from("test data"),
+    };
+    let resp = client.post_form_capture::<_, _, HttpBinPostFormResp>((), &data).unwrap();
+
+    assert_eq!(resp.form.data, "test data");
+}
+
+#[test]
+fn post_multipart() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    let path = std::env::temp_dir().join("restson_post_multipart.txt");
+    std::fs::write(&path, "file contents").unwrap();
+
+    let form = Multipart::new()
+        .text("field", "value")
+        .file("upload", &path)
+        .bytes("inline", "inline.bin", "application/octet-stream", "inline contents");
+    let resp = client.post_multipart_capture::<_, HttpBinPost, HttpBinPostMultipartResp>((), form).unwrap();
+
+    assert_eq!(resp.form["field"], "value");
+    assert_eq!(resp.files["upload"], "file contents");
+    assert_eq!(resp.files["inline"], "inline contents");
+}
+
+#[test]
+fn post_multipart_missing_file() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    let form = Multipart::new().file("upload", "/nonexistent/restson/file");
+    match client.post_multipart::<_, HttpBinPost>((), form) {
+        Err(Error::IoError(_)) => (),
+        _ => panic!("Expected IO error"),
+    };
+}
+
+#[test]
+fn post_status() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    let resp = client.post(201, &HttpBinStatus {}).unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let resp = client.post(202, &HttpBinStatus {}).unwrap();
+    assert_eq!(resp.status(), StatusCode::ACCEPTED);
+}
+
+#[test]
+fn post_as_curl() {
+    let client = RestClient::new_blocking("http://httpbin.org").unwrap();
+
+    let data = HttpBinPost {
+        data: String::from("it's"),
+    };
+    let cmd = client.as_curl(hyper::Method::POST, (), Some(&data)).unwrap();
+    assert!(cmd.starts_with("curl -X POST 'http://httpbin.org/post'"));
+    assert!(cmd.contains(" -H 'content-type: application/json'"));
+    assert!(cmd.ends_with(r#" --data-raw '{"data":"it'\''s"}'"#));
+}
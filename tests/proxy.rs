@@ -0,0 +1,127 @@
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+use std::io::{self, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+mod common;
+
+#[derive(Deserialize)]
+struct HttpBinAnything {
+    url: String,
+}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+/// Start proxy that forwards the requests to the servers and return its URL
+/// and receiver for the requests received by the proxy
+fn proxy() -> (String, Receiver<common::Request>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut client = stream.unwrap();
+            let mut reader = BufReader::new(client.try_clone().unwrap());
+            let req = common::read_request(&mut reader).unwrap();
+            let mut server = match req.target.strip_prefix("http://") {
+                Some(url) => {
+                    // Forward in origin form
+                    let (host, path) = url.split_at(url.find('/').unwrap());
+                    let mut head = format!("{} {} HTTP/1.1\r\n", req.method, path);
+                    for (name, value) in &req.headers {
+                        head.push_str(&format!("{}: {}\r\n", name, value));
+                    }
+                    head.push_str("\r\n");
+                    let mut server = TcpStream::connect(format!("{}:80", host)).unwrap();
+                    server.write_all(head.as_bytes()).unwrap();
+                    server.write_all(&req.body).unwrap();
+                    server
+                }
+                None => {
+                    let server = TcpStream::connect(&req.target).unwrap();
+                    client.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n").unwrap();
+                    server
+                }
+            };
+            let _ = tx.send(req);
+            let mut upstream = server.try_clone().unwrap();
+            thread::spawn(move || io::copy(&mut reader, &mut upstream));
+            thread::spawn(move || io::copy(&mut server, &mut client));
+        }
+    });
+    (url, rx)
+}
+
+#[test]
+fn http_proxy() {
+    let (url, rx) = proxy();
+    let client = RestClient::builder().proxy(&url).blocking("http://httpbin.org").unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.url, "http://httpbin.org/anything");
+    assert_eq!(rx.recv().unwrap().line(), "GET http://httpbin.org/anything");
+}
+
+#[test]
+fn https_proxy() {
+    let (url, rx) = proxy();
+    let client = RestClient::builder().proxy(&url).blocking("https://httpbin.org").unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+    assert_eq!(rx.recv().unwrap().line(), "CONNECT httpbin.org:443");
+}
+
+#[test]
+fn invalid_proxy() {
+    assert!(RestClient::builder().proxy("ftp://proxy").blocking("http://httpbin.org").is_err());
+}
+
+#[test]
+fn proxy_from_env() {
+    let (url, rx) = proxy();
+    std::env::set_var("HTTP_PROXY", &url);
+    std::env::set_var("NO_PROXY", "localhost, .example.com");
+
+    let client = RestClient::builder().proxy_from_env().blocking("http://httpbin.org").unwrap();
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(rx.recv().unwrap().line(), "GET http://httpbin.org/anything");
+
+    // Host in no_proxy is connected directly
+    std::env::set_var("NO_PROXY", "example.com,*.httpbin.org");
+    let client = RestClient::builder().proxy_from_env().blocking("http://httpbin.org").unwrap();
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    assert!(rx.try_recv().is_err());
+
+    std::env::remove_var("HTTP_PROXY");
+    std::env::remove_var("NO_PROXY");
+}
+
+#[test]
+fn proxy_auth() {
+    let (url, rx) = proxy();
+    let url = url.replace("http://", "http://user:p%40ss@");
+
+    // Header is sent with plain HTTP requests and CONNECT requests
+    let client = RestClient::builder().proxy(&url).blocking("http://httpbin.org").unwrap();
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(rx.recv().unwrap().header("proxy-authorization"), Some("Basic dXNlcjpwQHNz"));
+
+    let client = RestClient::builder().proxy(&url).blocking("https://httpbin.org").unwrap();
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(rx.recv().unwrap().header("proxy-authorization"), Some("Basic dXNlcjpwQHNz"));
+
+    let client = RestClient::builder()
+        .proxy(&url)
+        .proxy_auth("admin", "secret")
+        .blocking("https://httpbin.org")
+        .unwrap();
+    client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(rx.recv().unwrap().header("proxy-authorization"), Some("Basic YWRtaW46c2VjcmV0"));
+}
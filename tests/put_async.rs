@@ -1,5 +1,6 @@
 use restson::{Error, RestClient, RestPath};
 use serde_derive::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
 
 #[derive(Serialize, Deserialize)]
 struct HttpBinPut {
@@ -52,6 +53,19 @@ async fn put_capture() {
     assert_eq!(resp.url, "https://httpbin.org/put");
 }
 
+#[tokio::test]
+async fn put_capture_long_string() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    // String fields longer than 16 KB are sent intact
+    let data = HttpBinPut {
+        data: "0123456789abcdef€".repeat(4096),
+    };
+    let resp = client.put_capture::<_, _, HttpBinPutResp>((), &data).await.unwrap();
+
+    assert_eq!(resp.json.data, data.data);
+}
+
 #[tokio::test]
 async fn put_capture_query_params() {
     let client = RestClient::new("https://httpbin.org").unwrap();
@@ -65,3 +79,58 @@ async fn put_capture_query_params() {
     assert_eq!(resp.json.data, "test data");
     assert_eq!(resp.url, "https://httpbin.org/put?a=2&b=abcd");
 }
+
+#[tokio::test]
+async fn put_raw() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    client
+        .put_raw::<_, HttpBinPut>((), "a,b\n1,2\n", "text/csv")
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn put_stream() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let data = vec![0u8; 64 * 1024];
+    let len = data.len() as u64;
+    client
+        .put_stream::<_, HttpBinPut, _>((), std::io::Cursor::new(data), Some(len))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn put_stream_chunked() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let data = vec![0u8; 64 * 1024];
+    client
+        .put_stream::<_, HttpBinPut, _>((), std::io::Cursor::new(data), None)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn put_stream_with_progress() {
+    let client = RestClient::new("https://httpbin.org").unwrap();
+
+    let data = vec![0u8; 256 * 1024];
+    let len = data.len() as u64;
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let progress = {
+        let reported = reported.clone();
+        move |sent, total| reported.lock().unwrap().push((sent, total))
+    };
+    client
+        .put_stream_with_progress::<_, HttpBinPut, _, _>((), std::io::Cursor::new(data), Some(len), progress)
+        .await
+        .unwrap();
+
+    let reported = reported.lock().unwrap();
+    assert!(reported.len() > 1);
+    assert_eq!(reported.last(), Some(&(len, Some(len))));
+}
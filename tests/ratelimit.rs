@@ -0,0 +1,46 @@
+use restson::{Error, RestClient, RestPath};
+use serde_derive::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+#[derive(Serialize, Deserialize)]
+struct HttpBinAnything {}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+#[test]
+fn rate_limit() {
+    let client = RestClient::builder().rate_limit(5.0, 2).blocking("http://httpbin.org").unwrap();
+
+    let start = Instant::now();
+    for _ in 0..4 {
+        client.get::<_, HttpBinAnything>(()).unwrap();
+    }
+    // Two requests are sent immediately, the rest at 200 ms intervals
+    assert!(start.elapsed() >= Duration::from_millis(400));
+}
+
+#[test]
+fn rate_limit_timeout() {
+    let client = RestClient::builder()
+        .rate_limit(5.0, 1)
+        .timeout(Duration::from_millis(150))
+        .blocking("http://httpbin.org")
+        .unwrap();
+
+    // Waiting for the turn does not count towards the timeout
+    for _ in 0..3 {
+        client.get::<_, HttpBinAnything>(()).unwrap();
+    }
+}
+
+#[test]
+fn rate_limit_invalid() {
+    for rate in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let res = RestClient::builder().rate_limit(rate, 1).blocking("http://httpbin.org");
+        assert!(matches!(res, Err(Error::InvalidValue)));
+    }
+}
@@ -0,0 +1,75 @@
+use restson::{Error, RestClient, RestPath};
+use serde_derive::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+#[derive(Serialize, Deserialize)]
+struct HttpBinAnything {}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+#[tokio::test]
+async fn rate_limit() {
+    let client = RestClient::builder().rate_limit(5.0, 2).build("http://httpbin.org").unwrap();
+
+    let start = Instant::now();
+    for _ in 0..4 {
+        client.get::<_, HttpBinAnything>(()).await.unwrap();
+    }
+    // Two requests are sent immediately, the rest at 200 ms intervals
+    assert!(start.elapsed() >= Duration::from_millis(400));
+}
+
+#[tokio::test]
+async fn rate_limit_shared_by_clones() {
+    let client = RestClient::builder().rate_limit(5.0, 1).build("http://httpbin.org").unwrap();
+    let other = client.clone();
+
+    let start = Instant::now();
+    let (a, b) = tokio::join!(
+        client.get::<_, HttpBinAnything>(()),
+        other.get::<_, HttpBinAnything>(())
+    );
+    a.unwrap();
+    b.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(200));
+}
+
+#[tokio::test]
+async fn rate_limit_timeout() {
+    let client = RestClient::builder()
+        .rate_limit(5.0, 1)
+        .timeout(Duration::from_millis(150))
+        .build("http://httpbin.org")
+        .unwrap();
+
+    // Waiting for the turn does not count towards the timeout
+    for _ in 0..3 {
+        client.get::<_, HttpBinAnything>(()).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn rate_limit_cancelled() {
+    let client = RestClient::builder().rate_limit(2.0, 1).build("http://httpbin.org").unwrap();
+    client.get::<_, HttpBinAnything>(()).await.unwrap();
+
+    // Request cancelled while waiting gives its turn back
+    let cancelled = tokio::time::timeout(Duration::from_millis(50), client.get::<_, HttpBinAnything>(()));
+    assert!(cancelled.await.is_err());
+
+    let start = Instant::now();
+    client.get::<_, HttpBinAnything>(()).await.unwrap();
+    assert!(start.elapsed() < Duration::from_millis(700));
+}
+
+#[tokio::test]
+async fn rate_limit_invalid() {
+    for rate in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let res = RestClient::builder().rate_limit(rate, 1).build("http://httpbin.org");
+        assert!(matches!(res, Err(Error::InvalidValue)));
+    }
+}
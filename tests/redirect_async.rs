@@ -0,0 +1,115 @@
+use hyper::StatusCode;
+use restson::{Error, RedirectPolicy, RestClient, RestPath};
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+struct HttpBinAnything {
+    method: String,
+    url: String,
+    headers: HashMap<String, String>,
+}
+
+#[derive(Serialize)]
+struct Data {
+    value: u32,
+}
+
+impl RestPath<u32> for HttpBinAnything {
+    fn get_path(n: u32) -> Result<String, Error> {
+        Ok(format!("redirect/{}", n))
+    }
+}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("redirect-to"))
+    }
+}
+
+impl RestPath<()> for Data {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("redirect-to"))
+    }
+}
+
+#[tokio::test]
+async fn follow_redirects() {
+    let client = RestClient::builder().redirect(RedirectPolicy::default()).build("http://httpbin.org").unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(3).await.unwrap();
+    assert_eq!(data.url, "http://httpbin.org/get");
+}
+
+#[tokio::test]
+async fn no_redirects_by_default() {
+    let client = RestClient::new("http://httpbin.org").unwrap();
+
+    match client.get::<_, HttpBinAnything>(1).await {
+        Err(Error::HttpError(302, ..)) => (),
+        _ => panic!("Expected 302 status"),
+    };
+}
+
+#[tokio::test]
+async fn too_many_redirects() {
+    let client = RestClient::builder().redirect(RedirectPolicy::new(2)).build("http://httpbin.org").unwrap();
+
+    match client.get::<_, HttpBinAnything>(3).await {
+        Err(Error::TooManyRedirects) => (),
+        _ => panic!("Expected too many redirects error"),
+    };
+}
+
+#[tokio::test]
+async fn redirect_method() {
+    let client = RestClient::builder().redirect(RedirectPolicy::default()).build("http://httpbin.org").unwrap();
+
+    // POST is changed to GET with 302
+    let query = [("url", "/anything"), ("status_code", "302")];
+    let data = client.post_capture_with::<_, _, HttpBinAnything>((), &Data { value: 1 }, &query).await.unwrap();
+    assert_eq!(data.method, "GET");
+
+    // Method and body are kept with 307
+    let query = [("url", "/anything"), ("status_code", "307")];
+    let data = client.post_capture_with::<_, _, HttpBinAnything>((), &Data { value: 1 }, &query).await.unwrap();
+    assert_eq!(data.method, "POST");
+}
+
+#[tokio::test]
+async fn redirect_cross_host() {
+    let mut client = RestClient::builder().redirect(RedirectPolicy::default()).build("http://httpbin.org").unwrap();
+    client.set_auth_bearer("secret");
+    client.set_header("X-Api-Key", "key").unwrap();
+
+    // Authorization is removed when redirected to another host
+    let query = [("url", "http://127.0.0.1/anything")];
+    let data = client.get_with::<_, HttpBinAnything>((), &query).await.unwrap();
+    assert_eq!(data.url, "http://127.0.0.1/anything");
+    assert!(!data.headers.contains_key("Authorization"));
+    assert!(!data.headers.contains_key("X-Api-Key"));
+
+    let policy = RedirectPolicy::default().allow_cross_origin(false);
+    let client = RestClient::builder().redirect(policy).build("http://httpbin.org").unwrap();
+    match client.get_with::<_, HttpBinAnything>((), &query).await {
+        Err(Error::HttpError(302, ..)) => (),
+        _ => panic!("Expected 302 status"),
+    };
+}
+
+#[tokio::test]
+async fn redirect_history() {
+    let client = RestClient::builder().redirect(RedirectPolicy::default()).build("http://httpbin.org").unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(2).await.unwrap();
+    let redirects = data.redirects();
+    assert_eq!(redirects.len(), 2);
+    assert_eq!(redirects[0].url, "http://httpbin.org/redirect/2");
+    assert_eq!(redirects[0].status, StatusCode::FOUND);
+    assert_eq!(redirects[0].location, "http://httpbin.org/redirect/1");
+    assert_eq!(redirects[1].url, "http://httpbin.org/redirect/1");
+    assert_eq!(redirects[1].location, "http://httpbin.org/get");
+
+    let data = client.get_with::<_, HttpBinAnything>((), &[("url", "/anything")]).await.unwrap();
+    assert_eq!(data.redirects().len(), 1);
+}
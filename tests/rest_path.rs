@@ -0,0 +1,59 @@
+use restson::{rest_path, Error, PathSegments, RestClient, RestPath};
+use serde_derive::Deserialize;
+
+#[derive(Deserialize)]
+struct HttpBinAnything {
+    url: String,
+}
+
+rest_path!(
+    HttpBinAnything,
+    () => "anything",
+    (id: u32) => "anything/{id}",
+    (id: u32, name: &str) => "anything/{id}/sensors/{name}",
+);
+
+#[derive(Deserialize)]
+struct HttpBinSegments {
+    url: String,
+}
+
+impl RestPath<(u32, &str)> for HttpBinSegments {
+    fn get_path((id, name): (u32, &str)) -> Result<String, Error> {
+        Ok(PathSegments::new().push("anything")?.push(id)?.push(name)?.into())
+    }
+}
+
+#[test]
+fn rest_path_macro() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+
+    let data = client.get::<_, HttpBinAnything>(1234).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything/1234");
+
+    let data = client.get::<_, HttpBinAnything>((1234, "a b/c")).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything/1234/sensors/a%20b%2Fc");
+}
+
+#[test]
+fn path_segments() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    let data = client.get::<_, HttpBinSegments>((1, "a b/c?d#e")).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything/1/a%20b%2Fc%3Fd%23e");
+}
+
+#[test]
+fn rest_path_dot_segments() {
+    let client = RestClient::new_blocking("https://httpbin.org").unwrap();
+
+    assert!(matches!(client.get::<_, HttpBinAnything>((1234, "..")), Err(Error::InvalidValue)));
+    assert!(matches!(client.get::<_, HttpBinSegments>((1, ".")), Err(Error::InvalidValue)));
+    assert!(PathSegments::new().push("devices").unwrap().push("..").is_err());
+
+    let data = client.get::<_, HttpBinAnything>((1234, "...")).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything/1234/sensors/...");
+}
@@ -0,0 +1,171 @@
+use restson::{Error, RestClient, RestPath, RetryPolicy};
+use serde_derive::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+mod common;
+
+#[derive(Serialize, Deserialize)]
+struct HttpBinStatus {}
+
+impl RestPath<u16> for HttpBinStatus {
+    fn get_path(code: u16) -> Result<String, Error> {
+        Ok(format!("status/{}", code))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct HttpBinDelay {}
+
+impl RestPath<u16> for HttpBinDelay {
+    fn get_path(delay: u16) -> Result<String, Error> {
+        Ok(format!("delay/{}", delay))
+    }
+}
+
+/// `Idempotency-Key` header of the next request received by the server
+fn idempotency_key(server: &common::Server) -> String {
+    server.requests.recv().unwrap().header("idempotency-key").unwrap().to_owned()
+}
+
+fn policy() -> RetryPolicy {
+    RetryPolicy::new(3).initial_backoff(Duration::from_millis(200))
+}
+
+#[test]
+fn retry_server_error() {
+    let client = RestClient::builder().retry(policy()).blocking("http://httpbin.org").unwrap();
+
+    let start = Instant::now();
+    match client.get::<_, HttpBinStatus>(503) {
+        Err(Error::HttpError(503, ..)) => (),
+        _ => panic!("Expected 503 error status"),
+    };
+    // Backoff of 200 ms and 400 ms between the three attempts
+    assert!(start.elapsed() >= Duration::from_millis(600));
+}
+
+#[test]
+fn retry_connect_error() {
+    let client = RestClient::builder().retry(policy()).blocking("http://127.0.0.1:1").unwrap();
+
+    let start = Instant::now();
+    match client.get::<_, HttpBinStatus>(200) {
+        Err(Error::ConnectError(_)) => (),
+        _ => panic!("Expected connect error"),
+    };
+    assert!(start.elapsed() >= Duration::from_millis(600));
+}
+
+#[test]
+fn no_retry_client_error() {
+    let client = RestClient::builder().retry(policy()).blocking("http://httpbin.org").unwrap();
+
+    let start = Instant::now();
+    match client.get::<_, HttpBinStatus>(404) {
+        Err(Error::HttpError(404, ..)) => (),
+        _ => panic!("Expected 404 error status"),
+    };
+    assert!(start.elapsed() < Duration::from_millis(200));
+}
+
+#[test]
+fn no_retry_post() {
+    let client = RestClient::builder().retry(policy()).blocking("http://httpbin.org").unwrap();
+
+    let start = Instant::now();
+    match client.post_capture::<_, _, HttpBinStatus>(503, &HttpBinStatus {}) {
+        Err(Error::HttpError(503, ..)) => (),
+        _ => panic!("Expected 503 error status"),
+    };
+    assert!(start.elapsed() < Duration::from_millis(200));
+}
+
+#[test]
+fn retry_after() {
+    let server = common::serve_responses(&[
+        "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 1\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}",
+    ]);
+    let client = RestClient::builder().retry(policy()).blocking(&server.url).unwrap();
+
+    let start = Instant::now();
+    client.get::<_, HttpBinStatus>(200).unwrap();
+    assert!(start.elapsed() >= Duration::from_secs(1));
+}
+
+#[test]
+fn retry_after_too_long() {
+    let server = common::serve_responses(&[
+        "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 3600\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+    ]);
+    let client = RestClient::builder().retry(policy()).blocking(&server.url).unwrap();
+
+    match client.get::<_, HttpBinStatus>(200) {
+        Err(Error::HttpError(503, ..)) => (),
+        _ => panic!("Expected 503 error status"),
+    };
+}
+
+#[test]
+fn retry_post_with_idempotency_key() {
+    let server = common::serve_responses(&[
+        "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}",
+    ]);
+    let client = RestClient::builder().retry(policy()).idempotency_key(true).blocking(&server.url).unwrap();
+
+    client.post_capture::<_, _, HttpBinStatus>(200, &HttpBinStatus {}).unwrap();
+
+    // Both attempts have the same key
+    let first = idempotency_key(&server);
+    let second = idempotency_key(&server);
+    assert_eq!(first, second);
+
+    // Next request has a new key
+    let server = common::serve_responses(&["HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}"]);
+    let client = RestClient::builder().idempotency_key(true).blocking(&server.url).unwrap();
+    client.post_capture::<_, _, HttpBinStatus>(200, &HttpBinStatus {}).unwrap();
+    assert_ne!(idempotency_key(&server), first);
+}
+
+#[test]
+fn deadline_retries() {
+    let client = RestClient::builder()
+        .retry(RetryPolicy::new(5).initial_backoff(Duration::from_millis(200)))
+        .deadline(Duration::from_secs(1))
+        .blocking("http://httpbin.org")
+        .unwrap();
+
+    // Third attempt starts after 600 ms, and the retry after it would start
+    // after the deadline
+    let start = Instant::now();
+    match client.get::<_, HttpBinStatus>(503) {
+        Err(Error::DeadlineExceeded(info)) => {
+            assert_eq!(info.attempts, 3);
+            assert_eq!(info.last_status, Some(503));
+            assert!(info.last_error.is_none());
+        }
+        _ => panic!("Expected deadline error"),
+    };
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn deadline_slow_attempt() {
+    let client = RestClient::builder()
+        .retry(policy())
+        .deadline(Duration::from_millis(500))
+        .blocking("http://httpbin.org")
+        .unwrap();
+
+    let start = Instant::now();
+    match client.get::<_, HttpBinDelay>(3) {
+        Err(Error::DeadlineExceeded(info)) => {
+            assert_eq!(info.attempts, 1);
+            assert!(info.last_status.is_none());
+            assert!(info.elapsed >= Duration::from_millis(500));
+        }
+        _ => panic!("Expected deadline error"),
+    };
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
@@ -0,0 +1,115 @@
+use restson::{Error, Login, RedirectPolicy, RestClient, RestPath, Session};
+use serde_derive::{Deserialize, Serialize};
+
+mod common;
+
+#[derive(Serialize, Deserialize)]
+struct Data {}
+
+impl RestPath<()> for Data {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("data"))
+    }
+}
+
+#[derive(Serialize)]
+struct Credentials {
+    user: &'static str,
+    password: &'static str,
+}
+
+const CREDENTIALS: Credentials = Credentials { user: "user", password: "secret" };
+
+/// Request line and credential of the request, e.g. `GET /data Bearer t1`
+fn credential(req: common::Request) -> String {
+    match req.header("authorization").or(req.header("cookie")) {
+        Some(credential) => format!("{} {}", req.line(), credential),
+        None => req.line(),
+    }
+}
+
+const OK: &str = "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}";
+const UNAUTHORIZED: &str = "HTTP/1.1 401 Unauthorized\r\nContent-Length: 0\r\nConnection: close\r\n\r\n";
+
+#[test]
+fn session_token() {
+    let server = common::serve_responses(&[
+        "HTTP/1.1 200 OK\r\nContent-Length: 14\r\nConnection: close\r\n\r\n{\"token\":\"t1\"}",
+        OK,
+        UNAUTHORIZED,
+        "HTTP/1.1 200 OK\r\nContent-Length: 14\r\nConnection: close\r\n\r\n{\"token\":\"t2\"}",
+        OK,
+    ]);
+    let login = Login::new("login", &CREDENTIALS).unwrap().token_field("/token");
+    let client = RestClient::builder().with_middleware(Session::new(login)).blocking(&server.url).unwrap();
+
+    client.get::<_, Data>(()).unwrap();
+    // Token has expired and the session logs in again
+    client.get::<_, Data>(()).unwrap();
+
+    let requests: Vec<_> = server.requests.try_iter().map(credential).collect();
+    assert_eq!(
+        requests,
+        [
+            "POST /login",
+            "GET /data Bearer t1",
+            "GET /data Bearer t1",
+            "POST /login",
+            "GET /data Bearer t2",
+        ]
+    );
+}
+
+#[test]
+fn session_cookie() {
+    let server = common::serve_responses(&[
+        "HTTP/1.1 204 No Content\r\nSet-Cookie: session=abc; Path=/; HttpOnly\r\nSet-Cookie: lang=fi\r\nConnection: close\r\n\r\n",
+        OK,
+    ]);
+    let login = Login::new("login", &CREDENTIALS).unwrap();
+    let client = RestClient::builder().with_middleware(Session::new(login)).blocking(&server.url).unwrap();
+
+    client.get::<_, Data>(()).unwrap();
+
+    let requests: Vec<_> = server.requests.try_iter().map(credential).collect();
+    assert_eq!(requests, ["POST /login", "GET /data session=abc; lang=fi"]);
+}
+
+#[test]
+fn session_login_failed() {
+    let server = common::serve_responses(&[UNAUTHORIZED]);
+    let login = Login::new("login", &CREDENTIALS).unwrap();
+    let client = RestClient::builder().with_middleware(Session::new(login)).blocking(&server.url).unwrap();
+
+    match client.get::<_, Data>(()) {
+        Err(Error::HttpError(401, _, req)) => assert!(req.url.ends_with("/login")),
+        _ => panic!("Expected 401 error status"),
+    };
+}
+
+#[test]
+fn session_cross_origin_redirect() {
+    let other = common::serve_responses(&[OK]);
+    let redirect = format!(
+        "HTTP/1.1 302 Found\r\nLocation: {}/data\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        other.url
+    );
+    let server = common::serve(move |req| match req.target.as_str() {
+        "/login" => String::from("HTTP/1.1 200 OK\r\nContent-Length: 14\r\nConnection: close\r\n\r\n{\"token\":\"t1\"}"),
+        _ => redirect.clone(),
+    });
+    let login = Login::new("login", &CREDENTIALS).unwrap().token_field("/token");
+    let client = RestClient::builder()
+        .with_middleware(Session::new(login))
+        .redirect(RedirectPolicy::default())
+        .blocking(&server.url)
+        .unwrap();
+
+    client.get::<_, Data>(()).unwrap();
+
+    // Redirect to another port is another origin and the token is not sent
+    let requests: Vec<_> = server.requests.try_iter().map(credential).collect();
+    assert_eq!(requests, ["POST /login", "GET /data Bearer t1"]);
+    let requests: Vec<_> = other.requests.try_iter().map(credential).collect();
+    assert_eq!(requests, ["GET /data"]);
+}
@@ -0,0 +1,70 @@
+use futures::{StreamExt, TryStreamExt};
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+
+mod common;
+
+#[derive(Debug, Deserialize)]
+struct Notification {
+    message: String,
+}
+
+impl RestPath<()> for Notification {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("notifications"))
+    }
+}
+
+/// Serve the responses, one per connection, and 204 status after them
+fn serve(responses: Vec<&'static str>) -> common::Server {
+    let mut responses = responses.into_iter();
+    common::serve(move |_| responses.next().unwrap_or("HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n"))
+}
+
+#[tokio::test]
+async fn sse_events() {
+    let server = serve(vec![
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n\
+         retry: 10\n: comment\n\
+         data: {\"message\": \"first\"}\n\n\
+         event: update\r\nid: 7\r\ndata: {\r\ndata: \"message\": \"second\"}\r\n\r\n",
+    ]);
+    let client = RestClient::new(&server.url).unwrap();
+
+    let events: Vec<_> = client.sse::<_, Notification>(()).unwrap().try_collect().await.unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].event, "message");
+    assert_eq!(events[0].id, None);
+    assert_eq!(events[0].data.message, "first");
+    assert_eq!(events[1].event, "update");
+    assert_eq!(events[1].id.as_deref(), Some("7"));
+    assert_eq!(events[1].data.message, "second");
+    assert_eq!(server.requests.try_iter().count(), 2);
+}
+
+#[tokio::test]
+async fn sse_reconnect() {
+    let server = serve(vec![
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n\
+         retry: 10\nid: 1\ndata: {\"message\": \"first\"}\n\n",
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n\
+         id: 2\ndata: {\"message\": \"second\"}\n\n",
+    ]);
+    let client = RestClient::new(&server.url).unwrap();
+
+    let events: Vec<_> = client.sse::<_, Notification>(()).unwrap().map(|event| event.unwrap().data.message).collect().await;
+    assert_eq!(events, ["first", "second"]);
+
+    let last_ids: Vec<_> = server.requests.try_iter().map(|req| req.header("last-event-id").map(str::to_owned)).collect();
+    assert_eq!(last_ids, [None, Some(String::from("1")), Some(String::from("2"))]);
+}
+
+#[tokio::test]
+async fn sse_error_status() {
+    let server = serve(vec!["HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"]);
+    let client = RestClient::new(&server.url).unwrap();
+
+    let mut events = client.sse::<_, Notification>(()).unwrap();
+    assert!(matches!(events.next().await, Some(Err(Error::HttpError(503, ..)))));
+    assert!(events.next().await.is_none());
+}
@@ -0,0 +1,90 @@
+use hyper::Method;
+use restson::mock::MockClient;
+use restson::{Error, RestClient, RestPath, TemplateParams, TemplateValue, UriTemplate};
+use serde_derive::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Issue {
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Issues(Vec<Issue>);
+
+impl RestPath<&TemplateParams> for Issues {
+    fn get_path(params: &TemplateParams) -> Result<String, Error> {
+        Ok(UriTemplate::new("repos/{owner}/{repo}/issues{?state,labels}")?.expand(params))
+    }
+}
+
+#[test]
+fn uri_template_get() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/repos/spietika/restson%20rust/issues?state=open&labels=bug,help%20wanted")
+        .respond(200, r#"[{"title": "Issue"}]"#);
+    let client = RestClient::builder().transport(mock.clone()).blocking("http://api.test").unwrap();
+
+    let params = TemplateParams::new()
+        .set("owner", "spietika")
+        .set("repo", "restson rust")
+        .set("state", Some("open"))
+        .set("labels", vec!["bug", "help wanted"]);
+    let data = client.get::<_, Issues>(&params).unwrap();
+    assert_eq!(data.0[0].title, "Issue");
+    mock.verify();
+}
+
+#[test]
+fn uri_template_expansion() {
+    let params = TemplateParams::new()
+        .set("var", "value")
+        .set("hello", "Hello World!")
+        .set("half", "50%")
+        .set("empty", "")
+        .set("undef", None::<&str>)
+        .set("path", "/foo/bar")
+        .set("x", 1024)
+        .set("y", 768)
+        .set("list", vec!["red", "green", "blue"])
+        .set("keys", TemplateValue::Map(vec![
+            ("semi".to_string(), ";".to_string()),
+            ("dot".to_string(), ".".to_string()),
+            ("comma".to_string(), ",".to_string()),
+        ]));
+
+    // Examples of RFC 6570 section 3.2
+    let examples = [
+        ("{var}", "value"),
+        ("{hello}", "Hello%20World%21"),
+        ("{half}", "50%25"),
+        ("O{empty}X", "OX"),
+        ("O{undef}X", "OX"),
+        ("{x,hello,y}", "1024,Hello%20World%21,768"),
+        ("?{x,undef}", "?1024"),
+        ("{var:3}", "val"),
+        ("{list}", "red,green,blue"),
+        ("{keys}", "semi,%3B,dot,.,comma,%2C"),
+        ("{keys*}", "semi=%3B,dot=.,comma=%2C"),
+        ("{+path:6}/here", "/foo/b/here"),
+        ("{+keys}", "semi,;,dot,.,comma,,"),
+        ("{#keys*}", "#semi=;,dot=.,comma=,"),
+        ("X{.list*}", "X.red.green.blue"),
+        ("{/list*,path:4}", "/red/green/blue/%2Ffoo"),
+        ("{;x,y,empty}", ";x=1024;y=768;empty"),
+        ("{;list*}", ";list=red;list=green;list=blue"),
+        ("{?x,y,empty}", "?x=1024&y=768&empty="),
+        ("{?list}", "?list=red,green,blue"),
+        ("{?keys*}", "?semi=%3B&dot=.&comma=%2C"),
+        ("?fixed=yes{&x}", "?fixed=yes&x=1024"),
+    ];
+    for (template, expected) in examples {
+        assert_eq!(UriTemplate::new(template).unwrap().expand(&params), expected, "{}", template);
+    }
+
+    let template = UriTemplate::new("repos/{owner}/{repo}/issues{?state,labels}").unwrap();
+    assert_eq!(template.variables().collect::<Vec<_>>(), ["owner", "repo", "state", "labels"]);
+
+    for invalid in ["{var", "var}", "{}", "{var:0}", "{a b}"] {
+        assert!(matches!(UriTemplate::new(invalid), Err(Error::InvalidValue)), "{}", invalid);
+    }
+}
@@ -0,0 +1,35 @@
+use hyper::Method;
+use restson::mock::MockClient;
+use restson::{Error, RestClient, RestPath, TemplateParams, UriTemplate};
+use serde_derive::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Issue {
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Issues(Vec<Issue>);
+
+impl RestPath<&TemplateParams> for Issues {
+    fn get_path(params: &TemplateParams) -> Result<String, Error> {
+        Ok(UriTemplate::new("repos/{owner}/{repo}/issues{?state,labels}")?.expand(params))
+    }
+}
+
+#[tokio::test]
+async fn uri_template_get() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/repos/spietika/restson%20rust/issues?state=open&labels=bug,help%20wanted")
+        .respond(200, r#"[{"title": "Issue"}]"#);
+    let client = RestClient::builder().transport(mock.clone()).build("http://api.test").unwrap();
+
+    let params = TemplateParams::new()
+        .set("owner", "spietika")
+        .set("repo", "restson rust")
+        .set("state", Some("open"))
+        .set("labels", vec!["bug", "help wanted"]);
+    let data = client.get::<_, Issues>(&params).await.unwrap();
+    assert_eq!(data.0[0].title, "Issue");
+    mock.verify();
+}
@@ -0,0 +1,124 @@
+use restson::{Error, Identity, RedirectPolicy, RestClient, RestPath, TlsVersion};
+use serde_derive::Deserialize;
+
+static CERT: &[u8] = include_bytes!("data/client.pem");
+static KEY: &[u8] = include_bytes!("data/client.key");
+
+#[derive(Deserialize)]
+struct HttpBinAnything {
+    url: String,
+}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+impl RestPath<&str> for HttpBinAnything {
+    fn get_path(path: &str) -> Result<String, Error> {
+        Ok(String::from(path))
+    }
+}
+
+#[test]
+fn identity_pem() {
+    let client = RestClient::builder()
+        .identity(Identity::from_pem(CERT, KEY))
+        .blocking("https://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+}
+
+#[cfg(feature = "native-tls")]
+#[test]
+fn identity_pkcs12() {
+    let der = include_bytes!("data/client.p12");
+    let client = RestClient::builder()
+        .identity(Identity::from_pkcs12(der, "secret"))
+        .blocking("https://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+}
+
+#[test]
+fn invalid_identity() {
+    let result = RestClient::builder()
+        .identity(Identity::from_pem(CERT, b"invalid"))
+        .blocking("https://httpbin.org");
+
+    match result {
+        Err(Error::HttpClientError) => (),
+        _ => panic!("Expected HttpClientError"),
+    };
+}
+
+#[test]
+fn tls_version() {
+    let client = RestClient::builder()
+        .min_tls_version(TlsVersion::Tls1_3)
+        .blocking("https://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+}
+
+#[test]
+fn invalid_tls_version() {
+    let result = RestClient::builder()
+        .min_tls_version(TlsVersion::Tls1_3)
+        .max_tls_version(TlsVersion::Tls1_2)
+        .blocking("https://httpbin.org");
+
+    match result {
+        Err(Error::HttpClientError) => (),
+        _ => panic!("Expected HttpClientError"),
+    };
+}
+
+#[cfg(feature = "rustls")]
+#[test]
+fn invalid_cipher_suite() {
+    let result = RestClient::builder()
+        .tls_cipher_suites(&["TLS13_AES_256_GCM_SHA384", "TLS_RSA_WITH_RC4_128_MD5"])
+        .blocking("https://httpbin.org");
+
+    match result {
+        Err(Error::HttpClientError) => (),
+        _ => panic!("Expected HttpClientError"),
+    };
+}
+
+#[test]
+fn https_only() {
+    match RestClient::builder().https_only(true).blocking("http://httpbin.org") {
+        Err(Error::InsecureUrl) => (),
+        _ => panic!("Expected InsecureUrl"),
+    };
+
+    let client = RestClient::builder()
+        .https_only(true)
+        .redirect(RedirectPolicy::default())
+        .blocking("https://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+
+    match client.get::<_, HttpBinAnything>("http://httpbin.org/anything") {
+        Err(Error::InsecureUrl) => (),
+        _ => panic!("Expected InsecureUrl"),
+    };
+
+    // Redirect from HTTPS to plain HTTP is refused
+    let query = [("url", "http://httpbin.org/anything")];
+    match client.get_with::<_, HttpBinAnything>("redirect-to", &query) {
+        Err(Error::InsecureUrl) => (),
+        _ => panic!("Expected InsecureUrl"),
+    };
+}
@@ -0,0 +1,124 @@
+use restson::{Error, Identity, RedirectPolicy, RestClient, RestPath, TlsVersion};
+use serde_derive::Deserialize;
+
+static CERT: &[u8] = include_bytes!("data/client.pem");
+static KEY: &[u8] = include_bytes!("data/client.key");
+
+#[derive(Deserialize)]
+struct HttpBinAnything {
+    url: String,
+}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+impl RestPath<&str> for HttpBinAnything {
+    fn get_path(path: &str) -> Result<String, Error> {
+        Ok(String::from(path))
+    }
+}
+
+#[tokio::test]
+async fn identity_pem() {
+    let client = RestClient::builder()
+        .identity(Identity::from_pem(CERT, KEY))
+        .build("https://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+}
+
+#[cfg(feature = "native-tls")]
+#[tokio::test]
+async fn identity_pkcs12() {
+    let der = include_bytes!("data/client.p12");
+    let client = RestClient::builder()
+        .identity(Identity::from_pkcs12(der, "secret"))
+        .build("https://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+}
+
+#[tokio::test]
+async fn invalid_identity() {
+    let result = RestClient::builder()
+        .identity(Identity::from_pem(CERT, b"invalid"))
+        .build("https://httpbin.org");
+
+    match result {
+        Err(Error::HttpClientError) => (),
+        _ => panic!("Expected HttpClientError"),
+    };
+}
+
+#[tokio::test]
+async fn tls_version() {
+    let client = RestClient::builder()
+        .min_tls_version(TlsVersion::Tls1_3)
+        .build("https://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+}
+
+#[tokio::test]
+async fn invalid_tls_version() {
+    let result = RestClient::builder()
+        .min_tls_version(TlsVersion::Tls1_3)
+        .max_tls_version(TlsVersion::Tls1_2)
+        .build("https://httpbin.org");
+
+    match result {
+        Err(Error::HttpClientError) => (),
+        _ => panic!("Expected HttpClientError"),
+    };
+}
+
+#[cfg(feature = "rustls")]
+#[tokio::test]
+async fn invalid_cipher_suite() {
+    let result = RestClient::builder()
+        .tls_cipher_suites(&["TLS13_AES_256_GCM_SHA384", "TLS_RSA_WITH_RC4_128_MD5"])
+        .build("https://httpbin.org");
+
+    match result {
+        Err(Error::HttpClientError) => (),
+        _ => panic!("Expected HttpClientError"),
+    };
+}
+
+#[tokio::test]
+async fn https_only() {
+    match RestClient::builder().https_only(true).build("http://httpbin.org") {
+        Err(Error::InsecureUrl) => (),
+        _ => panic!("Expected InsecureUrl"),
+    };
+
+    let client = RestClient::builder()
+        .https_only(true)
+        .redirect(RedirectPolicy::default())
+        .build("https://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).await.unwrap();
+    assert_eq!(data.url, "https://httpbin.org/anything");
+
+    match client.get::<_, HttpBinAnything>("http://httpbin.org/anything").await {
+        Err(Error::InsecureUrl) => (),
+        _ => panic!("Expected InsecureUrl"),
+    };
+
+    // Redirect from HTTPS to plain HTTP is refused
+    let query = [("url", "http://httpbin.org/anything")];
+    match client.get_with::<_, HttpBinAnything>("redirect-to", &query).await {
+        Err(Error::InsecureUrl) => (),
+        _ => panic!("Expected InsecureUrl"),
+    };
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "tower")]
+
+use hyper::header::HeaderValue;
+use hyper::{Body, Request};
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+use tower::util::MapRequestLayer;
+
+#[derive(Deserialize)]
+struct HttpBinAnything {
+    headers: TestHeaders,
+}
+
+#[derive(Deserialize)]
+struct TestHeaders {
+    #[serde(default)]
+    #[serde(rename = "X-Test")]
+    test: String,
+}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+fn set_header(mut req: Request<Body>) -> Request<Body> {
+    req.headers_mut().insert("X-Test", HeaderValue::from_static("tower"));
+    req
+}
+
+#[test]
+fn tower_layer() {
+    let client = RestClient::builder()
+        .layer(MapRequestLayer::new(set_header))
+        .blocking("http://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).unwrap();
+    assert_eq!(data.headers.test, "tower");
+}
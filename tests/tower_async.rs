@@ -0,0 +1,75 @@
+#![cfg(feature = "tower")]
+
+use hyper::header::HeaderValue;
+use hyper::{Body, Request};
+use restson::{Error, RestClient, RestPath};
+use serde_derive::Deserialize;
+use std::time::Duration;
+use tower::timeout::TimeoutLayer;
+use tower::util::MapRequestLayer;
+use tower::ServiceExt;
+
+#[derive(Deserialize)]
+struct HttpBinAnything {
+    headers: TestHeaders,
+}
+
+#[derive(Deserialize)]
+struct TestHeaders {
+    #[serde(default)]
+    #[serde(rename = "X-Test")]
+    test: String,
+}
+
+impl RestPath<()> for HttpBinAnything {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("anything"))
+    }
+}
+
+#[derive(Deserialize)]
+struct HttpBinDelay {}
+
+impl RestPath<u16> for HttpBinDelay {
+    fn get_path(delay: u16) -> Result<String, Error> {
+        Ok(format!("delay/{}", delay))
+    }
+}
+
+fn set_header(mut req: Request<Body>) -> Request<Body> {
+    req.headers_mut().insert("X-Test", HeaderValue::from_static("tower"));
+    req
+}
+
+#[tokio::test]
+async fn tower_layer() {
+    let client = RestClient::builder()
+        .layer(MapRequestLayer::new(set_header))
+        .build("http://httpbin.org")
+        .unwrap();
+
+    let data = client.get::<_, HttpBinAnything>(()).await.unwrap();
+    assert_eq!(data.headers.test, "tower");
+}
+
+#[tokio::test]
+async fn tower_layer_error() {
+    let client = RestClient::builder()
+        .layer(TimeoutLayer::new(Duration::from_millis(500)))
+        .build("http://httpbin.org")
+        .unwrap();
+
+    match client.get::<_, HttpBinDelay>(2).await {
+        Err(Error::TimeoutError) => (),
+        _ => panic!("Expected timeout error"),
+    };
+}
+
+#[tokio::test]
+async fn tower_service() {
+    let client = RestClient::new("http://httpbin.org").unwrap();
+
+    let req = Request::get("http://httpbin.org/anything").body(Body::empty()).unwrap();
+    let res = client.oneshot(req).await.unwrap();
+    assert_eq!(res.status(), 200);
+}
@@ -0,0 +1,97 @@
+use futures::future::BoxFuture;
+use hyper::{Body, Request, Response, StatusCode};
+use restson::{Error, RestClient, RestPath, Transport};
+use serde_derive::Deserialize;
+use std::sync::{Arc, Mutex};
+
+#[derive(Deserialize)]
+struct Device {
+    id: u32,
+    name: String,
+}
+
+impl RestPath<u32> for Device {
+    fn get_path(id: u32) -> Result<String, Error> {
+        Ok(format!("devices/{}", id))
+    }
+}
+
+/// Transport that returns the same response to all requests and keeps the
+/// method, URL and user agent of the requests
+struct Canned {
+    status: StatusCode,
+    body: &'static str,
+    requests: Arc<Mutex<Vec<(String, String, String)>>>,
+}
+
+impl Canned {
+    fn new(status: StatusCode, body: &'static str) -> Self {
+        Self {
+            status,
+            body,
+            requests: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+}
+
+impl Transport for Canned {
+    fn send<'a>(&'a self, req: Request<Body>) -> BoxFuture<'a, Result<Response<Body>, Error>> {
+        let agent = req.headers()["user-agent"].to_str().unwrap().to_owned();
+        self.requests
+            .lock()
+            .unwrap()
+            .push((req.method().to_string(), req.uri().to_string(), agent));
+
+        let res = Response::builder().status(self.status).body(Body::from(self.body)).unwrap();
+        Box::pin(async { Ok(res) })
+    }
+}
+
+/// Transport that fails all requests
+struct Failing;
+
+impl Transport for Failing {
+    fn send<'a>(&'a self, _req: Request<Body>) -> BoxFuture<'a, Result<Response<Body>, Error>> {
+        Box::pin(async { Err(Error::IoError(std::io::ErrorKind::ConnectionRefused.into())) })
+    }
+}
+
+#[tokio::test]
+async fn transport_response() {
+    let transport = Canned::new(StatusCode::OK, r#"{"id": 1, "name": "sensor"}"#);
+    let requests = transport.requests.clone();
+    let client = RestClient::builder().transport(transport).build("http://api.test").unwrap();
+
+    let device = client.get::<_, Device>(1).await.unwrap().into_inner();
+    assert_eq!(device.id, 1);
+    assert_eq!(device.name, "sensor");
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].0, "GET");
+    assert_eq!(requests[0].1, "http://api.test/devices/1");
+    assert!(requests[0].2.starts_with("restson/"));
+}
+
+#[tokio::test]
+async fn transport_error_status() {
+    let client = RestClient::builder()
+        .transport(Canned::new(StatusCode::NOT_FOUND, "not found"))
+        .build("http://api.test")
+        .unwrap();
+
+    match client.get::<_, Device>(1).await {
+        Err(Error::HttpError(404, body, _)) => assert_eq!(body, "not found"),
+        _ => panic!("Expected 404 error status"),
+    };
+}
+
+#[tokio::test]
+async fn transport_error() {
+    let client = RestClient::builder().transport(Failing).build("http://api.test").unwrap();
+
+    match client.get::<_, Device>(1).await {
+        Err(Error::IoError(err)) => assert_eq!(err.kind(), std::io::ErrorKind::ConnectionRefused),
+        _ => panic!("Expected IO error"),
+    };
+}
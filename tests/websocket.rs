@@ -0,0 +1,78 @@
+#![cfg(feature = "websocket")]
+
+use restson::{Error, RestClient, RestPath};
+use serde_derive::{Deserialize, Serialize};
+use std::net::TcpListener;
+use std::thread;
+use tokio_tungstenite::tungstenite::handshake::server::{Request, Response};
+use tokio_tungstenite::tungstenite::{self, Message};
+
+mod common;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Chat {
+    text: String,
+}
+
+impl RestPath<()> for Chat {
+    fn get_path(_: ()) -> Result<String, Error> {
+        Ok(String::from("chat"))
+    }
+}
+
+/// Serve WebSocket connections on localhost and return the port. The server
+/// first sends the `Authorization` header of the request, and then echoes the
+/// messages in uppercase.
+fn serve() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut auth = String::new();
+            #[allow(clippy::result_large_err)]
+            let callback = |req: &Request, res: Response| {
+                if let Some(value) = req.headers().get("authorization") {
+                    auth = value.to_str().unwrap().to_owned();
+                }
+                Ok(res)
+            };
+            let mut ws = tungstenite::accept_hdr(stream.unwrap(), callback).unwrap();
+            ws.send(Message::Text(format!(r#"{{"text": "{}"}}"#, auth))).unwrap();
+            while let Ok(msg) = ws.read() {
+                if let Message::Text(text) = msg {
+                    ws.send(Message::Text(text.to_uppercase().replace("TEXT", "text"))).unwrap();
+                }
+            }
+        }
+    });
+    port
+}
+
+#[test]
+fn websocket_messages() {
+    let port = serve();
+    let client = RestClient::builder().auth_bearer("token").blocking(&format!("http://127.0.0.1:{}", port)).unwrap();
+
+    let mut ws = client.websocket::<_, Chat, Chat>(()).unwrap();
+    assert_eq!(ws.next().unwrap().unwrap().text, "Bearer token");
+
+    ws.send(Chat { text: String::from("hello") }).unwrap();
+    assert_eq!(ws.next().unwrap().unwrap().text, "HELLO");
+    ws.send(Chat { text: String::from("world") }).unwrap();
+    assert_eq!(ws.next().unwrap().unwrap().text, "WORLD");
+
+    ws.close().unwrap();
+    assert!(ws.next().is_none());
+}
+
+#[test]
+fn websocket_rejected() {
+    let server = common::serve_responses(&["HTTP/1.1 404 Not Found\r\nContent-Length: 9\r\n\r\nNot Found"]);
+    let client = RestClient::new_blocking(&server.url).unwrap();
+
+    match client.websocket::<_, Chat, Chat>(()) {
+        Err(Error::HttpError(404, body, _)) => assert_eq!(body, "Not Found"),
+        _ => panic!("Expected 404 error status"),
+    };
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "yaml")]
+
+use hyper::Method;
+use restson::mock::MockClient;
+use restson::{Error, RestClient, RestPath, Yaml};
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+struct ServiceConfig {
+    name: String,
+    replicas: u32,
+    hosts: Vec<String>,
+}
+
+impl RestPath<&str> for ServiceConfig {
+    fn get_path(name: &str) -> Result<String, Error> {
+        Ok(format!("config/{}", name))
+    }
+}
+
+#[tokio::test]
+async fn yaml_response() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/config/billing")
+        .respond_header("Content-Type", "application/yaml")
+        .respond(200, "name: billing\nreplicas: 3\nhosts:\n  - a.internal\n  - b.internal\n");
+    let client = RestClient::builder().transport(mock.clone()).body_format(Yaml).build("http://api.test").unwrap();
+
+    let config = client.get::<_, ServiceConfig>("billing").await.unwrap().into_inner();
+    assert_eq!(config.name, "billing");
+    assert_eq!(config.replicas, 3);
+    assert_eq!(config.hosts, ["a.internal", "b.internal"]);
+}
+
+#[tokio::test]
+async fn yaml_request_body() {
+    let mock = MockClient::new();
+    mock.when(Method::PUT, "/config/billing")
+        .header("content-type", "application/yaml")
+        .body("name: billing\nreplicas: 2\nhosts:\n- a.internal\n")
+        .times(1)
+        .respond(204, "");
+    let client = RestClient::builder().transport(mock.clone()).body_format(Yaml).build("http://api.test").unwrap();
+
+    let config = ServiceConfig { name: String::from("billing"), replicas: 2, hosts: vec![String::from("a.internal")] };
+    client.put("billing", &config).await.unwrap();
+    mock.verify();
+}
+
+#[tokio::test]
+async fn yaml_invalid_response() {
+    let mock = MockClient::new();
+    mock.when(Method::GET, "/config/billing").respond(200, "name: [billing");
+    let client = RestClient::builder().transport(mock.clone()).body_format(Yaml).build("http://api.test").unwrap();
+
+    match client.get::<_, ServiceConfig>("billing").await {
+        Err(Error::DeserializeParseError(_, body)) => assert_eq!(body, "name: [billing"),
+        _ => panic!("Expected deserialize error"),
+    };
+}